@@ -22,7 +22,7 @@ fn main() -> Result<(), core::convert::Infallible> {
     let curve_list = [(curve, BinaryColor::On)];
     let plot = SinglePlot::new(&curve_list, Scale::RangeFraction(3), Scale::RangeFraction(2))
         .into_drawable(Point { x: 18, y: 2 }, Point { x: 120, y: 30 })
-        .set_color(BinaryColor::On);
+        .with_default_style();
 
     plot.draw(&mut display)?;
 
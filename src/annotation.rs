@@ -0,0 +1,184 @@
+//! Text annotations anchored at data coordinates
+use embedded_graphics::{
+    mono_font::{ascii::FONT_5X8, MonoTextStyle},
+    prelude::*,
+    primitives::{Line, PrimitiveStyle},
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+
+use crate::curve::PlotPoint;
+use crate::transform::PlotTransform;
+
+/// Display-agnostic annotation, label attached to a point in data space
+#[derive(Clone, Copy)]
+pub struct Annotation<'a> {
+    point: PlotPoint,
+    label: &'a str,
+    /// shift of the label from the anchor point, in pixels
+    offset: Point,
+    leader_line: bool,
+}
+
+/// builder methods to modify annotation
+impl<'a> Annotation<'a> {
+    /// create annotation with label placed right next to the point
+    pub fn new(point: PlotPoint, label: &'a str) -> Annotation<'a> {
+        Annotation {
+            point,
+            label,
+            offset: Point { x: 3, y: -3 },
+            leader_line: false,
+        }
+    }
+
+    /// set how far the label is placed from the anchor point, in pixels
+    pub fn set_offset(mut self, offset: Point) -> Annotation<'a> {
+        self.offset = offset;
+        self
+    }
+
+    /// draw line connecting anchor point with the label
+    pub fn set_leader_line(mut self, enabled: bool) -> Annotation<'a> {
+        self.leader_line = enabled;
+        self
+    }
+
+    /// turn annotation into drawable object, placed using plot's coordinate mapping
+    pub fn into_drawable<C>(self, transform: &PlotTransform) -> DrawableAnnotation<'a, C>
+    where
+        C: PixelColor + Default,
+    {
+        DrawableAnnotation {
+            anchor: transform.to_screen(&self.point),
+            annotation: self,
+            color: None,
+            text_style: None,
+        }
+    }
+}
+
+/// Drawable annotation, constructed for specific display
+pub struct DrawableAnnotation<'a, C>
+where
+    C: PixelColor + Default,
+{
+    annotation: Annotation<'a>,
+    /// screen position of annotated point
+    anchor: Point,
+    color: Option<C>,
+    text_style: Option<MonoTextStyle<'a, C>>,
+}
+
+/// builder methods to modify annotation decoration
+impl<'a, C> DrawableAnnotation<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// set color of the leader line, also used for the label if text style is not set
+    pub fn set_color(mut self, color: C) -> DrawableAnnotation<'a, C> {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn set_text_style(mut self, style: MonoTextStyle<'a, C>) -> DrawableAnnotation<'a, C> {
+        self.text_style = Some(style);
+        self
+    }
+}
+
+impl<'a, C> Drawable for DrawableAnnotation<'a, C>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<(), D::Error> {
+        let color = self.color.unwrap_or_default();
+        let character_style = self
+            .text_style
+            .unwrap_or_else(|| MonoTextStyle::new(&FONT_5X8, color));
+        let label_position = self.anchor + self.annotation.offset;
+
+        if self.annotation.leader_line {
+            Line::new(self.anchor, label_position)
+                .into_styled(PrimitiveStyle::with_stroke(color, 1))
+                .draw(display)?;
+        }
+
+        // label grows away from the anchor, so it never covers the annotated point
+        let alignment = if self.annotation.offset.x < 0 {
+            Alignment::Right
+        } else {
+            Alignment::Left
+        };
+        let text_style = TextStyleBuilder::new()
+            .alignment(alignment)
+            .baseline(Baseline::Middle)
+            .build();
+        Text::with_text_style(
+            self.annotation.label,
+            label_position,
+            character_style,
+            text_style,
+        )
+        .draw(display)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::annotation::Annotation;
+    use crate::curve::PlotPoint;
+    use crate::transform::PlotTransform;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::*};
+
+    const POINT: PlotPoint = PlotPoint { x: 5, y: 5 };
+
+    fn transform() -> PlotTransform {
+        PlotTransform::new(0..10, 0..10, Point::zero(), Point::new(60, 60)).unwrap()
+    }
+
+    #[test]
+    fn label_grows_away_from_point() {
+        let anchor = transform().to_screen(&POINT);
+        let mut right = MockDisplay::new();
+        Annotation::new(POINT, "up")
+            .into_drawable(&transform())
+            .set_color(BinaryColor::On)
+            .draw(&mut right)
+            .unwrap();
+        assert_eq!(right.get_pixel(anchor), None);
+        assert!(right.affected_area().top_left.x >= anchor.x + 3);
+
+        let mut left = MockDisplay::new();
+        Annotation::new(POINT, "up")
+            .set_offset(Point::new(-3, 0))
+            .into_drawable(&transform())
+            .set_color(BinaryColor::On)
+            .draw(&mut left)
+            .unwrap();
+        let area = left.affected_area();
+        assert!(area.top_left.x + area.size.width as i32 <= anchor.x - 2);
+    }
+
+    #[test]
+    fn leader_line_reaches_point() {
+        let anchor = transform().to_screen(&POINT);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        Annotation::new(POINT, "peak")
+            .set_offset(Point::new(10, -10))
+            .set_leader_line(true)
+            .into_drawable(&transform())
+            .set_color(BinaryColor::On)
+            .draw(&mut display)
+            .unwrap();
+        assert_eq!(display.get_pixel(anchor), Some(BinaryColor::On));
+        assert_eq!(
+            display.get_pixel(anchor + Point::new(5, -5)),
+            Some(BinaryColor::On)
+        );
+    }
+}
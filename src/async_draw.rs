@@ -0,0 +1,102 @@
+//! Async drawing, available with `async` feature
+//!
+//! Drawing of the plot is split into chunks that yield to the executor in between, so tasks of
+//! async runtimes (e.g. embassy) driving the display keep running while a long plot is rendered.
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use embedded_graphics::{draw_target::DrawTarget, pixelcolor::PixelColor, Drawable};
+
+use crate::error::PlotError;
+use crate::single_plot::{DrawState, DrawableSinglePlot};
+
+/// future that is pending once, letting the executor run other tasks
+struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            Poll::Ready(())
+        } else {
+            self.yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+/// give other tasks a chance to run
+pub async fn yield_now() {
+    YieldNow { yielded: false }.await
+}
+
+/// draw any drawable - axis, curve or decoration - and yield afterwards, so drawing several
+/// objects in a row doesn't starve other tasks
+pub async fn draw_async<T, D>(drawable: &T, display: &mut D) -> Result<T::Output, D::Error>
+where
+    T: Drawable,
+    D: DrawTarget<Color = T::Color>,
+{
+    let output = drawable.draw(display)?;
+    yield_now().await;
+    Ok(output)
+}
+
+impl<'a, C> DrawableSinglePlot<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// draw the whole plot, yielding after the decoration and after every `max_segments` line segments
+    /// of the curves, see [DrawableSinglePlot::draw_some]
+    pub async fn draw_async<D>(
+        &self,
+        display: &mut D,
+        max_segments: usize,
+    ) -> Result<(), PlotError<D::Error>>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let layout = self.layout();
+        let mut state = DrawState::new();
+        while self
+            .draw_some(&layout, &mut state, display, max_segments)?
+            .is_pending()
+        {
+            yield_now().await;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::async_draw::yield_now;
+    use core::future::Future;
+    use core::pin::pin;
+    use core::ptr;
+    use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    fn noop_waker() -> Waker {
+        const VTABLE: RawWakerVTable = RawWakerVTable::new(
+            |_| RawWaker::new(ptr::null(), &VTABLE),
+            |_| {},
+            |_| {},
+            |_| {},
+        );
+        unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+    }
+
+    #[test]
+    fn yields_once() {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(yield_now());
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(future.as_mut().poll(&mut cx), Poll::Ready(()));
+    }
+}
@@ -1,19 +1,33 @@
+#[cfg(feature = "text")]
+use core::convert::TryFrom;
 use core::{fmt::Write, ops::Range};
+#[cfg(feature = "text")]
 use heapless::String;
 
 use embedded_graphics::{
     prelude::*,
-    primitives::{Line, PrimitiveStyle},
-    text::Text,
-    text::TextStyle,
+    primitives::{Line, PrimitiveStyle, Rectangle, Triangle},
+    text::renderer::{CharacterStyle, TextRenderer},
+    transform::Transform,
 };
 
+use crate::dirty::{drawn_area, DirtyTracker};
 use crate::range_conv::Scalable;
-use embedded_graphics::mono_font::ascii::FONT_5X8;
-use embedded_graphics::mono_font::MonoTextStyle;
-use embedded_graphics::text::{Alignment, Baseline, TextStyleBuilder};
+use crate::style::{draw_line_runs, draw_patterned_line, LinePattern};
+use embedded_graphics::mono_font::{ascii::FONT_5X8, MonoFont, MonoTextStyle};
+#[cfg(feature = "text")]
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+#[cfg(feature = "wrapped-titles")]
+use embedded_text::{
+    alignment::HorizontalAlignment,
+    style::{TextBoxStyle, TextBoxStyleBuilder},
+    TextBox,
+};
 
 /// Used to provide alignment of an axis, it will be dsizerown exactly on the line marked by the points
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Placement {
     X { x1: i32, x2: i32, y: i32 },
     Y { y1: i32, y2: i32, x: i32 },
@@ -21,32 +35,204 @@ pub enum Placement {
 
 /// Used to describe how densely ticks should be drawn
 #[derive(Clone, Copy)]
-pub enum Scale {
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Scale<'a> {
     /// Fixed scale means that ticks will be drawn between each increment of absolute distance provided.
     /// for example, on range 0..30 and Fixed(10), ticks will be drawn for 0, 10 and 20
     Fixed(usize),
     /// RangeFraction means that provided number of ticks ticks will be drawn on entire range
     /// for example, on range 0..60 and RangeFraction(3), ticks will be drawn for 0, 20 and 40
     RangeFraction(usize),
+    /// Auto scale picks step of 1, 2 or 5 times power of 10 so that at most 5 ticks are drawn
+    /// and labels land on round values, for example, on range 3..97 ticks will be drawn for 20, 40, 60 and 80
+    Auto,
+    /// Custom scale places ticks exactly at the provided values, labeled with the provided text instead of numbers,
+    /// for example, on range 0..1 and Custom(&[(0, "off"), (1, "on")]), ticks "off" and "on" will be drawn at both ends.
+    /// Ticks outside of the range are skipped, labels longer than label capacity of the axis are truncated.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(&'a [(i32, &'a str)]),
+}
+
+/// maximum number of ticks drawn with [Scale::Auto]
+const AUTO_SCALE_TICKS: u64 = 5;
+
+/// smallest "nice" step (1, 2 or 5 times power of 10) that is not smaller than `raw_step`
+fn nice_step(raw_step: u64) -> u64 {
+    let mut magnitude = 1;
+    while magnitude * 10 <= raw_step {
+        magnitude *= 10;
+    }
+    [1, 2, 5, 10]
+        .iter()
+        .map(|m| m * magnitude)
+        .find(|step| *step >= raw_step)
+        .unwrap_or(10 * magnitude)
 }
 
-impl Default for Scale {
+impl Default for Scale<'_> {
     fn default() -> Self {
         Scale::RangeFraction(5)
     }
 }
 
-/// Display-agnostic axis object, only contains scale range and title, can be converted to drawable axis for specific display
-pub struct Axis<'a> {
+/// Used to describe how axis title is laid out
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TitleOrientation {
+    /// title is written as a single line of text
+    Horizontal,
+    /// characters of the title are stacked one under another, which saves horizontal space
+    /// taken by Y axis on narrow displays. Applies only to vertical axes.
+    Vertical,
+}
+
+impl Default for TitleOrientation {
+    fn default() -> Self {
+        TitleOrientation::Horizontal
+    }
+}
+
+/// Side of the vertical axis on which tick labels are drawn
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TickLabelSide {
+    /// labels are drawn outside of the plot, to the left of the axis
+    Left,
+    /// labels are drawn inside of the plot, to the right of the axis, which takes no horizontal space
+    /// next to it - handy on narrow displays. Applies only to vertical axes.
+    Right,
+}
+
+impl Default for TickLabelSide {
+    fn default() -> Self {
+        TickLabelSide::Left
+    }
+}
+
+/// Where tick labels of horizontal axis are drawn relative to their ticks
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TickLabelPlacement {
+    /// labels are drawn below the axis line, starting right of the tick
+    BelowRight,
+    /// labels are drawn below the axis line, centered on the tick
+    BelowCentered,
+    /// labels are drawn below the axis line, ending left of the tick, so the last label doesn't run off
+    /// the right edge of the display
+    BelowLeft,
+    /// labels are drawn above the axis line inside the plot, centered on the tick, which takes no room
+    /// below the axis
+    Above,
+}
+
+impl Default for TickLabelPlacement {
+    fn default() -> Self {
+        TickLabelPlacement::BelowRight
+    }
+}
+
+/// Which side of the axis line ticks are drawn on
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TickDirection {
+    /// ticks straddle the axis line
+    Cross,
+    /// ticks are drawn only inside the plot area - above horizontal axis, to the right of vertical one,
+    /// e.g. when the axis lies at the display edge
+    Inward,
+    /// ticks are drawn only outside the plot area, keeping it clear for the curves
+    Outward,
+}
+
+impl Default for TickDirection {
+    fn default() -> Self {
+        TickDirection::Cross
+    }
+}
+
+impl TickLabelSide {
+    /// side used for labels in the second unit
+    #[cfg(feature = "text")]
+    pub(crate) fn opposite(self) -> TickLabelSide {
+        match self {
+            TickLabelSide::Left => TickLabelSide::Right,
+            TickLabelSide::Right => TickLabelSide::Left,
+        }
+    }
+}
+
+impl TickDirection {
+    /// how far ticks of given size reach outside and inside of the plot area
+    pub(crate) fn reach(self, size: usize) -> (usize, usize) {
+        match self {
+            TickDirection::Cross => (size, size),
+            TickDirection::Inward => (0, size),
+            TickDirection::Outward => (size, 0),
+        }
+    }
+}
+
+/// Text of the tick label, characters beyond capacity of the buffer are dropped
+#[cfg(feature = "text")]
+struct Label<const L: usize> {
+    text: String<L>,
+    truncated: bool,
+}
+
+#[cfg(feature = "text")]
+impl<const L: usize> Label<L> {
+    fn new() -> Label<L> {
+        Label {
+            text: String::new(),
+            truncated: false,
+        }
+    }
+}
+
+#[cfg(feature = "text")]
+impl<const L: usize> Write for Label<L> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for c in s.chars() {
+            if self.text.push(c).is_err() {
+                self.truncated = true;
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Function writing tick label for the scale mark, e.g. to show raw fixed point values as fractions
+pub type LabelFormatter = fn(i32, &mut dyn Write) -> core::fmt::Result;
+
+/// Display-agnostic axis object, only contains scale range and title, can be converted to drawable axis for specific display.
+/// Tick labels are formatted into buffer of `L` characters, see [Axis::set_label_capacity].
+#[derive(Clone)]
+pub struct Axis<'a, const L: usize = 11> {
     /// range that the scale will be drawn for
     range: Range<i32>,
     /// axis title displayed right next to it
     title: Option<&'a str>,
     /// Definition on how scale ticks should be drawn
-    scale: Option<Scale>,
+    scale: Option<Scale<'a>>,
+    /// how scale marks are turned into tick labels
+    label_formatter: Option<LabelFormatter>,
+    /// names of the categories, placed at their indices on the axis
+    categories: Option<&'a [&'a str]>,
+    /// ticks labeled in the second row of horizontal axis, e.g. hours under minutes
+    major_scale: Option<Scale<'a>>,
+    major_label_formatter: Option<LabelFormatter>,
+    /// ticks labeled also in second unit on the other side of the axis line, e.g. °F next to °C
+    secondary_label_formatter: Option<LabelFormatter>,
+    /// range start and end are always marked, even if the step skips them
+    end_marks: bool,
 }
 
-/// builder methods to modify axis decoration
 impl<'a> Axis<'a> {
     /// create new axis data
     pub fn new(range: Range<i32>) -> Axis<'a> {
@@ -54,153 +240,791 @@ impl<'a> Axis<'a> {
             range,
             title: None,
             scale: None,
+            label_formatter: None,
+            categories: None,
+            major_scale: None,
+            major_label_formatter: None,
+            secondary_label_formatter: None,
+            end_marks: false,
+        }
+    }
+}
+
+/// builder methods to modify axis decoration
+impl<'a, const L: usize> Axis<'a, L> {
+    /// set how many characters fit in the tick label, longer labels are truncated - 11 if not set,
+    /// enough for any i32. Labels are formatted on the stack, so short labels can save some of it.
+    pub fn set_label_capacity<const N: usize>(self) -> Axis<'a, N> {
+        Axis {
+            range: self.range,
+            title: self.title,
+            scale: self.scale,
+            label_formatter: self.label_formatter,
+            categories: self.categories,
+            major_scale: self.major_scale,
+            major_label_formatter: self.major_label_formatter,
+            secondary_label_formatter: self.secondary_label_formatter,
+            end_marks: self.end_marks,
         }
     }
 
     /// define how scale ticks should be drawn
-    pub fn set_scale(mut self, scale: Scale) -> Axis<'a> {
+    pub fn set_scale(mut self, scale: Scale<'a>) -> Axis<'a, L> {
         self.scale = Some(scale);
         self
     }
 
-    /// set axis title
-    pub fn set_title(mut self, title: &'a str) -> Axis<'a> {
+    /// set axis title. With `wrapped-titles` feature, outside title of horizontal axis is wrapped
+    /// within the axis width, titles of vertical axes and titles drawn inside are always a single line.
+    pub fn set_title(mut self, title: &'a str) -> Axis<'a, L> {
         self.title = Some(title);
         self
     }
 
+    /// set how scale marks are turned into tick labels, if not set, marks are printed as integers
+    pub fn set_label_formatter(mut self, formatter: LabelFormatter) -> Axis<'a, L> {
+        self.label_formatter = Some(formatter);
+        self
+    }
+
+    /// turn the axis into categorical one - category N is placed at value N and ticks are labeled
+    /// with category names instead of numbers, e.g. weekdays or channel names.
+    /// Ticks are drawn for every category within the range, scale is not used.
+    /// Names longer than label capacity are truncated.
+    pub fn set_categories(mut self, categories: &'a [&'a str]) -> Axis<'a, L> {
+        self.categories = Some(categories);
+        self
+    }
+
+    /// add second level of ticks labeled in another row below the regular tick labels,
+    /// e.g. hours on major ticks under minutes on the regular ones. Horizontal axis reserves
+    /// room for both rows, vertical axes draw major ticks without the labels.
+    pub fn set_major_scale(mut self, scale: Scale<'a>) -> Axis<'a, L> {
+        self.major_scale = Some(scale);
+        self
+    }
+
+    /// set how major ticks are turned into labels, if not set, regular label formatter is used
+    pub fn set_major_label_formatter(mut self, formatter: LabelFormatter) -> Axis<'a, L> {
+        self.major_label_formatter = Some(formatter);
+        self
+    }
+
+    /// label the ticks also in second unit, on the other side of the axis line than the regular labels -
+    /// above horizontal axis, unless they are moved above it. Formatter gets the scale mark
+    /// and writes it converted, e.g. `|c, f| write!(f, "{}", c * 9 / 5 + 32)` for °F next to °C.
+    pub fn set_secondary_label_formatter(mut self, formatter: LabelFormatter) -> Axis<'a, L> {
+        self.secondary_label_formatter = Some(formatter);
+        self
+    }
+
+    /// axis of the major ticks, marks and labels of which are produced the same way as the regular ones
+    fn major_axis(&self) -> Option<Axis<'a, L>> {
+        let scale = self.major_scale?;
+        Some(Axis {
+            range: self.range.clone(),
+            title: None,
+            scale: Some(scale),
+            label_formatter: self.major_label_formatter.or(self.label_formatter),
+            categories: None,
+            major_scale: None,
+            major_label_formatter: None,
+            secondary_label_formatter: None,
+            end_marks: false,
+        })
+    }
+
+    /// tick label text for the scale mark
+    #[cfg(feature = "text")]
+    fn format_mark(&self, mark: i32) -> Label<L> {
+        let mut label = Label::new();
+        if let Some(categories) = self.categories {
+            let name = usize::try_from(mark)
+                .ok()
+                .and_then(|index| categories.get(index))
+                .unwrap_or(&"");
+            label.write_str(name).ok();
+        } else if let Some(Scale::Custom(ticks)) = self.scale {
+            let text = ticks
+                .iter()
+                .find(|(value, _)| *value == mark)
+                .map_or("", |(_, text)| *text);
+            label.write_str(text).ok();
+        } else {
+            match self.label_formatter {
+                Some(formatter) => formatter(mark, &mut label).ok(),
+                None => write!(label, "{}", mark).ok(),
+            };
+        }
+        label
+    }
+
+    /// tick label text for the scale mark in the second unit, none without secondary labels
+    #[cfg(feature = "text")]
+    fn format_secondary(&self, mark: i32) -> Option<Label<L>> {
+        let formatter = self.secondary_label_formatter?;
+        let mut label = Label::new();
+        formatter(mark, &mut label).ok();
+        Some(label)
+    }
+
+    /// check that all tick labels fit in the label capacity, so none of them is truncated when drawn
+    #[cfg(feature = "text")]
+    pub fn check_labels(&self) -> Result<(), &'static str> {
+        let truncated = |axis: &Axis<'a, L>| {
+            axis.scale_marks().any(|mark| {
+                let secondary = axis.format_secondary(mark);
+                axis.format_mark(mark).truncated || secondary.iter().any(|label| label.truncated)
+            })
+        };
+        if truncated(self) || self.major_axis().iter().any(truncated) {
+            Err("Tick label exceeds label capacity")
+        } else {
+            Ok(())
+        }
+    }
+
+    /// flip the direction of the axis, e.g. to have depth or pressure increasing downwards.
+    /// Descending range passed to [Axis::new] has the same effect.
+    pub fn set_inverted(mut self) -> Axis<'a, L> {
+        self.range = self.range.end..self.range.start;
+        self
+    }
+
+    /// always put ticks and labels at the start and the end of the range, so the plot shows its bounds
+    /// even if the step of the scale skips them. Labels of neighbouring ticks may overlap the end ones.
+    /// Categories and custom ticks are not affected.
+    pub fn set_end_marks(mut self) -> Axis<'a, L> {
+        self.end_marks = true;
+        self
+    }
+
+    /// values at which ticks are drawn, going from the start of the range towards its end,
+    /// custom ticks are kept in the given order
+    fn scale_marks(&self) -> impl Iterator<Item = i32> + 'a {
+        let Range { start, end } = self.range;
+        let (start, end) = (start as i64, end as i64);
+        let len = (end - start).unsigned_abs();
+        let categories = self.categories.map(|categories| categories.len() as i64);
+        let scale = match categories {
+            Some(_) => Scale::Fixed(1),
+            None => self.scale.unwrap_or_default(),
+        };
+        let (step, aligned) = match scale {
+            Scale::Fixed(interval) => (interval as u64, false),
+            Scale::RangeFraction(fraction) => (len / fraction.max(1) as u64, false),
+            Scale::Auto => (nice_step(len / AUTO_SCALE_TICKS), true),
+            Scale::Custom(_) => (1, false),
+        };
+        let step = step.max(1) as i64;
+        let direction = if start <= end { 1 } else { -1 };
+        let mut first = start;
+        if aligned {
+            // move to the first multiple of the step, so labels land on round values
+            let remainder = first.rem_euclid(step);
+            if remainder != 0 {
+                first += if direction > 0 {
+                    step - remainder
+                } else {
+                    -remainder
+                };
+            }
+        }
+        let (count, custom) = match scale {
+            Scale::Custom(ticks) => (0, ticks),
+            _ => (
+                (((end - first) * direction + step - 1) / step).max(0),
+                &[][..],
+            ),
+        };
+        let (low, high) = (start.min(end), start.max(end));
+        let ends = self.end_marks && categories.is_none() && !matches!(scale, Scale::Custom(_));
+        let last = first + (count - 1) * step * direction;
+        let head = if ends && (count == 0 || first != start) {
+            Some(start)
+        } else {
+            None
+        };
+        let tail = if ends && (count == 0 || last != end) && end != start {
+            Some(end)
+        } else {
+            None
+        };
+        head.into_iter()
+            .chain((0..count).map(move |i| first + i * step * direction))
+            .filter(move |mark| match categories {
+                Some(len) => (0..len).contains(mark),
+                None => true,
+            })
+            .chain(
+                custom
+                    .iter()
+                    .map(|(mark, _)| *mark as i64)
+                    .filter(move |mark| (low..=high).contains(mark)),
+            )
+            .chain(tail)
+            .map(|mark| mark as i32)
+    }
+
     /// turn axis data into drawable object suitable for specific display
-    pub fn into_drawable_axis<C>(self, placement: Placement) -> DrawableAxis<'a, C>
+    pub fn into_drawable_axis<C>(self, placement: Placement) -> DrawableAxis<'a, C, L>
+    where
+        C: PixelColor + Default,
+    {
+        self.drawable(placement, None)
+    }
+
+    /// turn axis data into drawable object drawing its text with given [AxisTextStyle],
+    /// e.g. BDF or U8g2 font style for localized or larger labels
+    pub fn into_drawable_axis_with_renderer<C, S>(
+        self,
+        placement: Placement,
+        renderer: S,
+    ) -> DrawableAxis<'a, C, L, S>
+    where
+        C: PixelColor + Default,
+        S: AxisTextStyle<'a, C>,
+    {
+        self.drawable(placement, Some(renderer))
+    }
+
+    fn drawable<C, S>(
+        self,
+        placement: Placement,
+        text_style: Option<S>,
+    ) -> DrawableAxis<'a, C, L, S>
     where
         C: PixelColor + Default,
-        TextStyle: Clone + Default,
     {
         DrawableAxis {
             axis: self,
             placement,
             color: None,
-            text_style: None,
+            text_style,
+            tick_font: None,
+            title_font: None,
+            title_orientation: None,
+            tick_label_side: None,
+            tick_label_placement: None,
             tick_size: None,
+            tick_direction: None,
             thickness: None,
+            tick_thickness: None,
+            arrowhead: None,
+            line_hidden: false,
+            ticks_hidden: false,
+            tick_labels_hidden: false,
+            title_inside: false,
+            grid: None,
+            grid_color: None,
+            grid_thickness: None,
+            grid_pattern: None,
         }
     }
 }
 
-/// Drawable axis object, constructed for specific display
-pub struct DrawableAxis<'a, C>
+/// Character style tick labels and title of [DrawableAxis] are drawn with. Besides [MonoTextStyle],
+/// any [TextRenderer] - e.g. BDF or U8g2 font style - can be used after opting in with an empty impl.
+/// Only axes take other renderers, text of the other widgets is drawn with mono fonts.
+pub trait AxisTextStyle<'a, C>:
+    TextRenderer<Color = C> + CharacterStyle<Color = C> + Clone
 where
     C: PixelColor,
-    TextStyle: Clone + Default,
 {
-    axis: Axis<'a>,
+    /// style used when none is set, text is not drawn without it
+    fn fallback(_color: C) -> Option<Self> {
+        None
+    }
+
+    /// style with given mono font, fonts set on the axis are ignored by other renderers
+    fn with_mono_font(self, _font: &'a MonoFont<'a>) -> Self {
+        self
+    }
+}
+
+impl<'a, C> AxisTextStyle<'a, C> for MonoTextStyle<'a, C>
+where
+    C: PixelColor,
+{
+    /// axis color and 5x8 font
+    fn fallback(color: C) -> Option<Self> {
+        Some(MonoTextStyle::new(&FONT_5X8, color))
+    }
+
+    fn with_mono_font(mut self, font: &'a MonoFont<'a>) -> Self {
+        self.font = font;
+        self
+    }
+}
+
+/// Drawable axis object, constructed for specific display. Text is drawn with 5x8 mono font
+/// unless other font is set, or other renderer given to [Axis::into_drawable_axis_with_renderer].
+pub struct DrawableAxis<'a, C, const L: usize = 11, S = MonoTextStyle<'a, C>>
+where
+    C: PixelColor,
+{
+    axis: Axis<'a, L>,
     placement: Placement,
     color: Option<C>,
-    text_style: Option<MonoTextStyle<'a, C>>,
+    text_style: Option<S>,
+    tick_font: Option<&'a MonoFont<'a>>,
+    title_font: Option<&'a MonoFont<'a>>,
+    title_orientation: Option<TitleOrientation>,
+    tick_label_side: Option<TickLabelSide>,
+    tick_label_placement: Option<TickLabelPlacement>,
     tick_size: Option<usize>,
+    tick_direction: Option<TickDirection>,
     thickness: Option<usize>,
+    tick_thickness: Option<usize>,
+    arrowhead: Option<u32>,
+    line_hidden: bool,
+    ticks_hidden: bool,
+    tick_labels_hidden: bool,
+    title_inside: bool,
+    /// screen coordinates across the axis between which grid lines span
+    grid: Option<(i32, i32)>,
+    grid_color: Option<C>,
+    grid_thickness: Option<usize>,
+    grid_pattern: Option<LinePattern>,
 }
 
-impl<'a, C> DrawableAxis<'a, C>
+impl<'a, C, const L: usize, S> DrawableAxis<'a, C, L, S>
 where
     C: PixelColor + Default,
-    TextStyle: Clone + Default,
 {
-    pub fn set_color(mut self, val: C) -> DrawableAxis<'a, C> {
+    pub fn set_color(mut self, val: C) -> DrawableAxis<'a, C, L, S> {
         self.color = Some(val);
         self
     }
-    pub fn set_text_style(mut self, val: MonoTextStyle<'a, C>) -> DrawableAxis<'a, C> {
+
+    /// set style of tick labels and title, if not set, axis color and 5x8 mono font are used
+    pub fn set_text_style(mut self, val: S) -> DrawableAxis<'a, C, L, S> {
         self.text_style = Some(val);
         self
     }
 
+    /// set how the title is laid out, vertical orientation applies only to vertical axes
+    pub fn set_title_orientation(mut self, val: TitleOrientation) -> DrawableAxis<'a, C, L, S> {
+        self.title_orientation = Some(val);
+        self
+    }
+
+    /// set on which side of the axis tick labels are drawn, applies only to vertical axes
+    pub fn set_tick_label_side(mut self, val: TickLabelSide) -> DrawableAxis<'a, C, L, S> {
+        self.tick_label_side = Some(val);
+        self
+    }
+
+    /// set where tick labels are drawn relative to the ticks, applies only to horizontal axes
+    pub fn set_tick_label_placement(
+        mut self,
+        val: TickLabelPlacement,
+    ) -> DrawableAxis<'a, C, L, S> {
+        self.tick_label_placement = Some(val);
+        self
+    }
+
     /// set how wide tick should be drawn on the axis
-    pub fn set_tick_size(mut self, val: usize) -> DrawableAxis<'a, C> {
+    pub fn set_tick_size(mut self, val: usize) -> DrawableAxis<'a, C, L, S> {
         self.tick_size = Some(val);
         self
     }
 
+    /// set which side of the axis line ticks are drawn on, if not set, they straddle the line
+    pub fn set_tick_direction(mut self, val: TickDirection) -> DrawableAxis<'a, C, L, S> {
+        self.tick_direction = Some(val);
+        self
+    }
+
     /// set thickness of the main line of the axis
-    pub fn set_thickness(mut self, val: usize) -> DrawableAxis<'a, C> {
+    pub fn set_thickness(mut self, val: usize) -> DrawableAxis<'a, C, L, S> {
         self.thickness = Some(val);
         self
     }
+
+    /// set thickness of the ticks, if not set, thickness of the main line is used
+    pub fn set_tick_thickness(mut self, val: usize) -> DrawableAxis<'a, C, L, S> {
+        self.tick_thickness = Some(val);
+        self
+    }
+
+    /// draw arrowhead of given length at the end of the axis - right end of horizontal axis, top of vertical one
+    pub fn set_arrowhead(mut self, val: u32) -> DrawableAxis<'a, C, L, S> {
+        self.arrowhead = Some(val);
+        self
+    }
+
+    /// skip the main line of the axis together with its arrowhead, e.g. when plot frame is drawn instead
+    pub fn set_line_hidden(mut self) -> DrawableAxis<'a, C, L, S> {
+        self.line_hidden = true;
+        self
+    }
+
+    /// skip the ticks, tick labels are still drawn unless hidden too
+    pub fn set_ticks_hidden(mut self) -> DrawableAxis<'a, C, L, S> {
+        self.ticks_hidden = true;
+        self
+    }
+
+    /// draw ticks without their labels, e.g. where there is no room for text
+    pub fn set_tick_labels_hidden(mut self) -> DrawableAxis<'a, C, L, S> {
+        self.tick_labels_hidden = true;
+        self
+    }
+
+    /// draw the title on the inner side of the axis - above the right end of horizontal axis,
+    /// to the right of the top of vertical one - so it takes no space outside of the plot area.
+    /// Inside title is always written horizontally.
+    pub fn set_title_inside(mut self) -> DrawableAxis<'a, C, L, S> {
+        self.title_inside = true;
+        self
+    }
+
+    /// draw grid lines at the ticks, spanning between given screen coordinates across the axis -
+    /// Y coordinates for horizontal axis, X coordinates for vertical one. See [DrawableAxis::draw_grid].
+    pub fn set_grid(mut self, start: i32, end: i32) -> DrawableAxis<'a, C, L, S> {
+        self.grid = Some((start, end));
+        self
+    }
+
+    /// set color of grid lines, if not set, axis color is used
+    pub fn set_grid_color(mut self, val: C) -> DrawableAxis<'a, C, L, S> {
+        self.grid_color = Some(val);
+        self
+    }
+
+    /// set thickness of grid lines, 1 px if not set
+    pub fn set_grid_thickness(mut self, val: usize) -> DrawableAxis<'a, C, L, S> {
+        self.grid_thickness = Some(val);
+        self
+    }
+
+    /// set dash pattern of grid lines, e.g. [LinePattern::DOTTED] for unobtrusive grid on monochromatic displays
+    pub fn set_grid_pattern(mut self, val: LinePattern) -> DrawableAxis<'a, C, L, S> {
+        self.grid_pattern = Some(val);
+        self
+    }
 }
 
-impl<'a, C> Drawable for DrawableAxis<'a, C>
+/// settings of the default mono font text
+impl<'a, C, const L: usize> DrawableAxis<'a, C, L>
 where
     C: PixelColor + Default,
-    TextStyle: Clone + Default,
 {
-    type Color = C;
-    type Output = ();
+    /// set font of tick labels, overrides the one from text style
+    pub fn set_tick_font(mut self, val: &'a MonoFont<'a>) -> DrawableAxis<'a, C, L> {
+        self.tick_font = Some(val);
+        self
+    }
 
-    /// most important function - draw the axis on the display
-    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<(), D::Error> {
-        let color = self.color.unwrap_or_default();
-        let thickness = self.thickness.unwrap_or(1);
-        let tick_size = self.tick_size.unwrap_or(2);
+    /// set font of axis title, overrides the one from text style
+    pub fn set_title_font(mut self, val: &'a MonoFont<'a>) -> DrawableAxis<'a, C, L> {
+        self.title_font = Some(val);
+        self
+    }
+}
+
+impl<'a, C, const L: usize, S> DrawableAxis<'a, C, L, S>
+where
+    C: PixelColor + Default,
+    S: AxisTextStyle<'a, C>,
+{
+    /// character styles of tick labels and title, none if there is no style to draw the text with
+    #[cfg(feature = "text")]
+    fn character_styles(&self) -> Option<(S, S)> {
+        let style = self
+            .text_style
+            .clone()
+            .or_else(|| S::fallback(self.color.unwrap_or_default()))?;
+        let tick_character_style = match self.tick_font {
+            Some(font) => style.clone().with_mono_font(font),
+            None => style.clone(),
+        };
+        let title_character_style = match self.title_font {
+            Some(font) => style.with_mono_font(font),
+            None => style,
+        };
+        Some((tick_character_style, title_character_style))
+    }
+
+    /// how far ticks reach from the axis line outside and inside of the plot area, zero when they are hidden
+    fn tick_reach(&self) -> (i32, i32) {
+        if self.ticks_hidden {
+            return (0, 0);
+        }
+        let (outward, inward) = self
+            .tick_direction
+            .unwrap_or_default()
+            .reach(self.tick_size.unwrap_or(2));
+        (outward as i32, inward as i32)
+    }
+
+    /// rows of tick labels below horizontal axis - regular labels, unless they are drawn above the line
+    /// and labels in second unit take their place, and major labels
+    #[cfg(feature = "text")]
+    fn x_label_rows_below(&self) -> i32 {
+        if self.tick_labels_hidden {
+            return 0;
+        }
+        let regular = self.tick_label_placement.unwrap_or_default() != TickLabelPlacement::Above
+            || self.axis.secondary_label_formatter.is_some();
+        regular as i32 + self.axis.major_scale.is_some() as i32
+    }
+
+    /// distance of the outside title of horizontal axis from the axis line
+    #[cfg(feature = "text")]
+    fn x_title_offset(&self, tick_font_height: i32) -> i32 {
+        match self.x_label_rows_below() {
+            0 => self.tick_reach().0 + 1,
+            rows => 2 + rows * (tick_font_height + 1) - 1,
+        }
+    }
+
+    /// height of the outside title of horizontal axis, wrapped within the axis width
+    #[cfg(feature = "wrapped-titles")]
+    fn x_title_height(&self, title: &str, style: &S, width: u32) -> u32 {
+        x_title_box_style().measure_text_height(style, title, width)
+    }
 
-        let character_style = MonoTextStyle::new(&FONT_5X8, color);
+    /// height of the outside title of horizontal axis, written as a single line
+    #[cfg(all(feature = "text", not(feature = "wrapped-titles")))]
+    fn x_title_height(&self, _title: &str, style: &S, _width: u32) -> u32 {
+        style.line_height()
+    }
+
+    /// draw one row of tick labels of horizontal axis at their marks, aligned to the ticks
+    /// according to the label placement
+    #[cfg(feature = "text")]
+    fn draw_x_label_row<D, I>(
+        &self,
+        display: &mut D,
+        labels: I,
+        y: i32,
+        baseline: Baseline,
+        style: &S,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        I: Iterator<Item = (i32, Label<L>)>,
+    {
+        let (x1, x2) = match self.placement {
+            Placement::X { x1, x2, .. } => (x1, x2),
+            Placement::Y { .. } => return Ok(()),
+        };
+        let (alignment, offset) = match self.tick_label_placement.unwrap_or_default() {
+            TickLabelPlacement::BelowRight => (Alignment::Left, 2),
+            TickLabelPlacement::BelowLeft => (Alignment::Right, -1),
+            TickLabelPlacement::BelowCentered | TickLabelPlacement::Above => (Alignment::Center, 0),
+        };
+        let text_style = TextStyleBuilder::new()
+            .alignment(alignment)
+            .baseline(baseline)
+            .build();
+        for (mark, label) in labels {
+            let x = mark.scale_between_ranges(&self.axis.range, &(x1..x2));
+            Text::with_text_style(
+                &label.text,
+                Point { x: x + offset, y },
+                style.clone(),
+                text_style,
+            )
+            .draw(display)?;
+        }
+        Ok(())
+    }
+
+    /// draw grid lines set by [DrawableAxis::set_grid]. Grid is not drawn together with the axis,
+    /// so it can be put behind both axes and the curves. Returns bounding box of touched pixels.
+    pub fn draw_grid<D>(&self, display: &mut D) -> Result<Rectangle, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let display = &mut DirtyTracker::new(display);
+        let (start, end) = match self.grid {
+            Some(span) => span,
+            None => return Ok(display.dirty()),
+        };
+        let color = self.grid_color.or(self.color).unwrap_or_default();
+        let thickness = self.grid_thickness.unwrap_or(1);
+        let pattern = self.grid_pattern.unwrap_or_default();
+        for mark in self.axis.scale_marks() {
+            let line = match self.placement {
+                Placement::X { x1, x2, .. } => {
+                    let x = mark.scale_between_ranges(&self.axis.range, &(x1..x2));
+                    Line::new(Point { x, y: start }, Point { x, y: end })
+                }
+                Placement::Y { y1, y2, .. } => {
+                    let y = mark.scale_between_ranges(&self.axis.range, &(y2..y1));
+                    Line::new(Point { x: start, y }, Point { x: end, y })
+                }
+            };
+            if pattern == LinePattern::SOLID {
+                draw_line_runs(display, line, color, thickness)?;
+            } else {
+                draw_patterned_line(display, line, pattern, color, thickness, &mut 0)?;
+            }
+        }
+        Ok(display.dirty())
+    }
 
-        let scale_marks = match self.axis.scale.unwrap_or_default() {
-            Scale::Fixed(interval) => self.axis.range.clone().into_iter().step_by(interval.max(1)),
-            Scale::RangeFraction(fraction) => {
-                let len = self.axis.range.len();
-                self.axis.range.clone().into_iter().step_by((len / fraction).max(1))
+    /// how far tick labels and title reach away from the axis line, in pixels -
+    /// below horizontal axis or to the left of vertical one. Used to fit the axis in the given area.
+    #[cfg(feature = "text")]
+    pub fn label_extent(&self) -> u32 {
+        let tick_size = self.tick_reach().0 as u32;
+        let (tick_character_style, title_character_style) = match self.character_styles() {
+            Some(styles) => styles,
+            None => return tick_size,
+        };
+        let tick_font_height = tick_character_style.line_height();
+        match self.placement {
+            Placement::X { x1, x2, .. } => {
+                let width = (x2 - x1 + 1).max(0) as u32;
+                let title_height = match self.axis.title {
+                    Some(title) if !self.title_inside => {
+                        self.x_title_height(title, &title_character_style, width)
+                    }
+                    _ => 0,
+                };
+                let title_offset = self.x_title_offset(tick_font_height as i32) as u32;
+                (title_offset + title_height).max(tick_size)
+            }
+            Placement::Y { .. } => {
+                // regular labels, or the ones in second unit when regular labels are on the right
+                let tick_labels_width = self
+                    .axis
+                    .scale_marks()
+                    .filter(|_| !self.tick_labels_hidden)
+                    .filter_map(|mark| match self.tick_label_side.unwrap_or_default() {
+                        TickLabelSide::Left => Some(self.axis.format_mark(mark)),
+                        TickLabelSide::Right => self.axis.format_secondary(mark),
+                    })
+                    .map(|label| {
+                        let buf = label.text;
+                        Text::new(&buf, Point::zero(), tick_character_style.clone())
+                            .bounding_box()
+                            .size
+                            .width
+                    })
+                    .max()
+                    .unwrap_or(0);
+                let title_width =
+                    match (self.axis.title, self.title_orientation.unwrap_or_default()) {
+                        (None, _) => 0,
+                        (Some(_), _) if self.title_inside => 0,
+                        (Some(title), TitleOrientation::Horizontal) => {
+                            Text::new(title, Point::zero(), title_character_style)
+                                .bounding_box()
+                                .size
+                                .width
+                                + 1
+                        }
+                        (Some(title), TitleOrientation::Vertical) => {
+                            widest_char(title, &title_character_style) + 1
+                        }
+                    };
+                tick_labels_width.max(tick_size) + title_width
             }
+        }
+    }
+
+    /// how far ticks reach away from the axis line, without text there are no labels or title
+    #[cfg(not(feature = "text"))]
+    pub fn label_extent(&self) -> u32 {
+        self.tick_reach().0 as u32
+    }
+
+    /// draw tick labels and title of the axis
+    #[cfg(feature = "text")]
+    fn draw_text<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<(), D::Error> {
+        let (_, inward) = self.tick_reach();
+        let (tick_character_style, title_character_style) = match self.character_styles() {
+            Some(styles) => styles,
+            None => return Ok(()),
         };
+        let tick_font_height = tick_character_style.line_height() as i32;
         match self.placement {
             Placement::X { x1, x2, y } => {
-                let title_text_style = TextStyleBuilder::new()
-                    .alignment(Alignment::Center)
-                    .baseline(Baseline::Top)
-                    .build();
-                let tick_text_style = TextStyleBuilder::new()
-                    .alignment(Alignment::Left)
-                    .baseline(Baseline::Top)
-                    .build();
-                Line {
-                    start: Point { x: x1, y },
-                    end: Point { x: x2, y },
+                match self.axis.title {
+                    Some(title) if self.title_inside => {
+                        Text::with_text_style(
+                            title,
+                            Point {
+                                x: x2,
+                                y: y - inward - 1,
+                            },
+                            title_character_style,
+                            TextStyleBuilder::new()
+                                .alignment(Alignment::Right)
+                                .baseline(Baseline::Bottom)
+                                .build(),
+                        )
+                        .draw(display)?;
+                    }
+                    #[cfg(feature = "wrapped-titles")]
+                    Some(title) => {
+                        let width = (x2 - x1 + 1).max(0) as u32;
+                        let height = self.x_title_height(title, &title_character_style, width);
+                        TextBox::with_textbox_style(
+                            title,
+                            Rectangle::new(
+                                Point {
+                                    x: x1,
+                                    y: y + self.x_title_offset(tick_font_height),
+                                },
+                                Size::new(width, height),
+                            ),
+                            title_character_style,
+                            x_title_box_style(),
+                        )
+                        .draw(display)?;
+                    }
+                    #[cfg(not(feature = "wrapped-titles"))]
+                    Some(title) => {
+                        Text::with_text_style(
+                            title,
+                            Point {
+                                x: x1 + (x2 - x1) / 2,
+                                y: y + self.x_title_offset(tick_font_height),
+                            },
+                            title_character_style,
+                            TextStyleBuilder::new()
+                                .alignment(Alignment::Center)
+                                .baseline(Baseline::Top)
+                                .build(),
+                        )
+                        .draw(display)?;
+                    }
+                    None => {}
                 }
-                .into_styled(PrimitiveStyle::with_stroke(color, thickness as u32))
-                .draw(display)?;
-                if let Some(title) = self.axis.title {
-                    Text::with_text_style(
-                        title,
-                        Point {
-                            x: x1 + (x2 - x1) / 2,
-                            y: y + 10,
-                        },
-                        character_style,
-                        title_text_style,
-                    )
-                    .draw(display)?;
+                if self.tick_labels_hidden {
+                    return Ok(());
                 }
-                for mark in scale_marks {
-                    let x = mark.scale_between_ranges(&self.axis.range, &(x1..x2));
-                    Line {
-                        start: Point {
-                            x,
-                            y: y - tick_size as i32,
-                        },
-                        end: Point {
-                            x,
-                            y: y + tick_size as i32,
-                        },
-                    }
-                    .into_styled(PrimitiveStyle::with_stroke(color, thickness as u32))
-                    .draw(display)?;
-                    let mut buf: String<11> = String::new();
-                    write!(buf, "{}", mark).ok();
-                    Text::with_text_style(
-                        &buf,
-                        Point { x: x + 2, y: y + 2 },
-                        character_style,
-                        tick_text_style,
-                    )
-                    .draw(display)?;
+                let style = &tick_character_style;
+                let below = |row: i32| y + 2 + row * (tick_font_height + 1);
+                let above = y - inward - 1;
+                let regular = self
+                    .axis
+                    .scale_marks()
+                    .map(|mark| (mark, self.axis.format_mark(mark)));
+                let secondary = self
+                    .axis
+                    .scale_marks()
+                    .filter_map(|mark| Some((mark, self.axis.format_secondary(mark)?)));
+                // labels in second unit are drawn on the other side of the line
+                if self.tick_label_placement.unwrap_or_default() == TickLabelPlacement::Above {
+                    self.draw_x_label_row(display, regular, above, Baseline::Bottom, style)?;
+                    self.draw_x_label_row(display, secondary, below(0), Baseline::Top, style)?;
+                } else {
+                    self.draw_x_label_row(display, regular, below(0), Baseline::Top, style)?;
+                    self.draw_x_label_row(display, secondary, above, Baseline::Bottom, style)?;
+                }
+                if let Some(major) = self.axis.major_axis() {
+                    let labels = major
+                        .scale_marks()
+                        .map(|mark| (mark, major.format_mark(mark)));
+                    let row = below(self.x_label_rows_below() - 1);
+                    self.draw_x_label_row(display, labels, row, Baseline::Top, style)?;
                 }
             }
             Placement::Y { y1, y2, x } => {
@@ -208,59 +1032,607 @@ where
                     .alignment(Alignment::Right)
                     .baseline(Baseline::Middle)
                     .build();
-                let tick_text_style = TextStyleBuilder::new()
-                    .alignment(Alignment::Right)
-                    .baseline(Baseline::Top)
-                    .build();
-                Line {
-                    start: Point { x, y: y1 },
-                    end: Point { x, y: y2 },
-                }
-                .into_styled(PrimitiveStyle::with_stroke(color, thickness as u32))
-                .draw(display)?;
+                let side_text_style = |side: TickLabelSide| match side {
+                    TickLabelSide::Left => (
+                        TextStyleBuilder::new()
+                            .alignment(Alignment::Right)
+                            .baseline(Baseline::Top)
+                            .build(),
+                        x,
+                    ),
+                    TickLabelSide::Right => (
+                        TextStyleBuilder::new()
+                            .alignment(Alignment::Left)
+                            .baseline(Baseline::Top)
+                            .build(),
+                        x + inward + 1,
+                    ),
+                };
+                let side = self.tick_label_side.unwrap_or_default();
+                let (tick_text_style, tick_text_x) = side_text_style(side);
+                let (secondary_text_style, secondary_text_x) = side_text_style(side.opposite());
 
-                let mut tick_text_left_pos_bound = i32::MAX;
-                for mark in scale_marks {
-                    let y = mark.scale_between_ranges(&self.axis.range, &(y2..y1));
-                    Line {
-                        start: Point {
-                            x: x - tick_size as i32,
-                            y,
-                        },
-                        end: Point {
-                            x: x + tick_size as i32,
-                            y,
-                        },
+                let mut tick_text_left_pos_bound = x - self.tick_reach().0;
+                for mark in self.axis.scale_marks() {
+                    if self.tick_labels_hidden {
+                        break;
                     }
-                    .into_styled(PrimitiveStyle::with_stroke(color, thickness as u32))
-                    .draw(display)?;
-                    let mut buf: String<11> = String::new();
-                    write!(buf, "{}", mark).ok();
+                    let y = mark.scale_between_ranges(&self.axis.range, &(y2..y1));
+                    let buf = self.axis.format_mark(mark).text;
                     let tick_val = Text::with_text_style(
                         &buf,
-                        Point { x, y },
-                        character_style,
+                        Point { x: tick_text_x, y },
+                        tick_character_style.clone(),
                         tick_text_style,
                     );
                     if tick_val.bounding_box().top_left.x < tick_text_left_pos_bound {
                         tick_text_left_pos_bound = tick_val.bounding_box().top_left.x
                     };
                     tick_val.draw(display)?;
+                    if let Some(label) = self.axis.format_secondary(mark) {
+                        let secondary = Text::with_text_style(
+                            &label.text,
+                            Point {
+                                x: secondary_text_x,
+                                y,
+                            },
+                            tick_character_style.clone(),
+                            secondary_text_style,
+                        );
+                        let left = secondary.bounding_box().top_left.x;
+                        tick_text_left_pos_bound = tick_text_left_pos_bound.min(left);
+                        secondary.draw(display)?;
+                    }
                 }
                 if let Some(title) = self.axis.title {
-                    Text::with_text_style(
-                        title,
-                        Point {
-                            x: tick_text_left_pos_bound - 1,
-                            y: y1 + (y2 - y1) / 2,
-                        },
-                        character_style,
-                        title_text_style,
-                    )
-                    .draw(display)?;
+                    let center_y = y1 + (y2 - y1) / 2;
+                    match (
+                        self.title_inside,
+                        self.title_orientation.unwrap_or_default(),
+                    ) {
+                        (true, _) => {
+                            Text::with_text_style(
+                                title,
+                                Point {
+                                    x: x + inward + 2,
+                                    y: y1,
+                                },
+                                title_character_style,
+                                TextStyleBuilder::new()
+                                    .alignment(Alignment::Left)
+                                    .baseline(Baseline::Top)
+                                    .build(),
+                            )
+                            .draw(display)?;
+                        }
+                        (false, TitleOrientation::Horizontal) => {
+                            Text::with_text_style(
+                                title,
+                                Point {
+                                    x: tick_text_left_pos_bound - 1,
+                                    y: center_y,
+                                },
+                                title_character_style,
+                                title_text_style,
+                            )
+                            .draw(display)?;
+                        }
+                        (false, TitleOrientation::Vertical) => {
+                            let char_width = widest_char(title, &title_character_style);
+                            let char_height = title_character_style.line_height();
+                            let x = tick_text_left_pos_bound - 1 - char_width as i32 / 2;
+                            let mut y =
+                                center_y - (title.chars().count() as u32 * char_height) as i32 / 2;
+                            let stacked_text_style = TextStyleBuilder::new()
+                                .alignment(Alignment::Center)
+                                .baseline(Baseline::Top)
+                                .build();
+                            for c in title.chars() {
+                                let mut buf = [0u8; 4];
+                                Text::with_text_style(
+                                    c.encode_utf8(&mut buf),
+                                    Point { x, y },
+                                    title_character_style.clone(),
+                                    stacked_text_style,
+                                )
+                                .draw(display)?;
+                                y += char_height as i32;
+                            }
+                        }
+                    }
                 }
             }
         }
         Ok(())
     }
 }
+
+impl<'a, C, const L: usize, S> Dimensions for DrawableAxis<'a, C, L, S>
+where
+    C: PixelColor + Default,
+    S: AxisTextStyle<'a, C>,
+{
+    /// area covered by the axis, including ticks, labels and title
+    fn bounding_box(&self) -> Rectangle {
+        drawn_area(self)
+    }
+}
+
+impl<'a, C, const L: usize, S> Transform for DrawableAxis<'a, C, L, S>
+where
+    C: PixelColor + Default,
+    S: AxisTextStyle<'a, C>,
+{
+    fn translate(&self, by: Point) -> Self {
+        let mut axis = DrawableAxis {
+            axis: self.axis.clone(),
+            text_style: self.text_style.clone(),
+            ..*self
+        };
+        axis.translate_mut(by);
+        axis
+    }
+
+    fn translate_mut(&mut self, by: Point) -> &mut Self {
+        let across = match self.placement {
+            Placement::X { .. } => by.y,
+            Placement::Y { .. } => by.x,
+        };
+        self.grid = self.grid.map(|(start, end)| (start + across, end + across));
+        self.placement = match self.placement {
+            Placement::X { x1, x2, y } => Placement::X {
+                x1: x1 + by.x,
+                x2: x2 + by.x,
+                y: y + by.y,
+            },
+            Placement::Y { y1, y2, x } => Placement::Y {
+                y1: y1 + by.y,
+                y2: y2 + by.y,
+                x: x + by.x,
+            },
+        };
+        self
+    }
+}
+
+impl<'a, C, const L: usize, S> Drawable for DrawableAxis<'a, C, L, S>
+where
+    C: PixelColor + Default,
+    S: AxisTextStyle<'a, C>,
+{
+    type Color = C;
+    /// bounding box of pixels touched by the axis, for flushing only changed region to the display
+    type Output = Rectangle;
+
+    /// most important function - draw the axis on the display
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<Rectangle, D::Error> {
+        let display = &mut DirtyTracker::new(display);
+        let color = self.color.unwrap_or_default();
+        let thickness = self.thickness.unwrap_or(1);
+        let (outward, inward) = self.tick_reach();
+        let tick_thickness = self.tick_thickness.unwrap_or(thickness);
+
+        let major_marks = self
+            .axis
+            .major_axis()
+            .into_iter()
+            .flat_map(|major| major.scale_marks());
+        let scale_marks = self.axis.scale_marks().chain(major_marks);
+        match self.placement {
+            Placement::X { x1, x2, y } => {
+                if !self.line_hidden {
+                    draw_line_runs(
+                        display,
+                        Line {
+                            start: Point { x: x1, y },
+                            end: Point { x: x2, y },
+                        },
+                        color,
+                        thickness,
+                    )?;
+                    if let Some(size) = self.arrowhead {
+                        let size = size as i32;
+                        Triangle::new(
+                            Point { x: x2, y },
+                            Point {
+                                x: x2 - size,
+                                y: y - size / 2,
+                            },
+                            Point {
+                                x: x2 - size,
+                                y: y + size / 2,
+                            },
+                        )
+                        .into_styled(PrimitiveStyle::with_fill(color))
+                        .draw(display)?;
+                    }
+                }
+                for mark in scale_marks.filter(|_| !self.ticks_hidden) {
+                    let x = mark.scale_between_ranges(&self.axis.range, &(x1..x2));
+                    draw_line_runs(
+                        display,
+                        Line {
+                            start: Point { x, y: y - inward },
+                            end: Point { x, y: y + outward },
+                        },
+                        color,
+                        tick_thickness,
+                    )?;
+                }
+            }
+            Placement::Y { y1, y2, x } => {
+                if !self.line_hidden {
+                    draw_line_runs(
+                        display,
+                        Line {
+                            start: Point { x, y: y1 },
+                            end: Point { x, y: y2 },
+                        },
+                        color,
+                        thickness,
+                    )?;
+                    if let Some(size) = self.arrowhead {
+                        let size = size as i32;
+                        Triangle::new(
+                            Point { x, y: y1 },
+                            Point {
+                                x: x - size / 2,
+                                y: y1 + size,
+                            },
+                            Point {
+                                x: x + size / 2,
+                                y: y1 + size,
+                            },
+                        )
+                        .into_styled(PrimitiveStyle::with_fill(color))
+                        .draw(display)?;
+                    }
+                }
+                for mark in scale_marks.filter(|_| !self.ticks_hidden) {
+                    let y = mark.scale_between_ranges(&self.axis.range, &(y2..y1));
+                    draw_line_runs(
+                        display,
+                        Line {
+                            start: Point { x: x - outward, y },
+                            end: Point { x: x + inward, y },
+                        },
+                        color,
+                        tick_thickness,
+                    )?;
+                }
+            }
+        }
+        #[cfg(feature = "text")]
+        self.draw_text(display)?;
+        Ok(display.dirty())
+    }
+}
+
+/// width of the widest character of the text, for stacking the characters vertically
+#[cfg(feature = "text")]
+fn widest_char<S: TextRenderer>(text: &str, style: &S) -> u32 {
+    text.chars()
+        .map(|c| {
+            let mut buf = [0u8; 4];
+            style
+                .measure_string(c.encode_utf8(&mut buf), Point::zero(), Baseline::Top)
+                .bounding_box
+                .size
+                .width
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// outside title of horizontal axis is centered below the axis, wrapped into as many lines as needed
+#[cfg(feature = "wrapped-titles")]
+fn x_title_box_style() -> TextBoxStyle {
+    TextBoxStyleBuilder::new()
+        .alignment(HorizontalAlignment::Center)
+        .build()
+}
+
+#[cfg(test)]
+#[allow(clippy::reversed_empty_ranges)]
+mod tests {
+    use crate::axis::{nice_step, Axis, Placement, Scale, TickDirection};
+    #[cfg(feature = "text")]
+    use crate::axis::{TickLabelPlacement, TickLabelSide};
+    use core::ops::Range;
+    #[cfg(feature = "text")]
+    use embedded_graphics::mono_font::{ascii::FONT_6X10, MonoTextStyle};
+    use embedded_graphics::{
+        mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::*, primitives::Rectangle,
+    };
+    use heapless::Vec;
+    use test_case::test_case;
+
+    #[test_case(1 => 1; "one")]
+    #[test_case(3 => 5; "round up to five")]
+    #[test_case(18 => 20; "twenty")]
+    #[test_case(200 => 200; "exact")]
+    #[test_case(501 => 1000; "next magnitude")]
+    fn nice(raw_step: u64) -> u64 {
+        nice_step(raw_step)
+    }
+
+    #[test_case(0..30, Scale::Fixed(10), &[0, 10, 20]; "fixed")]
+    #[test_case(0..60, Scale::RangeFraction(3), &[0, 20, 40]; "range fraction")]
+    #[test_case(3..97, Scale::Auto, &[20, 40, 60, 80]; "auto")]
+    #[test_case(-7..12, Scale::Auto, &[-5, 0, 5, 10]; "auto negative")]
+    #[test_case(30..0, Scale::Fixed(10), &[30, 20, 10]; "descending")]
+    #[test_case(97..3, Scale::Auto, &[80, 60, 40, 20]; "auto descending")]
+    fn marks(range: Range<i32>, scale: Scale, expected: &[i32]) {
+        let marks: Vec<i32, 8> = Axis::new(range).set_scale(scale).scale_marks().collect();
+        assert_eq!(&marks[..], expected);
+    }
+
+    #[test_case(0..30, Scale::Fixed(10), &[0, 10, 20, 30]; "end added")]
+    #[test_case(0..25, Scale::Fixed(10), &[0, 10, 20, 25]; "end off step")]
+    #[test_case(3..97, Scale::Auto, &[3, 20, 40, 60, 80, 97]; "auto")]
+    #[test_case(30..3, Scale::Fixed(10), &[30, 20, 10, 3]; "descending")]
+    #[test_case(5..5, Scale::Fixed(10), &[5]; "empty range")]
+    #[test_case(0..10, Scale::Custom(&[]), &[]; "empty custom")]
+    fn end_marks(range: Range<i32>, scale: Scale, expected: &[i32]) {
+        let axis = Axis::new(range).set_scale(scale).set_end_marks();
+        let marks: Vec<i32, 8> = axis.scale_marks().collect();
+        assert_eq!(&marks[..], expected);
+    }
+
+    #[test]
+    #[cfg(feature = "text")]
+    fn categories() {
+        let axis = Axis::new(-1..3).set_categories(&["Mon", "Tue", "Wednesday, 1st"]);
+        let marks: Vec<i32, 8> = axis.scale_marks().collect();
+        assert_eq!(&marks[..], &[0, 1, 2]);
+        assert_eq!(axis.format_mark(1).text, "Tue");
+        assert_eq!(axis.format_mark(2).text, "Wednesday, ");
+    }
+
+    #[test]
+    #[cfg(feature = "text")]
+    fn custom_ticks() {
+        let axis =
+            Axis::new(0..10).set_scale(Scale::Custom(&[(10, "on"), (0, "off"), (20, "far")]));
+        let marks: Vec<i32, 8> = axis.scale_marks().collect();
+        assert_eq!(&marks[..], &[10, 0]);
+        assert_eq!(axis.format_mark(10).text, "on");
+        assert_eq!(axis.format_mark(0).text, "off");
+    }
+
+    #[test]
+    #[cfg(feature = "text")]
+    fn label_capacity() {
+        let axis = Axis::new(-1000..1000).set_scale(Scale::Fixed(500));
+        assert!(axis.check_labels().is_ok());
+        let axis = axis.set_label_capacity::<4>();
+        assert_eq!(
+            axis.check_labels(),
+            Err("Tick label exceeds label capacity")
+        );
+        assert_eq!(axis.format_mark(-1000).text, "-100");
+        assert_eq!(axis.format_mark(500).text, "500");
+    }
+
+    #[test]
+    #[cfg(feature = "text")]
+    fn major_labels_in_second_row() {
+        fn hours(minutes: i32, f: &mut dyn core::fmt::Write) -> core::fmt::Result {
+            write!(f, "{}h", minutes / 60)
+        }
+        let axis = Axis::new(0..180).set_scale(Scale::Fixed(30));
+        let placement = Placement::X {
+            x1: 0,
+            x2: 60,
+            y: 0,
+        };
+        let single_row = axis
+            .clone()
+            .into_drawable_axis::<BinaryColor>(placement)
+            .label_extent();
+        let axis = axis
+            .set_major_scale(Scale::Fixed(60))
+            .set_major_label_formatter(hours);
+        let major = axis.major_axis().unwrap();
+        let marks: Vec<i32, 8> = major.scale_marks().collect();
+        assert_eq!(&marks[..], &[0, 60, 120]);
+        assert_eq!(major.format_mark(120).text, "2h");
+        let two_rows = axis
+            .into_drawable_axis::<BinaryColor>(placement)
+            .label_extent();
+        assert_eq!(two_rows, single_row + 9);
+    }
+
+    #[test]
+    #[cfg(feature = "text")]
+    fn secondary_unit_labels() {
+        fn fahrenheit(celsius: i32, f: &mut dyn core::fmt::Write) -> core::fmt::Result {
+            write!(f, "{}", celsius * 9 / 5 + 32)
+        }
+        let axis = Axis::new(0..10)
+            .set_scale(Scale::Fixed(5))
+            .set_secondary_label_formatter(fahrenheit);
+        assert_eq!(axis.format_secondary(5).unwrap().text, "41");
+        let placement = Placement::Y {
+            y1: 0,
+            y2: 30,
+            x: 20,
+        };
+        let drawable = |side: TickLabelSide| {
+            axis.clone()
+                .into_drawable_axis::<BinaryColor>(placement)
+                .set_tick_label_side(side)
+        };
+        // °F labels are drawn inside the plot, on the right of the axis
+        let left = drawable(TickLabelSide::Left);
+        assert_eq!(left.label_extent(), 5);
+        assert!(left.bounding_box().bottom_right().unwrap().x > 20 + 10);
+        // and take the room outside of it when °C labels are moved inside
+        assert_eq!(drawable(TickLabelSide::Right).label_extent(), 10);
+    }
+
+    #[test]
+    #[cfg(feature = "text")]
+    fn tick_label_placement() {
+        let axis = Axis::new(0..100).set_scale(Scale::Custom(&[(0, "0"), (100, "100")]));
+        let x_axis = Placement::X {
+            x1: 0,
+            x2: 40,
+            y: 9,
+        };
+        let drawable = |placement: TickLabelPlacement| {
+            axis.clone()
+                .into_drawable_axis::<BinaryColor>(x_axis)
+                .set_tick_label_placement(placement)
+        };
+        let right = |placement| drawable(placement).bounding_box().bottom_right().unwrap().x;
+        assert!(right(TickLabelPlacement::BelowRight) > 40);
+        // the last label stays within the axis width
+        assert_eq!(right(TickLabelPlacement::BelowLeft), 40);
+        let above = drawable(TickLabelPlacement::Above);
+        assert_eq!(above.label_extent(), 3);
+        assert!(above.bounding_box().top_left.y < 9 - 8);
+    }
+
+    #[test]
+    #[cfg(feature = "wrapped-titles")]
+    fn long_title_wrapped() {
+        let axis = Axis::new(0..10).set_title("time of day");
+        let extent = |x2: i32| {
+            axis.clone()
+                .into_drawable_axis::<BinaryColor>(Placement::X { x1: 0, x2, y: 0 })
+                .label_extent()
+        };
+        assert_eq!(extent(30), extent(200) + 8);
+    }
+
+    #[test]
+    #[cfg(feature = "text")]
+    fn custom_text_renderer() {
+        let style = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
+        let placement = Placement::X {
+            x1: 0,
+            x2: 40,
+            y: 5,
+        };
+        let axis = || {
+            Axis::new(0..100)
+                .set_title("X")
+                .into_drawable_axis::<BinaryColor>(placement)
+        };
+        let mono = axis().set_text_style(style);
+        let renderer = Axis::new(0..100)
+            .set_title("X")
+            .into_drawable_axis_with_renderer::<BinaryColor, _>(placement, style);
+        assert_eq!(renderer.label_extent(), mono.label_extent());
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        let mut mono_display = MockDisplay::new();
+        mono_display.set_allow_overdraw(true);
+        assert_eq!(
+            renderer.draw(&mut display).unwrap(),
+            mono.draw(&mut mono_display).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "text")]
+    fn bounding_box_includes_labels() {
+        let axis = Axis::new(0..100)
+            .set_title("X")
+            .into_drawable_axis::<BinaryColor>(Placement::X {
+                x1: 10,
+                x2: 90,
+                y: 50,
+            })
+            .set_color(BinaryColor::On);
+        let area = axis.bounding_box();
+        assert_eq!(area.top_left, Point::new(10, 48));
+        // tick labels and title are drawn below the axis line
+        assert!(area.bottom_right().unwrap().y > 50 + 8);
+    }
+
+    #[test]
+    fn compact_takes_no_room_outside() {
+        let axis = Axis::new(0..100)
+            .set_title("Y")
+            .into_drawable_axis::<BinaryColor>(Placement::Y {
+                y1: 0,
+                y2: 30,
+                x: 10,
+            })
+            .set_tick_size(1)
+            .set_tick_labels_hidden()
+            .set_title_inside();
+        assert_eq!(axis.label_extent(), 1);
+        assert_eq!(axis.bounding_box().top_left.x, 9);
+    }
+
+    #[test]
+    #[cfg(feature = "text")]
+    fn tick_labels_on_the_right() {
+        let axis = Axis::new(0..100)
+            .into_drawable_axis::<BinaryColor>(Placement::Y {
+                y1: 0,
+                y2: 50,
+                x: 10,
+            })
+            .set_color(BinaryColor::On)
+            .set_tick_label_side(TickLabelSide::Right);
+        assert_eq!(axis.label_extent(), 2);
+        assert_eq!(axis.bounding_box().top_left.x, 8);
+        assert!(axis.bounding_box().size.width > 10);
+    }
+
+    #[test]
+    #[cfg(not(feature = "text"))]
+    fn without_text_only_ticks_take_room() {
+        let axis = Axis::new(0..100)
+            .set_title("Y")
+            .into_drawable_axis::<BinaryColor>(Placement::Y {
+                y1: 0,
+                y2: 30,
+                x: 10,
+            })
+            .set_tick_size(3);
+        assert_eq!(axis.label_extent(), 3);
+    }
+
+    #[test]
+    fn grid_spans_across_axis() {
+        let axis = Axis::new(0..10)
+            .set_scale(Scale::Fixed(5))
+            .into_drawable_axis::<BinaryColor>(Placement::X {
+                x1: 0,
+                x2: 10,
+                y: 20,
+            })
+            .set_color(BinaryColor::On)
+            .set_grid(0, 20);
+        let mut display = MockDisplay::new();
+        let dirty = axis.draw_grid(&mut display).unwrap();
+        assert_eq!(
+            dirty,
+            Rectangle::with_corners(Point::zero(), Point::new(5, 20))
+        );
+        assert_eq!(display.get_pixel(Point::new(5, 0)), Some(BinaryColor::On));
+        assert_eq!(display.get_pixel(Point::new(3, 0)), None);
+    }
+
+    #[test_case(TickDirection::Cross => (8, 5); "cross")]
+    #[test_case(TickDirection::Inward => (10, 3); "inward")]
+    #[test_case(TickDirection::Outward => (8, 3); "outward")]
+    fn tick_direction(direction: TickDirection) -> (i32, u32) {
+        let area = Axis::new(0..100)
+            .into_drawable_axis::<BinaryColor>(Placement::Y {
+                y1: 0,
+                y2: 50,
+                x: 10,
+            })
+            .set_color(BinaryColor::On)
+            .set_tick_labels_hidden()
+            .set_tick_direction(direction)
+            .bounding_box();
+        (area.top_left.x, area.size.width)
+    }
+}
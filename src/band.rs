@@ -0,0 +1,110 @@
+//! Shaded band regions in data coordinates, e.g. "acceptable range 20-25 °C"
+use core::ops::Range;
+
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+use crate::transform::PlotTransform;
+
+/// Display-agnostic band, range of values spanning the whole plot in the other direction
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Band {
+    /// band spanning whole plot width, between given Y values
+    Horizontal(Range<i32>),
+    /// band spanning whole plot height, between given X values
+    Vertical(Range<i32>),
+}
+
+impl Band {
+    /// create band between given Y values
+    pub fn horizontal(y_range: Range<i32>) -> Band {
+        Band::Horizontal(y_range)
+    }
+
+    /// create band between given X values
+    pub fn vertical(x_range: Range<i32>) -> Band {
+        Band::Vertical(x_range)
+    }
+
+    /// turn band into drawable object, clipped to the area of provided transform
+    pub fn into_drawable<C>(&self, transform: &PlotTransform) -> DrawableBand<C>
+    where
+        C: PixelColor + Default,
+    {
+        let top_left = transform.top_left();
+        let bottom_right = transform.bottom_right();
+        let band = match self {
+            Band::Horizontal(range) => Rectangle::with_corners(
+                Point::new(top_left.x, transform.y_to_screen(range.end)),
+                Point::new(bottom_right.x, transform.y_to_screen(range.start)),
+            ),
+            Band::Vertical(range) => Rectangle::with_corners(
+                Point::new(transform.x_to_screen(range.start), top_left.y),
+                Point::new(transform.x_to_screen(range.end), bottom_right.y),
+            ),
+        };
+        DrawableBand {
+            area: transform.area().intersection(&band),
+            color: None,
+        }
+    }
+}
+
+/// Drawable band, constructed for specific display
+pub struct DrawableBand<C>
+where
+    C: PixelColor + Default,
+{
+    area: Rectangle,
+    color: Option<C>,
+}
+
+/// builder methods to modify band decoration
+impl<C> DrawableBand<C>
+where
+    C: PixelColor + Default,
+{
+    pub fn set_color(mut self, color: C) -> DrawableBand<C> {
+        self.color = Some(color);
+        self
+    }
+}
+
+impl<C> Drawable for DrawableBand<C>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<(), D::Error> {
+        display.fill_solid(&self.area, self.color.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::band::Band;
+    use crate::transform::PlotTransform;
+    use embedded_graphics::{
+        mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::*, primitives::Rectangle,
+    };
+    use test_case::test_case;
+
+    fn transform() -> PlotTransform {
+        PlotTransform::new(0..10, 0..10, Point::new(0, 0), Point::new(40, 40)).unwrap()
+    }
+
+    #[test_case(Band::horizontal(2..5) => Rectangle::with_corners(Point::new(0, 20), Point::new(40, 32)); "horizontal")]
+    #[test_case(Band::vertical(5..10) => Rectangle::with_corners(Point::new(20, 0), Point::new(40, 40)); "vertical")]
+    #[test_case(Band::horizontal(8..20) => Rectangle::with_corners(Point::new(0, 0), Point::new(40, 8)); "clipped to plot")]
+    #[test_case(Band::vertical(20..30) => Rectangle::zero(); "outside of plot")]
+    fn band_area(band: Band) -> Rectangle {
+        let mut display = MockDisplay::new();
+        band.into_drawable(&transform())
+            .set_color(BinaryColor::On)
+            .draw(&mut display)
+            .unwrap();
+        display.affected_area()
+    }
+}
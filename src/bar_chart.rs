@@ -0,0 +1,480 @@
+//! Bar chart of values per category, with multiple series grouped side by side or stacked
+use core::ops::Range;
+
+use embedded_graphics::{
+    draw_target::DrawTargetExt,
+    mono_font::MonoTextStyle,
+    prelude::*,
+    primitives::{Line, PrimitiveStyle, Rectangle},
+};
+#[cfg(feature = "text")]
+use embedded_graphics::{
+    mono_font::ascii::FONT_5X8,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+
+use crate::axis::{Axis, DrawableAxis, Placement, Scale};
+use crate::dirty::DirtyTracker;
+use crate::placement::{area_drawable, IntoDrawableArea};
+use crate::transform::PlotTransform;
+#[cfg(feature = "text")]
+use crate::value_labels::{format_value, place_label};
+
+/// How multiple series are laid out within the category
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BarMode {
+    /// bars of each series are drawn next to each other
+    Grouped,
+    /// bars of each series are drawn on top of each other, negative values stack downwards
+    Stacked,
+}
+
+impl Default for BarMode {
+    fn default() -> Self {
+        BarMode::Grouped
+    }
+}
+
+/// Display-agnostic bar chart
+#[derive(Clone, Copy)]
+pub struct BarChart<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// labels of the categories, drawn on the shared category axis
+    categories: &'a [&'a str],
+    /// values of each series, one per category, and color of its bars
+    series: &'a [(&'a [i32], C)],
+    mode: Option<BarMode>,
+    /// manual range of values, if not set, it's deduced from the data
+    y_range: Option<(i32, i32)>,
+}
+
+impl<'a, C> BarChart<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// create bar chart, each series has to provide single value per category
+    pub fn new(
+        categories: &'a [&'a str],
+        series: &'a [(&'a [i32], C)],
+    ) -> Result<BarChart<'a, C>, &'static str> {
+        if series.is_empty() {
+            Err("No series provided")
+        } else if series
+            .iter()
+            .any(|(values, _)| values.len() != categories.len())
+        {
+            Err("Series length doesn't match categories")
+        } else {
+            Ok(BarChart {
+                categories,
+                series,
+                mode: None,
+                y_range: None,
+            })
+        }
+    }
+
+    pub fn set_mode(mut self, mode: BarMode) -> BarChart<'a, C> {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// set manual range of values, bars reaching outside of it are clipped
+    pub fn set_y_range(mut self, y_range: Range<i32>) -> BarChart<'a, C> {
+        self.y_range = Some((y_range.start, y_range.end));
+        self
+    }
+
+    /// range of values shown on the chart, deduced from the data always includes zero
+    pub fn y_range(&self) -> Range<i32> {
+        if let Some((start, end)) = self.y_range {
+            return start..end;
+        }
+        let (min, max) = match self.mode.unwrap_or_default() {
+            BarMode::Grouped => self
+                .series
+                .iter()
+                .flat_map(|(values, _)| values.iter())
+                .fold((0, 0), |(min, max), value| {
+                    (min.min(*value), max.max(*value))
+                }),
+            BarMode::Stacked => (0..self.categories.len())
+                .map(|category| self.stack(category))
+                .fold((0, 0), |(min, max), (negative, positive)| {
+                    (min.min(negative), max.max(positive))
+                }),
+        };
+        if min == max {
+            min..max + 1
+        } else {
+            min..max
+        }
+    }
+
+    /// sums of negative and positive values of the category
+    fn stack(&self, category: usize) -> (i32, i32) {
+        self.series.iter().map(|(values, _)| values[category]).fold(
+            (0, 0),
+            |(negative, positive), value| {
+                if value < 0 {
+                    (negative.saturating_add(value), positive)
+                } else {
+                    (negative, positive.saturating_add(value))
+                }
+            },
+        )
+    }
+
+    /// convert to drawable form for specific display
+    pub fn into_drawable(self, top_left: Point, bottom_right: Point) -> DrawableBarChart<'a, C> {
+        DrawableBarChart {
+            chart: self,
+            color: None,
+            text_style: None,
+            bar_gap: None,
+            #[cfg(feature = "text")]
+            value_labels: false,
+            top_left,
+            bottom_right,
+        }
+    }
+}
+
+impl<'a, C> IntoDrawableArea<DrawableBarChart<'a, C>> for BarChart<'a, C>
+where
+    C: PixelColor + Default,
+{
+    fn into_drawable_between(
+        self,
+        top_left: Point,
+        bottom_right: Point,
+    ) -> DrawableBarChart<'a, C> {
+        self.into_drawable(top_left, bottom_right)
+    }
+}
+
+/// Drawable bar chart, constructed for specific display
+pub struct DrawableBarChart<'a, C>
+where
+    C: PixelColor + Default,
+{
+    chart: BarChart<'a, C>,
+    color: Option<C>,
+    text_style: Option<MonoTextStyle<'a, C>>,
+    bar_gap: Option<u32>,
+    #[cfg(feature = "text")]
+    value_labels: bool,
+    top_left: Point,
+    bottom_right: Point,
+}
+
+/// single bar of the chart, spanning between screen X coordinates and between two values
+struct Bar<C> {
+    x_start: i32,
+    x_end: i32,
+    base: i32,
+    top: i32,
+    #[cfg(feature = "text")]
+    value: i32,
+    color: C,
+}
+
+/// builder methods to modify chart decoration
+impl<'a, C> DrawableBarChart<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// set color of the axes, also used for labels if text style is not set
+    pub fn set_color(mut self, color: C) -> DrawableBarChart<'a, C> {
+        self.color = Some(color);
+        self
+    }
+
+    /// set style of category and tick labels, if not set, axis color and 5x8 font are used
+    pub fn set_text_style(mut self, style: MonoTextStyle<'a, C>) -> DrawableBarChart<'a, C> {
+        self.text_style = Some(style);
+        self
+    }
+
+    /// set space between bars of neighbouring categories in pixels, 2 by default
+    pub fn set_bar_gap(mut self, gap: u32) -> DrawableBarChart<'a, C> {
+        self.bar_gap = Some(gap);
+        self
+    }
+
+    /// print value of each bar at its end, labels overlapping the previous one are moved
+    /// to the other side of the bar end or skipped
+    #[cfg(feature = "text")]
+    pub fn set_value_labels(mut self) -> DrawableBarChart<'a, C> {
+        self.value_labels = true;
+        self
+    }
+
+    #[cfg(feature = "text")]
+    fn text_style(&self) -> MonoTextStyle<'a, C> {
+        self.text_style
+            .unwrap_or_else(|| MonoTextStyle::new(&FONT_5X8, self.color.unwrap_or_default()))
+    }
+
+    /// space for category labels below the bars
+    #[cfg(feature = "text")]
+    fn label_height(&self) -> i32 {
+        self.text_style().font.character_size.height as i32 + 2
+    }
+
+    #[cfg(not(feature = "text"))]
+    fn label_height(&self) -> i32 {
+        0
+    }
+
+    /// value axis, placed along the left edge of given area
+    fn y_axis(&self, top_left: Point, bottom_right: Point) -> DrawableAxis<'a, C> {
+        let axis = Axis::new(self.chart.y_range())
+            .set_scale(Scale::Auto)
+            .into_drawable_axis(Placement::Y {
+                y1: top_left.y,
+                y2: bottom_right.y,
+                x: top_left.x,
+            })
+            .set_color(self.color.unwrap_or_default());
+        // axis falls back to its color and 5x8 font on its own
+        match self.text_style {
+            Some(text_style) => axis.set_text_style(text_style),
+            None => axis,
+        }
+    }
+
+    /// area where bars are drawn - the chart bounds without space for labels
+    pub fn plot_area(&self) -> (Point, Point) {
+        let y_axis = self.y_axis(self.top_left, self.bottom_right);
+        let label_height = self.label_height();
+        (
+            Point {
+                x: self.top_left.x + y_axis.label_extent() as i32,
+                y: self.top_left.y,
+            },
+            Point {
+                x: self.bottom_right.x,
+                y: self.bottom_right.y - label_height,
+            },
+        )
+    }
+}
+
+impl<'a, C> DrawableBarChart<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// visit bars of all series category by category, bars grow from `zero` value
+    fn for_each_bar<E>(
+        &self,
+        top_left: Point,
+        bottom_right: Point,
+        zero: i32,
+        mut f: impl FnMut(Bar<C>) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let categories = self.chart.categories.len() as i32;
+        let bar_gap = self.bar_gap.unwrap_or(2) as i32;
+        let series = self.chart.series.len() as i32;
+        let width = bottom_right.x - top_left.x;
+        for (category, _) in self.chart.categories.iter().enumerate() {
+            let category = category as i32;
+            let slot_start = top_left.x + width * category / categories + bar_gap / 2;
+            let slot_end = top_left.x + width * (category + 1) / categories - (bar_gap + 1) / 2;
+            let (mut negative, mut positive) = (zero, zero);
+            for (index, (values, color)) in self.chart.series.iter().enumerate() {
+                let value = values[category as usize];
+                let (x_start, x_end, base, top) = match self.chart.mode.unwrap_or_default() {
+                    BarMode::Grouped => {
+                        let index = index as i32;
+                        let slot_width = slot_end - slot_start + 1;
+                        (
+                            slot_start + slot_width * index / series,
+                            slot_start + slot_width * (index + 1) / series - 1,
+                            zero,
+                            value,
+                        )
+                    }
+                    BarMode::Stacked if value < 0 => {
+                        let base = negative;
+                        negative = negative.saturating_add(value);
+                        (slot_start, slot_end, base, negative)
+                    }
+                    BarMode::Stacked => {
+                        let base = positive;
+                        positive = positive.saturating_add(value);
+                        (slot_start, slot_end, base, positive)
+                    }
+                };
+                if x_end < x_start || value == 0 {
+                    continue;
+                }
+                f(Bar {
+                    x_start,
+                    x_end,
+                    base,
+                    top,
+                    #[cfg(feature = "text")]
+                    value,
+                    color: *color,
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+area_drawable!(DrawableBarChart);
+
+impl<'a, C> Drawable for DrawableBarChart<'a, C>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    /// bounding box of pixels touched by the chart, for flushing only changed region to the display
+    type Output = Rectangle;
+
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<Rectangle, D::Error> {
+        let display = &mut DirtyTracker::new(display);
+        let (top_left, bottom_right) = self.plot_area();
+        let categories = self.chart.categories.len() as i32;
+        let transform = match PlotTransform::new(
+            0..categories.max(1),
+            self.chart.y_range(),
+            top_left,
+            bottom_right,
+        ) {
+            Ok(transform) => transform,
+            Err(_) => return Ok(display.dirty()),
+        };
+        // bars grow from zero, or from the edge of the chart if zero is out of manual range
+        let Range { start, end } = *transform.y_range();
+        let zero = 0.max(start.min(end)).min(start.max(end));
+        {
+            let mut bars = display.clipped(&transform.area());
+            self.for_each_bar(top_left, bottom_right, zero, |bar| {
+                bars.fill_solid(
+                    &Rectangle::with_corners(
+                        Point::new(bar.x_start, transform.y_to_screen(bar.top)),
+                        Point::new(bar.x_end, transform.y_to_screen(bar.base)),
+                    ),
+                    bar.color,
+                )
+            })?;
+        }
+
+        let color = self.color.unwrap_or_default();
+        self.y_axis(top_left, bottom_right).draw(display)?;
+        Line::new(
+            Point::new(top_left.x, transform.y_to_screen(zero)),
+            Point::new(bottom_right.x, transform.y_to_screen(zero)),
+        )
+        .into_styled(PrimitiveStyle::with_stroke(color, 1))
+        .draw(display)?;
+
+        #[cfg(feature = "text")]
+        if self.value_labels {
+            let area = transform.area();
+            let mut previous = None;
+            self.for_each_bar(top_left, bottom_right, zero, |bar| {
+                let text = format_value(bar.value, None);
+                let style = self.text_style();
+                let text = Text::with_baseline(&text, Point::zero(), style, Baseline::Top);
+                let size = text.bounding_box().size;
+                let anchor = Point::new(
+                    (bar.x_start + bar.x_end) / 2,
+                    transform.y_to_screen(bar.top),
+                );
+                match place_label(anchor, size, bar.value < 0, &area, previous) {
+                    Some(label) => {
+                        previous = Some(label);
+                        text.translate(label.top_left).draw(display).map(|_| ())
+                    }
+                    None => Ok(()),
+                }
+            })?;
+        }
+        #[cfg(feature = "text")]
+        {
+            let width = bottom_right.x - top_left.x;
+            let label_style = TextStyleBuilder::new()
+                .alignment(Alignment::Center)
+                .baseline(Baseline::Top)
+                .build();
+            for (category, label) in self.chart.categories.iter().enumerate() {
+                let category = category as i32;
+                let center = top_left.x + width * (2 * category + 1) / (2 * categories);
+                Text::with_text_style(
+                    label,
+                    Point::new(center, bottom_right.y + 2),
+                    self.text_style(),
+                    label_style,
+                )
+                .draw(display)?;
+            }
+        }
+        Ok(display.dirty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "text")]
+    use crate::bar_chart::DrawableBarChart;
+    use crate::bar_chart::{BarChart, BarMode};
+    use core::ops::Range;
+    use embedded_graphics::pixelcolor::BinaryColor;
+    #[cfg(feature = "text")]
+    use embedded_graphics::{mock_display::MockDisplay, prelude::*};
+    use test_case::test_case;
+
+    const CATEGORIES: [&str; 3] = ["a", "b", "c"];
+    const SERIES: [(&[i32], BinaryColor); 2] = [
+        (&[3, -2, 5], BinaryColor::On),
+        (&[4, -1, 2], BinaryColor::On),
+    ];
+
+    #[test_case(BarMode::Grouped => -2..5; "grouped")]
+    #[test_case(BarMode::Stacked => -3..7; "stacked")]
+    fn auto_y_range(mode: BarMode) -> Range<i32> {
+        BarChart::new(&CATEGORIES, &SERIES)
+            .unwrap()
+            .set_mode(mode)
+            .y_range()
+    }
+
+    #[test]
+    #[cfg(feature = "text")]
+    fn value_labels_above_bars() {
+        let series: [(&[i32], BinaryColor); 1] = [(&[5, 5, 5], BinaryColor::On)];
+        let chart = BarChart::new(&CATEGORIES, &series)
+            .unwrap()
+            .set_y_range(0..10)
+            .into_drawable(Point::zero(), Point::new(60, 60))
+            .set_color(BinaryColor::On);
+        let label_pixels = |chart: &DrawableBarChart<BinaryColor>| {
+            let mut display = MockDisplay::new();
+            display.set_allow_overdraw(true);
+            chart.draw(&mut display).unwrap();
+            let (top_left, bottom_right) = chart.plot_area();
+            // area above the bars, clear of the axis ticks
+            let middle = (top_left.y + bottom_right.y) / 2;
+            (top_left.x + 4..bottom_right.x)
+                .flat_map(|x| (top_left.y..middle - 1).map(move |y| Point::new(x, y)))
+                .filter(|p| display.get_pixel(*p).is_some())
+                .count()
+        };
+        assert_eq!(label_pixels(&chart), 0);
+        assert!(label_pixels(&chart.set_value_labels()) > 0);
+    }
+
+    #[test]
+    fn mismatched_series() {
+        let series: [(&[i32], BinaryColor); 1] = [(&[1, 2], BinaryColor::On)];
+        assert!(BarChart::new(&CATEGORIES, &series).is_err());
+    }
+}
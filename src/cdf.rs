@@ -0,0 +1,221 @@
+//! Empirical cumulative distribution of samples, e.g. latency or jitter analysis on the device
+//!
+//! [CdfPlot] shows which share of the samples is at or below each value as a step curve
+//! with 0-100% Y axis - percentiles can be read directly from the plot.
+use core::iter::once;
+use core::ops::Range;
+
+use embedded_graphics::{
+    draw_target::DrawTargetExt, mono_font::MonoTextStyle, prelude::*, primitives::Rectangle,
+};
+
+use crate::axis::{Axis, Scale};
+use crate::curve::PlotPoint;
+use crate::dirty::DirtyTracker;
+use crate::edge_axes::EdgeAxes;
+use crate::placement::{area_drawable, IntoDrawableArea};
+use crate::transform::PlotTransform;
+
+/// Display-agnostic distribution of sorted samples
+#[derive(Clone, Copy)]
+pub struct CdfPlot<'a> {
+    samples: &'a [i32],
+}
+
+impl<'a> CdfPlot<'a> {
+    /// create distribution of the samples, they are sorted in place
+    pub fn new(samples: &'a mut [i32]) -> Result<CdfPlot<'a>, &'static str> {
+        samples.sort_unstable();
+        CdfPlot::from_sorted(samples)
+    }
+
+    /// create distribution of samples that are already sorted in ascending order
+    pub fn from_sorted(samples: &'a [i32]) -> Result<CdfPlot<'a>, &'static str> {
+        if samples.is_empty() {
+            Err("No samples provided")
+        } else {
+            Ok(CdfPlot { samples })
+        }
+    }
+
+    /// smallest sample that given percentage of samples is at or below, e.g. 99 for the 99th percentile
+    pub fn percentile(&self, percent: u32) -> i32 {
+        let len = self.samples.len();
+        let count = (percent.min(100) as usize * len + 99) / 100;
+        self.samples[count.clamp(1, len) - 1]
+    }
+
+    /// range of sample values, shown on X axis
+    pub fn x_range(&self) -> Range<i32> {
+        let (min, max) = (self.samples[0], self.samples[self.samples.len() - 1]);
+        if min == max {
+            min..max + 1
+        } else {
+            min..max
+        }
+    }
+
+    /// corners of the step curve - at each distinct value the percentage of samples at or below it
+    /// goes up from the previous level
+    pub fn points(&self) -> impl Iterator<Item = PlotPoint> + Clone + '_ {
+        let samples = self.samples;
+        let percent = move |count: usize| (count as u64 * 100 / samples.len() as u64) as i32;
+        (0..samples.len())
+            .filter(move |index| samples.get(index + 1) != Some(&samples[*index]))
+            .scan(0, move |below, last| {
+                let from = percent(*below);
+                *below = last + 1;
+                Some((samples[last], from, percent(last + 1)))
+            })
+            .flat_map(|(x, from, to)| {
+                once(PlotPoint { x, y: from }).chain(once(PlotPoint { x, y: to }))
+            })
+    }
+
+    /// convert to drawable form for specific display
+    pub fn into_drawable<C>(self, top_left: Point, bottom_right: Point) -> DrawableCdfPlot<'a, C>
+    where
+        C: PixelColor + Default,
+    {
+        DrawableCdfPlot {
+            cdf: self,
+            color: None,
+            axis_color: None,
+            text_style: None,
+            x_label: None,
+            top_left,
+            bottom_right,
+        }
+    }
+}
+
+impl<'a, C> IntoDrawableArea<DrawableCdfPlot<'a, C>> for CdfPlot<'a>
+where
+    C: PixelColor + Default,
+{
+    fn into_drawable_between(self, top_left: Point, bottom_right: Point) -> DrawableCdfPlot<'a, C> {
+        self.into_drawable(top_left, bottom_right)
+    }
+}
+
+/// Drawable distribution plot, constructed for specific display
+pub struct DrawableCdfPlot<'a, C>
+where
+    C: PixelColor + Default,
+{
+    cdf: CdfPlot<'a>,
+    color: Option<C>,
+    axis_color: Option<C>,
+    text_style: Option<MonoTextStyle<'a, C>>,
+    x_label: Option<&'a str>,
+    top_left: Point,
+    bottom_right: Point,
+}
+
+/// builder methods to modify plot decoration
+impl<'a, C> DrawableCdfPlot<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// set color of the step curve
+    pub fn set_color(mut self, color: C) -> DrawableCdfPlot<'a, C> {
+        self.color = Some(color);
+        self
+    }
+
+    /// if not set, main color will be used
+    pub fn set_axis_color(mut self, color: C) -> DrawableCdfPlot<'a, C> {
+        self.axis_color = Some(color);
+        self
+    }
+
+    /// set style of tick labels and titles, if not set, axis color and 5x8 font are used
+    pub fn set_text_style(mut self, style: MonoTextStyle<'a, C>) -> DrawableCdfPlot<'a, C> {
+        self.text_style = Some(style);
+        self
+    }
+
+    /// set title of the value axis, e.g. "latency \[us\]", no title if not set
+    pub fn set_x_label(mut self, label: &'a str) -> DrawableCdfPlot<'a, C> {
+        self.x_label = Some(label);
+        self
+    }
+
+    /// value axis along the bottom edge and percentage axis along the left edge
+    fn axes(&self) -> EdgeAxes<'a, C> {
+        let mut x_axis = Axis::new(self.cdf.x_range()).set_scale(Scale::Auto);
+        if let Some(label) = self.x_label {
+            x_axis = x_axis.set_title(label);
+        }
+        EdgeAxes {
+            x_axis,
+            y_axis: Axis::new(0..100).set_title("%").set_scale(Scale::Fixed(25)),
+            color: self.axis_color.or(self.color).unwrap_or_default(),
+            text_style: self.text_style,
+        }
+    }
+
+    /// area where the curve is drawn - the plot bounds without space for labels
+    pub fn plot_area(&self) -> (Point, Point) {
+        self.axes().plot_area(self.top_left, self.bottom_right)
+    }
+}
+
+area_drawable!(DrawableCdfPlot);
+
+impl<'a, C> Drawable for DrawableCdfPlot<'a, C>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    /// bounding box of pixels touched by the plot, for flushing only changed region to the display
+    type Output = Rectangle;
+
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<Rectangle, D::Error> {
+        let display = &mut DirtyTracker::new(display);
+        let axes = self.axes();
+        let (top_left, bottom_right) = axes.plot_area(self.top_left, self.bottom_right);
+        let (x_axis, y_axis) = axes.place(top_left, bottom_right);
+        let transform = match PlotTransform::new(self.cdf.x_range(), 0..100, top_left, bottom_right)
+        {
+            Ok(transform) => transform,
+            Err(_) => return Ok(display.dirty()),
+        };
+        transform
+            .drawable_curve(self.cdf.points())
+            .set_color(self.color.unwrap_or_default())
+            .set_thickness(1)
+            .draw(&mut display.clipped(&transform.area()))?;
+        x_axis.draw(display)?;
+        y_axis.draw(display)?;
+        Ok(display.dirty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cdf::CdfPlot;
+    use heapless::Vec;
+    use test_case::test_case;
+
+    #[test]
+    fn steps() {
+        let mut samples = [4, 2, 1, 2];
+        let cdf = CdfPlot::new(&mut samples).unwrap();
+        let points: Vec<(i32, i32), 8> = cdf.points().map(|p| (p.x, p.y)).collect();
+        assert_eq!(
+            &points[..],
+            &[(1, 0), (1, 25), (2, 25), (2, 75), (4, 75), (4, 100)]
+        );
+    }
+
+    #[test_case(0 => 1; "lowest")]
+    #[test_case(50 => 2; "median")]
+    #[test_case(76 => 4; "above step")]
+    #[test_case(100 => 4; "highest")]
+    fn percentile(percent: u32) -> i32 {
+        CdfPlot::from_sorted(&[1, 2, 2, 4])
+            .unwrap()
+            .percentile(percent)
+    }
+}
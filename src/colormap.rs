@@ -0,0 +1,131 @@
+//! Colormaps turning values into colors, e.g. for heatmaps or gradient fills
+//!
+//! Maps are stored as small lookup tables of 9 anchor colors with linear interpolation between them,
+//! which is cheap enough to be evaluated per pixel on microcontrollers.
+//! ```rust
+//! # use embedded_graphics::pixelcolor::Rgb565;
+//! # use embedded_plots::colormap::{normalize, Colormap};
+//! let level = normalize(25, 0..100);
+//! let color: Rgb565 = Colormap::Viridis.color(level);
+//! ```
+use core::ops::Range;
+
+use embedded_graphics::pixelcolor::Rgb888;
+
+use crate::range_conv::Scalable;
+
+/// anchor colors of viridis colormap at 0, 1/8, 2/8, ... 1
+const VIRIDIS: [(u8, u8, u8); 9] = [
+    (68, 1, 84),
+    (71, 44, 122),
+    (59, 82, 139),
+    (44, 113, 142),
+    (33, 145, 140),
+    (40, 174, 128),
+    (92, 200, 99),
+    (170, 220, 50),
+    (253, 231, 37),
+];
+
+/// anchor colors of turbo colormap at 0, 1/8, 2/8, ... 1
+const TURBO: [(u8, u8, u8); 9] = [
+    (48, 18, 59),
+    (70, 107, 227),
+    (41, 187, 236),
+    (36, 236, 166),
+    (164, 252, 60),
+    (227, 216, 53),
+    (251, 128, 34),
+    (215, 57, 6),
+    (122, 4, 3),
+];
+
+/// Available colormaps
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Colormap {
+    /// perceptually uniform blue-green-yellow map, readable also when printed in grayscale
+    Viridis,
+    /// rainbow-like blue-green-red map with high contrast
+    Turbo,
+    /// black to white
+    Grayscale,
+}
+
+impl Default for Colormap {
+    fn default() -> Self {
+        Colormap::Viridis
+    }
+}
+
+impl Colormap {
+    /// RGB components of the color for the level, 0 being the lowest and 255 the highest value
+    pub fn rgb(self, level: u8) -> (u8, u8, u8) {
+        let table = match self {
+            Colormap::Viridis => &VIRIDIS,
+            Colormap::Turbo => &TURBO,
+            Colormap::Grayscale => return (level, level, level),
+        };
+        // levels are spread over 8 intervals between the anchors
+        let position = level as i32 * 8;
+        let index = (position / 255) as usize;
+        let fraction = position % 255;
+        let (from, to) = (table[index], table[(index + 1).min(table.len() - 1)]);
+        let interpolate =
+            |from: u8, to: u8| (from as i32 + (to as i32 - from as i32) * fraction / 255) as u8;
+        (
+            interpolate(from.0, to.0),
+            interpolate(from.1, to.1),
+            interpolate(from.2, to.2),
+        )
+    }
+
+    /// color for the level, 0 being the lowest and 255 the highest value,
+    /// in any color type convertible from [Rgb888], e.g. [Rgb565](embedded_graphics::pixelcolor::Rgb565)
+    pub fn color<C>(self, level: u8) -> C
+    where
+        C: From<Rgb888>,
+    {
+        let (r, g, b) = self.rgb(level);
+        C::from(Rgb888::new(r, g, b))
+    }
+}
+
+/// turn value from given range into colormap level, values outside of the range are clamped
+pub fn normalize(value: i32, range: Range<i32>) -> u8 {
+    if range.start == range.end {
+        return 0;
+    }
+    let (low, high) = (range.start.min(range.end), range.start.max(range.end));
+    value
+        .clamp(low, high)
+        .scale_between_ranges(&range, &(0..255))
+        .clamp(0, 255) as u8
+}
+
+#[cfg(test)]
+#[allow(clippy::reversed_empty_ranges)]
+mod tests {
+    use crate::colormap::{normalize, Colormap};
+    use core::ops::Range;
+    use test_case::test_case;
+
+    #[test_case(Colormap::Viridis, 0 => (68, 1, 84); "viridis low")]
+    #[test_case(Colormap::Viridis, 255 => (253, 231, 37); "viridis high")]
+    #[test_case(Colormap::Viridis, 16 => (69, 22, 103); "viridis interpolated")]
+    #[test_case(Colormap::Turbo, 128 => (164, 252, 60); "turbo middle")]
+    #[test_case(Colormap::Grayscale, 100 => (100, 100, 100); "grayscale")]
+    fn rgb(colormap: Colormap, level: u8) -> (u8, u8, u8) {
+        colormap.rgb(level)
+    }
+
+    #[test_case(50, 0..100 => 127; "middle")]
+    #[test_case(-10, 0..100 => 0; "clamped low")]
+    #[test_case(200, 0..100 => 255; "clamped high")]
+    #[test_case(0, 100..0 => 255; "descending")]
+    #[test_case(5, 5..5 => 0; "empty range")]
+    fn normalized(value: i32, range: Range<i32>) -> u8 {
+        normalize(value, range)
+    }
+}
@@ -0,0 +1,285 @@
+//! Crosshair cursor with value readout
+//!
+//! Cursor is positioned by data X value and is meant to be driven externally,
+//! e.g. by encoder or buttons on the device. It draws vertical line across the plot,
+//! marker on the curve point nearest to the cursor and a small box with (x, y) values of that point.
+#[cfg(feature = "text")]
+use core::fmt::Write;
+#[cfg(feature = "text")]
+use heapless::String;
+
+#[cfg(feature = "text")]
+use embedded_graphics::{
+    mono_font::ascii::FONT_5X8,
+    primitives::{PrimitiveStyleBuilder, Rectangle},
+    text::{Baseline, Text},
+};
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    prelude::*,
+    primitives::{Circle, Line, PrimitiveStyle},
+};
+
+use crate::curve::{Curve, PlotPoint};
+use crate::transform::PlotTransform;
+
+/// Display-agnostic cursor, only holds its position in data coordinates
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cursor {
+    x: i32,
+}
+
+impl Cursor {
+    /// create cursor at given X value
+    pub fn new(x: i32) -> Cursor {
+        Cursor { x }
+    }
+
+    /// X value the cursor is placed at
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    /// move cursor by `delta` along X axis, e.g. on encoder step
+    pub fn step(&mut self, delta: i32) {
+        self.x = self.x.saturating_add(delta);
+    }
+
+    /// turn cursor into drawable object, snapped to the nearest point of the curve
+    pub fn into_drawable<'a, C>(
+        self,
+        curve: &Curve,
+        transform: &PlotTransform,
+    ) -> DrawableCursor<'a, C>
+    where
+        C: PixelColor + Default,
+    {
+        let line_x = if transform.contains_x(self.x) {
+            Some(transform.x_to_screen(self.x))
+        } else {
+            None
+        };
+        let point = curve
+            .nearest_point(self.x)
+            .map(|p| (p, transform.to_screen(&p)));
+        DrawableCursor {
+            line_x,
+            point,
+            top: transform.top_left().y,
+            bottom: transform.bottom_right().y,
+            #[cfg(feature = "text")]
+            right: transform.bottom_right().x,
+            color: None,
+            marker_color: None,
+            box_color: None,
+            text_style: None,
+        }
+    }
+}
+
+/// Drawable cursor, constructed for specific display
+pub struct DrawableCursor<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// screen column of the cursor line, if cursor is within the plot
+    line_x: Option<i32>,
+    /// nearest curve point, in data and screen coordinates
+    point: Option<(PlotPoint, Point)>,
+    top: i32,
+    bottom: i32,
+    #[cfg(feature = "text")]
+    right: i32,
+    color: Option<C>,
+    marker_color: Option<C>,
+    box_color: Option<C>,
+    text_style: Option<MonoTextStyle<'a, C>>,
+}
+
+/// builder methods to modify cursor decoration
+impl<'a, C> DrawableCursor<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// set color of the cursor line and readout box border, also used for text if text style is not set
+    pub fn set_color(mut self, color: C) -> DrawableCursor<'a, C> {
+        self.color = Some(color);
+        self
+    }
+
+    /// if not set, main color will be used
+    pub fn set_marker_color(mut self, color: C) -> DrawableCursor<'a, C> {
+        self.marker_color = Some(color);
+        self
+    }
+
+    /// set fill color of readout box, if not set, box is transparent
+    pub fn set_box_color(mut self, color: C) -> DrawableCursor<'a, C> {
+        self.box_color = Some(color);
+        self
+    }
+
+    pub fn set_text_style(mut self, style: MonoTextStyle<'a, C>) -> DrawableCursor<'a, C> {
+        self.text_style = Some(style);
+        self
+    }
+
+    /// box with (x, y) values of the marked point, next to the line
+    #[cfg(feature = "text")]
+    fn draw_readout<D: DrawTarget<Color = C>>(
+        &self,
+        display: &mut D,
+        line_x: i32,
+        color: C,
+    ) -> Result<(), D::Error> {
+        let value = match self.point {
+            Some((value, _)) => value,
+            None => return Ok(()),
+        };
+        let mut buf: String<24> = String::new();
+        write!(buf, "{}, {}", value.x, value.y).ok();
+        let character_style = self
+            .text_style
+            .unwrap_or_else(|| MonoTextStyle::new(&FONT_5X8, color));
+        let mut text = Text::with_baseline(
+            &buf,
+            Point {
+                x: line_x + 3,
+                y: self.top + 2,
+            },
+            character_style,
+            Baseline::Top,
+        );
+        // keep readout inside the plot, flip it to the left side of the line if needed
+        let width = text.bounding_box().size.width as i32;
+        if line_x + 3 + width + 2 > self.right {
+            text.position.x = line_x - 3 - width;
+        }
+        let mut box_style = PrimitiveStyleBuilder::new()
+            .stroke_color(color)
+            .stroke_width(1);
+        if let Some(box_color) = self.box_color {
+            box_style = box_style.fill_color(box_color);
+        }
+        let text_area = text.bounding_box();
+        Rectangle::new(
+            text_area.top_left - Point::new(2, 2),
+            text_area.size + Size::new(4, 4),
+        )
+        .into_styled(box_style.build())
+        .draw(display)?;
+        text.draw(display)?;
+        Ok(())
+    }
+}
+
+impl<'a, C> Drawable for DrawableCursor<'a, C>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<(), D::Error> {
+        let color = self.color.unwrap_or_default();
+        let marker_color = self.marker_color.unwrap_or(color);
+        let line_x = match self.line_x {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+
+        Line::new(
+            Point {
+                x: line_x,
+                y: self.top,
+            },
+            Point {
+                x: line_x,
+                y: self.bottom,
+            },
+        )
+        .into_styled(PrimitiveStyle::with_stroke(color, 1))
+        .draw(display)?;
+
+        if let Some((_, position)) = self.point {
+            Circle::with_center(position, 5)
+                .into_styled(PrimitiveStyle::with_stroke(marker_color, 1))
+                .draw(display)?;
+            #[cfg(feature = "text")]
+            self.draw_readout(display, line_x, color)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cursor::Cursor;
+    use crate::curve::{Curve, PlotPoint};
+    use crate::transform::PlotTransform;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::*};
+    use test_case::test_case;
+
+    const POINTS: [PlotPoint; 3] = [
+        PlotPoint { x: 0, y: 0 },
+        PlotPoint { x: 5, y: 5 },
+        PlotPoint { x: 10, y: 10 },
+    ];
+
+    fn transform() -> PlotTransform {
+        PlotTransform::new(0..10, 0..10, Point::new(2, 2), Point::new(60, 60)).unwrap()
+    }
+
+    fn draw(cursor: Cursor) -> MockDisplay<BinaryColor> {
+        let curve = Curve::from_data(&POINTS);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        cursor
+            .into_drawable(&curve, &transform())
+            .set_color(BinaryColor::On)
+            .draw(&mut display)
+            .unwrap();
+        display
+    }
+
+    #[test_case(0, 3 => 3; "single step")]
+    #[test_case(i32::MAX, 1 => i32::MAX; "saturates")]
+    fn step(x: i32, delta: i32) -> i32 {
+        let mut cursor = Cursor::new(x);
+        cursor.step(delta);
+        cursor.x()
+    }
+
+    #[test]
+    fn line_and_marker_on_nearest_point() {
+        let display = draw(Cursor::new(4));
+        let line_x = transform().x_to_screen(4);
+        assert_eq!(
+            display.get_pixel(Point::new(line_x, 60)),
+            Some(BinaryColor::On)
+        );
+        let marker = transform().to_screen(&POINTS[1]);
+        assert_eq!(
+            display.get_pixel(marker + Point::new(2, 0)),
+            Some(BinaryColor::On)
+        );
+    }
+
+    #[test]
+    fn outside_of_plot_not_drawn() {
+        assert_eq!(draw(Cursor::new(20)), MockDisplay::new());
+    }
+
+    #[test]
+    #[cfg(feature = "text")]
+    fn readout_flips_at_right_edge() {
+        let display = draw(Cursor::new(10));
+        let line_x = transform().x_to_screen(10);
+        let area = display.affected_area();
+        assert!(area.top_left.x < line_x - 10);
+        // only the marker reaches right of the line
+        assert!(area.top_left.x + (area.size.width as i32) <= line_x + 3);
+    }
+}
@@ -1,114 +1,559 @@
-use core::ops::Range;
+use core::iter::once;
+use core::ops::{Deref, Range};
 
-use crate::range_conv::Scalable;
-use itertools::{Itertools, MinMaxResult, MinMaxResult::MinMax};
+use crate::derived::{CumulativeSum, CurveOperation, Difference, MovingAverage, Pointwise};
+use crate::dirty::DirtyTracker;
+use crate::envelope::fill_column;
+use crate::spline::{catmull_rom, MAX_SUBDIVISIONS};
+use crate::transform::{OutOfRange, PlotTransform};
+use itertools::{Either, Itertools, MinMaxResult, MinMaxResult::MinMax};
 
-use embedded_graphics::{draw_target::DrawTarget, geometry::Point, Drawable};
+use embedded_graphics::{
+    draw_target::{DrawTarget, DrawTargetExt},
+    geometry::Point,
+    transform::Transform,
+    Drawable,
+};
 
-use embedded_graphics::primitives::Primitive;
-use embedded_graphics::{primitives::Line, primitives::PrimitiveStyle};
 use embedded_graphics::pixelcolor::PixelColor;
+use embedded_graphics::primitives::{Circle, Polyline, Primitive, Rectangle};
+use embedded_graphics::{primitives::Line, primitives::PrimitiveStyle};
+
+use crate::style::{
+    draw_line_runs, draw_patterned_line, fill_line_runs, DefaultStyle, LinePattern,
+};
 
 /// representation of the single point on the curve
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PlotPoint {
     pub x: i32,
     pub y: i32,
 }
 
+impl PlotPoint {
+    /// value of `y` that marks a gap (missing sample, sensor dropout),
+    /// curve is not drawn across the gap instead of connecting neighbouring points with misleading segment
+    pub const GAP: i32 = i32::MIN;
+
+    /// create point marking a missing sample at given `x`
+    pub fn gap(x: i32) -> PlotPoint {
+        PlotPoint {
+            x,
+            y: PlotPoint::GAP,
+        }
+    }
+
+    /// check if point marks a gap
+    pub fn is_gap(&self) -> bool {
+        self.y == PlotPoint::GAP
+    }
+}
+
+/// color of the point, the one above threshold if it's set and the point lies above it
+fn point_color<C>(point: Point, color: C, threshold: Option<(i32, C)>) -> C {
+    match threshold {
+        Some((y, color_above)) if point.y < y => color_above,
+        _ => color,
+    }
+}
+
+/// x where the segment crosses the row `y` lying between its ends, computed in i64 so that
+/// distant points don't overflow, clamped to the segment
+fn crossing_x(start: Point, end: Point, y: i32) -> i32 {
+    let (dx, dy) = (end.x as i64 - start.x as i64, end.y as i64 - start.y as i64);
+    let x = start.x as i64 + (y as i64 - start.y as i64).saturating_mul(dx) / dy;
+    x.clamp(start.x.min(end.x) as i64, start.x.max(end.x) as i64) as i32
+}
+
+/// draw consecutive points as single styled polyline
+fn draw_polyline_chunk<D>(
+    display: &mut D,
+    points: &[Point],
+    color: D::Color,
+    thickness: u32,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+{
+    Polyline::new(points)
+        .into_styled(PrimitiveStyle::with_stroke(color, thickness))
+        .draw(display)
+}
+
+/// check if points are sorted by X
+fn is_sorted(points: &[PlotPoint]) -> bool {
+    points.windows(2).all(|pair| pair[0].x <= pair[1].x)
+}
+
+/// X of the n-th value of data with implicit X
+fn implicit_x(x0: i32, dx: i32, index: usize) -> i32 {
+    (x0 as i64 + dx as i64 * index as i64).clamp(i32::MIN as i64, i32::MAX as i64) as i32
+}
+
+/// Data backing a curve - explicit points, or Y values only with evenly spaced X: the n-th value is placed
+/// at `x = n * dx + x0`. Implicit X halves the memory needed by time series and lets raw sample buffers
+/// be plotted without conversion.
+#[derive(Clone, Copy)]
+pub enum XYData<'a> {
+    Points(&'a [PlotPoint]),
+    /// Y values, [PlotPoint::GAP] marks missing samples
+    Y {
+        y: &'a [i32],
+        x0: i32,
+        dx: i32,
+    },
+    /// raw unsigned samples, e.g. ADC capture buffer
+    U16 {
+        y: &'a [u16],
+        x0: i32,
+        dx: i32,
+    },
+    /// raw signed samples
+    I16 {
+        y: &'a [i16],
+        x0: i32,
+        dx: i32,
+    },
+}
+
+impl<'a> XYData<'a> {
+    pub fn len(&self) -> usize {
+        match self {
+            XYData::Points(points) => points.len(),
+            XYData::Y { y, .. } => y.len(),
+            XYData::U16 { y, .. } => y.len(),
+            XYData::I16 { y, .. } => y.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// point at given index, None past the end of the data
+    pub fn get(&self, index: usize) -> Option<PlotPoint> {
+        let point = |x0, dx, y| PlotPoint {
+            x: implicit_x(x0, dx, index),
+            y,
+        };
+        match *self {
+            XYData::Points(points) => points.get(index).copied(),
+            XYData::Y { y, x0, dx } => y.get(index).map(|y| point(x0, dx, *y)),
+            XYData::U16 { y, x0, dx } => y.get(index).map(|y| point(x0, dx, *y as i32)),
+            XYData::I16 { y, x0, dx } => y.get(index).map(|y| point(x0, dx, *y as i32)),
+        }
+    }
+
+    /// iterate over the points, implicit X is computed on the fly
+    pub fn points(&self) -> CurvePoints<'a> {
+        CurvePoints {
+            data: *self,
+            range: 0..self.len(),
+        }
+    }
+
+    fn is_sorted(&self) -> bool {
+        match *self {
+            XYData::Points(points) => is_sorted(points),
+            XYData::Y { dx, .. } | XYData::U16 { dx, .. } | XYData::I16 { dx, .. } => dx >= 0,
+        }
+    }
+}
+
+/// Iterator over points of the curve, points of data with implicit X are computed on the fly
+#[derive(Clone)]
+pub struct CurvePoints<'a> {
+    data: XYData<'a>,
+    range: Range<usize>,
+}
+
+impl<'a> Iterator for CurvePoints<'a> {
+    type Item = PlotPoint;
+
+    fn next(&mut self) -> Option<PlotPoint> {
+        let index = self.range.next()?;
+        self.data.get(index)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<PlotPoint> {
+        let index = self.range.nth(n)?;
+        self.data.get(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for CurvePoints<'a> {
+    fn next_back(&mut self) -> Option<PlotPoint> {
+        let index = self.range.next_back()?;
+        self.data.get(index)
+    }
+}
+
+impl<'a> ExactSizeIterator for CurvePoints<'a> {}
+
+/// range of values, empty range at zero if there are none. Single value is padded by one on both sides,
+/// so the range can be scaled and the value lands in the middle of it
+pub(crate) fn value_range(values: impl Iterator<Item = i32>) -> Range<i32> {
+    match values.minmax() {
+        MinMaxResult::NoElements => 0..0,
+        MinMaxResult::OneElement(v) => v.saturating_sub(1)..v.saturating_add(1),
+        MinMax(min, max) if min == max => min.saturating_sub(1)..max.saturating_add(1),
+        MinMax(min, max) => min..max,
+    }
+}
+
 /// curve object that contains data to be plotted
 pub struct Curve<'a> {
-    /// slice of points to be drawn
-    points: &'a [PlotPoint],
+    /// points or Y values to be drawn
+    data: XYData<'a>,
     pub x_range: Range<i32>,
     pub y_range: Range<i32>,
+    /// points are sorted by X, which allows binary search
+    sorted: bool,
+    out_of_range: Option<OutOfRange>,
 }
 
 impl<'a> Curve<'a> {
     /// create new curve data with manual ranges
     pub fn new(points: &'a [PlotPoint], x_range: Range<i32>, y_range: Range<i32>) -> Curve {
         Curve {
-            points,
+            data: XYData::Points(points),
             x_range,
             y_range,
+            sorted: is_sorted(points),
+            out_of_range: None,
         }
     }
 
-    /// create new curve data with ranges automatically deducted based on provided points
+    /// create new curve data with ranges automatically deducted based on provided points, gaps are skipped.
+    /// Ranges covering single value, e.g. of one point or of flat line, are padded around it.
     pub fn from_data(points: &'a [PlotPoint]) -> Curve {
-        let x_range = match points.iter().map(|p| (p.x)).minmax() {
-            MinMaxResult::NoElements => 0..0,
-            MinMaxResult::OneElement(v) => v..v,
-            MinMax(min, max) => min..max,
-        };
+        Curve::from_xy_data(XYData::Points(points))
+    }
 
-        let y_range = match points.iter().map(|p| (p.y)).minmax() {
-            MinMaxResult::NoElements => 0..0,
-            MinMaxResult::OneElement(v) => v..v,
-            MinMax(min, max) => min..max,
-        };
+    /// create curve of raw samples, e.g. ADC capture buffer, plotted against evenly spaced X:
+    /// n-th sample is placed at `x_start + n * x_step`. Points are computed on the fly, so no copy
+    /// of the buffer is needed. Ranges are deduced from the samples.
+    pub fn from_samples(samples: &'a [u16], x_start: i32, x_step: i32) -> Curve<'a> {
+        Curve::from_xy_data(XYData::U16 {
+            y: samples,
+            x0: x_start,
+            dx: x_step,
+        })
+    }
 
+    /// create curve of signed raw samples, see [Curve::from_samples]
+    pub fn from_i16_samples(samples: &'a [i16], x_start: i32, x_step: i32) -> Curve<'a> {
+        Curve::from_xy_data(XYData::I16 {
+            y: samples,
+            x0: x_start,
+            dx: x_step,
+        })
+    }
+
+    /// create curve of any data with manual ranges
+    pub fn with_xy_data(data: XYData<'a>, x_range: Range<i32>, y_range: Range<i32>) -> Curve<'a> {
         Curve {
-            points,
+            data,
             x_range,
             y_range,
+            sorted: data.is_sorted(),
+            out_of_range: None,
         }
     }
 
+    /// create curve of any data with ranges automatically deducted, gaps are skipped
+    pub fn from_xy_data(data: XYData<'a>) -> Curve<'a> {
+        let values = data.points().filter(|p| !p.is_gap());
+        Curve {
+            data,
+            x_range: value_range(values.clone().map(|p| p.x)),
+            y_range: value_range(values.map(|p| p.y)),
+            sorted: data.is_sorted(),
+            out_of_range: None,
+        }
+    }
+
+    /// set what happens to points outside of the ranges the curve is drawn with,
+    /// segments are clipped at the boundary if not set
+    pub fn set_out_of_range(mut self, policy: OutOfRange) -> Curve<'a> {
+        self.out_of_range = Some(policy);
+        self
+    }
+
+    /// policy for points outside of the drawn ranges
+    pub fn out_of_range(&self) -> OutOfRange {
+        self.out_of_range.unwrap_or_default()
+    }
+
+    /// explicit points of the curve, none for curves of samples with implicit X -
+    /// see [Curve::iter] for points of any curve
+    pub fn points(&self) -> Option<&'a [PlotPoint]> {
+        match self.data {
+            XYData::Points(points) => Some(points),
+            _ => None,
+        }
+    }
+
+    /// iterate over points of the curve, implicit X of samples is computed on the fly
+    pub fn iter(&self) -> CurvePoints<'a> {
+        self.data.points()
+    }
+
+    /// data backing the curve
+    pub fn xy_data(&self) -> XYData<'a> {
+        self.data
+    }
+
+    fn points_between(&self, range: Range<usize>) -> CurvePoints<'a> {
+        CurvePoints {
+            data: self.data,
+            range,
+        }
+    }
+
+    /// number of points of the curve
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.len() == 0
+    }
+
+    /// point with X closest to the given value, gaps are skipped. Handy for snapping cursor or touch event
+    /// to the closest sample. Uses binary search when points are sorted by X, linear search otherwise.
+    pub fn nearest_point(&self, x: i32) -> Option<PlotPoint> {
+        let distance = |p: &PlotPoint| (p.x as i64 - x as i64).abs();
+        if !self.sorted {
+            return self
+                .iter()
+                .filter(|p| !p.is_gap())
+                .min_by_key(|p| distance(p));
+        }
+        // index of the first point with X not lower than the given one
+        let (mut index, mut end) = (0, self.len());
+        while index < end {
+            let middle = index + (end - index) / 2;
+            match self.data.get(middle) {
+                Some(p) if p.x < x => index = middle + 1,
+                _ => end = middle,
+            }
+        }
+        let before = self.points_between(0..index).rev().find(|p| !p.is_gap());
+        let after = self.points_between(index..self.len()).find(|p| !p.is_gap());
+        match (before, after) {
+            (Some(before), Some(after)) if distance(&after) < distance(&before) => Some(after),
+            (Some(before), _) => Some(before),
+            (None, after) => after,
+        }
+    }
+
+    /// running mean of last `window` points, computed on the fly during iteration
+    pub fn moving_average(&self, window: usize) -> MovingAverage<'a> {
+        MovingAverage::with_data(self.data, window)
+    }
+
+    /// change of Y between consecutive points, computed on the fly during iteration
+    pub fn difference(&self) -> Difference<'a> {
+        Difference::with_data(self.data)
+    }
+
+    /// running sum of Y values, computed on the fly during iteration
+    pub fn cumulative_sum(&self) -> CumulativeSum<'a> {
+        CumulativeSum::with_data(self.data)
+    }
+
+    /// pointwise combination with other curve sharing X values, e.g. difference of measured value
+    /// and setpoint, computed on the fly during iteration
+    pub fn combine(&self, other: &Curve<'a>, operation: CurveOperation) -> Pointwise<'a> {
+        Pointwise::with_data(self.data, other.data, operation)
+    }
+
+    /// create transform mapping curve ranges onto area of the display bounded by provided points
+    pub fn transform(
+        &self,
+        top_left: Point,
+        bottom_right: Point,
+    ) -> Result<PlotTransform, &'static str> {
+        PlotTransform::new(
+            self.x_range.clone(),
+            self.y_range.clone(),
+            top_left,
+            bottom_right,
+        )
+    }
+
     /// create curve that can be drawed on specific display
     pub fn into_drawable_curve<C>(
         &self,
         top_left: &'a Point,
         bottom_right: &'a Point,
-    ) -> Result<DrawableCurve<C, impl Iterator<Item = Point> + Clone + '_>, &str>
+    ) -> Result<DrawableCurve<C, impl Iterator<Item = Option<Point>> + Clone + '_>, &str>
     where
         C: PixelColor,
     {
-        if 
-        (top_left.x > bottom_right.x)|
-        (top_left.y > bottom_right.y)|
-        self.x_range.is_empty()|
-        self.y_range.is_empty() {
-            return Err("Invalid range");
-        }
-
-        let it = self.points.iter().map(move |p| Point {
-            x: p.x.scale_between_ranges(
-                &self.x_range,
-                &Range {
-                    start: top_left.x,
-                    end: bottom_right.x,
-                },
-            ),
-            y: p.y.scale_between_ranges(
-                &self.y_range,
-                &Range {
-                    start: bottom_right.y,
-                    end: top_left.y,
-                },
-            ),
+        let transform = self.transform(*top_left, *bottom_right)?;
+        let area = transform.area();
+        let policy = self.out_of_range();
+        let it = self.iter().map(move |p| {
+            let p = transform.limit(p, policy);
+            if p.is_gap() {
+                None
+            } else {
+                Some(transform.to_screen(&p))
+            }
         });
-        Ok(DrawableCurve {
-            scaled_data: it,
-            color: None,
-            thickness: None,
+        let curve = DrawableCurve::new(it);
+        Ok(match policy {
+            OutOfRange::Clip => curve.set_clip_area(area),
+            _ => curve,
         })
     }
 }
 
-/// Drawable curve object, constructed for specific display
+/// Curve data owned in storage of points `S` - fixed capacity `OwnedCurve`
+/// or growable `VecCurve`, borrowed as a regular [Curve] for drawing
+#[derive(Clone, Default)]
+pub struct CurveBuffer<S> {
+    points: S,
+    /// manual ranges, if not set, they are deduced from the points
+    ranges: Option<(Range<i32>, Range<i32>)>,
+}
+
+impl<S> CurveBuffer<S>
+where
+    S: Default + Deref<Target = [PlotPoint]>,
+{
+    /// create empty curve with ranges automatically deduced from added points
+    pub fn new() -> CurveBuffer<S> {
+        CurveBuffer::from_storage(S::default())
+    }
+
+    /// create empty curve with manual ranges
+    pub fn with_ranges(x_range: Range<i32>, y_range: Range<i32>) -> CurveBuffer<S> {
+        CurveBuffer {
+            points: S::default(),
+            ranges: Some((x_range, y_range)),
+        }
+    }
+
+    /// create curve of points already in the storage, with automatic ranges
+    pub fn from_storage(points: S) -> CurveBuffer<S> {
+        CurveBuffer {
+            points,
+            ranges: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn points(&self) -> &[PlotPoint] {
+        &self.points
+    }
+
+    /// storage of the points, e.g. to push new points or drop the oldest ones
+    pub fn storage_mut(&mut self) -> &mut S {
+        &mut self.points
+    }
+
+    /// borrow the data as a curve that can be drawn or put on a plot
+    pub fn as_curve(&self) -> Curve<'_> {
+        match &self.ranges {
+            Some((x_range, y_range)) => Curve::new(&self.points, x_range.clone(), y_range.clone()),
+            None => Curve::from_data(&self.points),
+        }
+    }
+}
+
+impl<'a, S> From<&'a CurveBuffer<S>> for Curve<'a>
+where
+    S: Default + Deref<Target = [PlotPoint]>,
+{
+    fn from(curve: &'a CurveBuffer<S>) -> Curve<'a> {
+        curve.as_curve()
+    }
+}
+
+/// Describes how points of the curve are turned into pixels
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RenderMode {
+    /// consecutive points are connected with lines
+    Lines,
+    /// consecutive points are connected with lines, but each line is emitted as filled runs of pixels
+    /// along its main direction instead of styled thick line. It cuts draw time substantially
+    /// on displays where every fill is a separate bus transaction (e.g. SPI), at the cost of rougher joins.
+    /// Line pattern is ignored.
+    FastLines,
+    /// for each screen column, vertical line is drawn from the minimum to the maximum of all samples
+    /// that land on this column, the way oscilloscopes do it - spikes are preserved even if there are
+    /// far more samples than pixels. Points are expected to be sorted by X, line thickness and pattern are ignored.
+    ColumnMinMax,
+}
+
+impl Default for RenderMode {
+    fn default() -> Self {
+        RenderMode::Lines
+    }
+}
+
+/// most edges of the filled curve crossing single row of pixels, rows crossing more are left unfilled
+const MAX_FILL_CROSSINGS: usize = 32;
+
+/// Drawable curve object, constructed for specific display,
+/// `None` in the scaled data marks a gap in the curve
 pub struct DrawableCurve<C, I> {
     scaled_data: I,
     color: Option<C>,
     thickness: Option<usize>,
+    pattern: Option<LinePattern>,
+    mode: Option<RenderMode>,
+    smoothing: Option<u32>,
+    threshold: Option<(i32, C)>,
+    closed: bool,
+    fill_color: Option<C>,
+    clip_area: Option<Rectangle>,
+    marker_every: Option<usize>,
+    offset: Point,
 }
 
 /// builder methods to modify curve decoration
 impl<C, I> DrawableCurve<C, I>
 where
     C: PixelColor,
-    I: Iterator<Item = Point> + Clone,
+    I: Iterator<Item = Option<Point>> + Clone,
 {
+    /// create drawable curve from already scaled points, e.g. produced by [PlotTransform::map_points]
+    pub fn new(scaled_data: I) -> DrawableCurve<C, I> {
+        DrawableCurve {
+            scaled_data,
+            color: None,
+            thickness: None,
+            pattern: None,
+            mode: None,
+            smoothing: None,
+            threshold: None,
+            closed: false,
+            fill_color: None,
+            clip_area: None,
+            marker_every: None,
+            offset: Point::zero(),
+        }
+    }
+
     /// set curve color
     pub fn set_color(mut self, color: C) -> DrawableCurve<C, I> {
         self.color = Some(color);
@@ -120,21 +565,306 @@ where
         self.thickness = Some(thickness);
         self
     }
+
+    /// set dash pattern of the curve line
+    pub fn set_pattern(mut self, pattern: LinePattern) -> DrawableCurve<C, I> {
+        self.pattern = Some(pattern);
+        self
+    }
+
+    /// set how points are turned into pixels
+    pub fn set_render_mode(mut self, mode: RenderMode) -> DrawableCurve<C, I> {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// draw smooth Catmull-Rom spline through the points instead of straight lines, each segment
+    /// between two points is split into given number of pieces (at most 32). Makes sparse samples
+    /// look like continuous trace. Ignored in [RenderMode::ColumnMinMax].
+    pub fn set_smoothing(mut self, subdivisions: u32) -> DrawableCurve<C, I> {
+        self.smoothing = Some(subdivisions.clamp(1, MAX_SUBDIVISIONS));
+        self
+    }
+
+    /// draw parts of the curve lying above given screen Y coordinate in different color, e.g. in alarm color
+    /// when values exceed the limit. Segments crossing the threshold are split at the crossing point.
+    /// Threshold is in screen coordinates, see [PlotTransform::y_to_screen].
+    pub fn set_threshold(mut self, y: i32, color_above: C) -> DrawableCurve<C, I> {
+        self.threshold = Some((y, color_above));
+        self
+    }
+
+    /// connect the last point of the curve back to the first one, e.g. for radar chart shapes
+    pub fn set_closed(mut self) -> DrawableCurve<C, I> {
+        self.closed = true;
+        self
+    }
+
+    /// fill the polygon enclosed by the curve, as if it was closed, before drawing the line.
+    /// Gaps are skipped, so all points form one polygon, overlapping parts are filled with even-odd rule.
+    /// Filled waveform envelope is made of upper edge followed by the lower one in reverse order.
+    /// Rows of pixels crossing more than 32 edges of the polygon are left unfilled.
+    pub fn set_fill_color(mut self, color: C) -> DrawableCurve<C, I> {
+        self.fill_color = Some(color);
+        self
+    }
+
+    /// draw only within given area of the display, e.g. plot area, so segments leaving it are cut at its boundary
+    pub fn set_clip_area(mut self, area: Rectangle) -> DrawableCurve<C, I> {
+        self.clip_area = Some(area);
+        self
+    }
+
+    /// draw dot on every n-th point and on the first and the last one, so samples of long curves
+    /// stand out without drowning the plot in symbols. Gaps count as points. Ignored in [RenderMode::ColumnMinMax].
+    pub fn set_marker_every(mut self, n: usize) -> DrawableCurve<C, I> {
+        self.marker_every = Some(n.max(1));
+        self
+    }
+
+    /// segments of the curve with their color, split at the threshold crossing if it's set
+    fn colored_segments(&self, color: C) -> impl Iterator<Item = (Point, Point, C)> {
+        let threshold = self.threshold;
+        self.segments().flat_map(move |(start, end)| {
+            let (y, color_above) = match threshold {
+                Some(threshold) => threshold,
+                None => return Either::Left(once((start, end, color))),
+            };
+            match (start.y < y, end.y < y) {
+                (false, false) => Either::Left(once((start, end, color))),
+                (true, true) => Either::Left(once((start, end, color_above))),
+                (start_above, _) => {
+                    let crossing = Point::new(crossing_x(start, end, y), y);
+                    let (first, second) = if start_above {
+                        (color_above, color)
+                    } else {
+                        (color, color_above)
+                    };
+                    Either::Right(
+                        once((start, crossing, first)).chain(once((crossing, end, second))),
+                    )
+                }
+            }
+        })
+    }
+
+    /// points not connected to any other point, e.g. the only point of the curve or a point between gaps,
+    /// with their color
+    fn isolated_points(&self, color: C) -> impl Iterator<Item = (Point, C)> {
+        let threshold = self.threshold;
+        once(None)
+            .chain(self.points())
+            .chain(once(None))
+            .tuple_windows()
+            .filter_map(move |(prev, point, next)| match (prev, point, next) {
+                (None, Some(point), None) => Some(point),
+                _ => None,
+            })
+            .map(move |point| (point, point_color(point, color, threshold)))
+    }
+
+    /// every n-th point and the last one if markers are set, with their color
+    fn markers(&self, color: C) -> impl Iterator<Item = (Point, C)> {
+        let threshold = self.threshold;
+        let every = self.marker_every.unwrap_or(0);
+        let last = match every {
+            0 => 0,
+            _ => self.points().count().saturating_sub(1),
+        };
+        self.points()
+            .enumerate()
+            .filter(move |(index, _)| every > 0 && (index % every == 0 || *index == last))
+            .filter_map(|(_, point)| point)
+            .map(move |point| (point, point_color(point, color, threshold)))
+    }
+
+    /// isolated points, as there is no segment to show them, and markers drawn as dots
+    fn draw_dots<D: DrawTarget<Color = C>>(
+        &self,
+        display: &mut D,
+        color: C,
+        thickness: usize,
+    ) -> Result<(), D::Error> {
+        let diameter = (thickness as u32 * 2).max(3);
+        self.isolated_points(color)
+            .chain(self.markers(color))
+            .try_for_each(|(point, color)| -> Result<(), D::Error> {
+                Circle::with_center(point, diameter)
+                    .into_styled(PrimitiveStyle::with_fill(color))
+                    .draw(display)
+            })
+    }
+
+    /// fill the polygon made of all points with scanlines, using even-odd rule
+    fn draw_fill<D: DrawTarget<Color = C>>(
+        &self,
+        display: &mut D,
+        color: C,
+    ) -> Result<(), D::Error> {
+        let vertices = self.points().flatten();
+        let first = match vertices.clone().next() {
+            Some(first) => first,
+            None => return Ok(()),
+        };
+        let edges = vertices
+            .clone()
+            .zip(vertices.clone().skip(1).chain(once(first)));
+        let (top, bottom) = match vertices.map(|p| p.y).minmax() {
+            MinMax(top, bottom) => (top, bottom),
+            _ => return Ok(()),
+        };
+        // rows outside of the display can't be seen, distant points would take ages to walk through
+        let area = display.bounding_box();
+        let (top_left, bottom_right) = match area.bottom_right() {
+            Some(bottom_right) => (area.top_left, bottom_right),
+            None => return Ok(()),
+        };
+        'rows: for y in top.max(top_left.y)..bottom.min(bottom_right.y + 1) {
+            let mut crossings = [0; MAX_FILL_CROSSINGS];
+            let mut count = 0;
+            for (start, end) in edges.clone() {
+                if (start.y <= y) != (end.y <= y) {
+                    if count == MAX_FILL_CROSSINGS {
+                        // spans can't be paired without all the crossings
+                        continue 'rows;
+                    }
+                    crossings[count] = crossing_x(start, end, y);
+                    count += 1;
+                }
+            }
+            let crossings = &mut crossings[..count];
+            crossings.sort_unstable();
+            for span in crossings.chunks_exact(2) {
+                let (left, right) = (span[0].max(top_left.x), span[1].min(bottom_right.x));
+                if left <= right {
+                    display.fill_solid(
+                        &Rectangle::with_corners(Point::new(left, y), Point::new(right, y)),
+                        color,
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// scaled points moved by the translation of the curve
+    fn points(&self) -> impl Iterator<Item = Option<Point>> + Clone {
+        let offset = self.offset;
+        self.scaled_data
+            .clone()
+            .map(move |point| point.map(|point| point + offset))
+    }
+
+    /// pairs of consecutive points to be connected, including the closing one if the curve is closed
+    fn segments(&self) -> impl Iterator<Item = (Point, Point)> {
+        let closing = if self.closed {
+            let mut points = self.points();
+            match (points.next().flatten(), points.last().flatten()) {
+                (Some(first), Some(last)) if first != last => Some((last, first)),
+                _ => None,
+            }
+        } else {
+            None
+        };
+        self.open_segments().chain(closing)
+    }
+
+    /// pairs of consecutive points to be connected, segments touching a gap are skipped
+    fn open_segments(&self) -> impl Iterator<Item = (Point, Point)> {
+        let subdivisions = match self.smoothing {
+            None => {
+                return Either::Left(
+                    self.points()
+                        .tuple_windows()
+                        .filter_map(|(prev, point)| Some((prev?, point?))),
+                )
+            }
+            Some(subdivisions) => subdivisions,
+        };
+        // spline segment between p1 and p2 is shaped by their neighbours,
+        // missing neighbours at the ends of the curve and around gaps are replaced by the point itself
+        let data = once(None).chain(self.points()).chain(once(None));
+        Either::Right(data.tuple_windows().flat_map(move |(p0, p1, p2, p3)| {
+            let segment = match (p1, p2) {
+                (Some(p1), Some(p2)) => Some((p0.unwrap_or(p1), p1, p2, p3.unwrap_or(p2))),
+                _ => None,
+            };
+            segment.into_iter().flat_map(move |(p0, p1, p2, p3)| {
+                (0..subdivisions).map(move |step| {
+                    (
+                        catmull_rom(p0, p1, p2, p3, step, subdivisions),
+                        catmull_rom(p0, p1, p2, p3, step + 1, subdivisions),
+                    )
+                })
+            })
+        }))
+    }
+}
+
+impl<C, I> DrawableCurve<C, I>
+where
+    C: DefaultStyle,
+    I: Iterator<Item = Option<Point>> + Clone,
+{
+    /// decorate curve as n-th curve of the plot, with style suitable for the color type
+    pub fn with_default_style(self, index: usize) -> DrawableCurve<C, I> {
+        self.set_color(C::curve_color(index))
+            .set_thickness(C::curve_thickness())
+            .set_pattern(C::curve_pattern(index))
+    }
+}
+
+impl<C, I> Transform for DrawableCurve<C, I>
+where
+    C: PixelColor,
+    I: Iterator<Item = Option<Point>> + Clone,
+{
+    /// move the curve, threshold is moved along with it
+    fn translate(&self, by: Point) -> Self {
+        DrawableCurve {
+            scaled_data: self.scaled_data.clone(),
+            offset: self.offset + by,
+            threshold: self.threshold.map(|(y, color)| (y + by.y, color)),
+            ..*self
+        }
+    }
+
+    fn translate_mut(&mut self, by: Point) -> &mut Self {
+        self.offset += by;
+        if let Some((y, _)) = &mut self.threshold {
+            *y += by.y;
+        }
+        self
+    }
 }
 
 impl<C, I> Drawable for DrawableCurve<C, I>
 where
     C: PixelColor + Default,
-    I: Iterator<Item = Point> + Clone,
+    I: Iterator<Item = Option<Point>> + Clone,
 {
     type Color = C;
-    type Output = ();
+    /// bounding box of pixels touched by the curve, for flushing only changed region to the display
+    type Output = Rectangle;
 
     /// most important function - draw the curve on the display
     fn draw<D: DrawTarget<Color = C>>(
         &self,
         display: &mut D,
-    ) -> Result<(), <D as DrawTarget>::Error> {
+    ) -> Result<Rectangle, <D as DrawTarget>::Error> {
+        match self.clip_area {
+            Some(area) => self.draw_on(&mut display.clipped(&area.translate(self.offset))),
+            None => self.draw_on(display),
+        }
+    }
+}
+
+impl<C, I> DrawableCurve<C, I>
+where
+    C: PixelColor + Default,
+    I: Iterator<Item = Option<Point>> + Clone,
+{
+    fn draw_on<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<Rectangle, D::Error> {
         let color = match &self.color {
             None => C::default(),
             Some(c) => *c,
@@ -143,11 +873,462 @@ where
             None => 2,
             Some(t) => *t,
         };
-        let style = PrimitiveStyle::with_stroke(color, thickness as u32);
-        self.scaled_data.clone().tuple_windows().try_for_each(
-            |(prev, point)| -> Result<(), D::Error> {
-                Line::new(prev, point).into_styled(style).draw(display)
-            },
-        )
+        let mut display = DirtyTracker::new(display);
+        if let Some(fill_color) = self.fill_color {
+            self.draw_fill(&mut display, fill_color)?;
+        }
+        match self.mode.unwrap_or_default() {
+            RenderMode::Lines => self.draw_lines(&mut display, color, thickness),
+            RenderMode::FastLines => self.draw_spans(&mut display, color, thickness),
+            // single point already makes a column of its own
+            RenderMode::ColumnMinMax => self.draw_column_min_max(&mut display, color),
+        }?;
+        if self.mode != Some(RenderMode::ColumnMinMax) {
+            self.draw_dots(&mut display, color, thickness)?;
+        }
+        Ok(display.dirty())
+    }
+}
+
+impl<C, I> DrawableCurve<C, I>
+where
+    C: PixelColor,
+    I: Iterator<Item = Option<Point>> + Clone,
+{
+    fn draw_lines<D: DrawTarget<Color = C>>(
+        &self,
+        display: &mut D,
+        color: C,
+        thickness: usize,
+    ) -> Result<(), D::Error> {
+        let pattern = self.pattern.unwrap_or_default();
+        if pattern != LinePattern::SOLID {
+            let mut phase = 0;
+            return self.colored_segments(color).try_for_each(
+                |(prev, point, color)| -> Result<(), D::Error> {
+                    draw_patterned_line(
+                        display,
+                        Line::new(prev, point),
+                        pattern,
+                        color,
+                        thickness,
+                        &mut phase,
+                    )
+                },
+            );
+        }
+        // same pixels as styled primitives, but with one fill per run of pixels where it can
+        self.colored_segments(color)
+            .try_for_each(|(prev, point, color)| -> Result<(), D::Error> {
+                draw_line_runs(display, Line::new(prev, point), color, thickness)
+            })
+    }
+
+    /// draw the curve as polylines instead of independent lines, which gives clean joins between
+    /// segments of thick lines. Scaled points are collected into provided buffer, curve is drawn in chunks
+    /// when it doesn't fit in. Line pattern and render mode are ignored. Returns bounding box of touched pixels.
+    pub fn draw_polyline<D>(
+        &self,
+        display: &mut D,
+        buffer: &mut [Point],
+    ) -> Result<Rectangle, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        C: Default,
+    {
+        match self.clip_area {
+            Some(area) => {
+                let area = area.translate(self.offset);
+                self.draw_polyline_on(&mut display.clipped(&area), buffer)
+            }
+            None => self.draw_polyline_on(display, buffer),
+        }
+    }
+
+    fn draw_polyline_on<D>(
+        &self,
+        display: &mut D,
+        buffer: &mut [Point],
+    ) -> Result<Rectangle, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        C: Default,
+    {
+        let mut display = DirtyTracker::new(display);
+        let color = self.color.unwrap_or_default();
+        let thickness = self.thickness.unwrap_or(2) as u32;
+        if let Some(fill_color) = self.fill_color {
+            self.draw_fill(&mut display, fill_color)?;
+        }
+        if buffer.len() < 2 {
+            self.colored_segments(color)
+                .try_for_each(|(start, end, color)| {
+                    Line::new(start, end)
+                        .into_styled(PrimitiveStyle::with_stroke(color, thickness))
+                        .draw(&mut display)
+                })?;
+            self.draw_dots(&mut display, color, thickness as usize)?;
+            return Ok(display.dirty());
+        }
+        // polyline being collected: its color and number of points in the buffer
+        let mut pending: Option<(C, usize)> = None;
+        for (start, end, segment_color) in self.colored_segments(color) {
+            let len = match pending {
+                Some((pending_color, len))
+                    if pending_color == segment_color && buffer[len - 1] == start =>
+                {
+                    len
+                }
+                _ => {
+                    if let Some((pending_color, len)) = pending.filter(|(_, len)| *len > 1) {
+                        draw_polyline_chunk(
+                            &mut display,
+                            &buffer[..len],
+                            pending_color,
+                            thickness,
+                        )?;
+                    }
+                    buffer[0] = start;
+                    1
+                }
+            };
+            buffer[len] = end;
+            pending = if len + 1 == buffer.len() {
+                // buffer is full, next chunk continues from the last point
+                draw_polyline_chunk(&mut display, buffer, segment_color, thickness)?;
+                buffer[0] = end;
+                Some((segment_color, 1))
+            } else {
+                Some((segment_color, len + 1))
+            };
+        }
+        if let Some((pending_color, len)) = pending.filter(|(_, len)| *len > 1) {
+            draw_polyline_chunk(&mut display, &buffer[..len], pending_color, thickness)?;
+        }
+        self.draw_dots(&mut display, color, thickness as usize)?;
+        Ok(display.dirty())
+    }
+
+    fn draw_spans<D: DrawTarget<Color = C>>(
+        &self,
+        display: &mut D,
+        color: C,
+        thickness: usize,
+    ) -> Result<(), D::Error> {
+        self.colored_segments(color)
+            .try_for_each(|(prev, point, color)| -> Result<(), D::Error> {
+                fill_line_runs(display, Line::new(prev, point), color, thickness.max(1))
+            })
+    }
+
+    fn draw_column_min_max<D: DrawTarget<Color = C>>(
+        &self,
+        display: &mut D,
+        color: C,
+    ) -> Result<(), D::Error> {
+        let threshold = self.threshold;
+        let draw_column = |display: &mut D, x: i32, min: i32, max: i32| {
+            let fill = |display: &mut D, min: i32, max: i32, color: C| {
+                fill_column(display, x, min, max, color)
+            };
+            match threshold {
+                Some((y, _)) if y <= min => fill(display, min, max, color),
+                Some((y, color_above)) if y > max => fill(display, min, max, color_above),
+                Some((y, color_above)) => {
+                    fill(display, min, y - 1, color_above)?;
+                    fill(display, y, max, color)
+                }
+                None => fill(display, min, max, color),
+            }
+        };
+        // column being collected: x, min, max and y of the last point that landed in it
+        let mut column: Option<(i32, i32, i32, i32)> = None;
+        for point in self.points() {
+            column = match (point, column) {
+                (None, Some((x, min, max, _))) => {
+                    draw_column(display, x, min, max)?;
+                    None
+                }
+                (None, None) => None,
+                (Some(p), None) => Some((p.x, p.y, p.y, p.y)),
+                (Some(p), Some((x, min, max, _))) if p.x == x => {
+                    Some((x, min.min(p.y), max.max(p.y), p.y))
+                }
+                (Some(p), Some((x, min, max, last))) => {
+                    draw_column(display, x, min, max)?;
+                    // start new column from the exit point of the previous one, so columns stay connected
+                    Some((p.x, p.y.min(last), p.y.max(last), p.y))
+                }
+            };
+        }
+        if let Some((x, min, max, _)) = column {
+            draw_column(display, x, min, max)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::curve::{Curve, DrawableCurve, PlotPoint, XYData};
+    use crate::transform::OutOfRange;
+    use core::iter::once;
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::{BinaryColor, Rgb565},
+        prelude::*,
+    };
+    use test_case::test_case;
+
+    const SORTED: [PlotPoint; 5] = [
+        PlotPoint { x: 0, y: 1 },
+        PlotPoint { x: 10, y: 2 },
+        PlotPoint {
+            x: 15,
+            y: PlotPoint::GAP,
+        },
+        PlotPoint { x: 20, y: 3 },
+        PlotPoint { x: 40, y: 4 },
+    ];
+
+    const UNSORTED: [PlotPoint; 4] = [
+        PlotPoint { x: 20, y: 3 },
+        PlotPoint { x: 0, y: 1 },
+        PlotPoint { x: 40, y: 4 },
+        PlotPoint { x: 10, y: 2 },
+    ];
+
+    #[test_case(-5 => Some(1); "before start")]
+    #[test_case(14 => Some(2); "closer to previous")]
+    #[test_case(16 => Some(3); "gap skipped")]
+    #[test_case(30 => Some(3); "tie picks earlier")]
+    #[test_case(100 => Some(4); "after end")]
+    fn nearest(x: i32) -> Option<i32> {
+        let sorted = Curve::from_data(&SORTED).nearest_point(x).map(|p| p.y);
+        let unsorted = Curve::from_data(&UNSORTED).nearest_point(x).map(|p| p.y);
+        assert_eq!(sorted, unsorted);
+        sorted
+    }
+
+    #[test]
+    fn points_slice() {
+        let points = [PlotPoint { x: 0, y: 1 }, PlotPoint { x: 5, y: 2 }];
+        let curve = Curve::from_data(&points);
+        assert_eq!(curve.points().map(|points| points.len()), Some(2));
+        assert_eq!(curve.iter().count(), 2);
+    }
+
+    #[test]
+    fn samples() {
+        let curve = Curve::from_samples(&[7, 3, 9], 100, -10);
+        assert_eq!(curve.x_range, 80..100);
+        assert_eq!(curve.y_range, 3..9);
+        assert_eq!(curve.iter().nth(2).map(|p| (p.x, p.y)), Some((80, 9)));
+        assert!(curve.points().is_none());
+        assert_eq!(curve.nearest_point(91).map(|p| p.y), Some(3));
+        let signed = Curve::from_i16_samples(&[-4, 2], 0, 5);
+        assert_eq!(signed.y_range, -4..2);
+        assert_eq!(signed.nearest_point(4).map(|p| p.y), Some(2));
+    }
+
+    #[test]
+    fn implicit_x() {
+        let y = [5, PlotPoint::GAP, 1];
+        let curve = Curve::from_xy_data(XYData::Y {
+            y: &y,
+            x0: 10,
+            dx: 2,
+        });
+        assert_eq!(curve.x_range, 10..14);
+        assert_eq!(curve.y_range, 1..5);
+        assert_eq!(curve.nearest_point(12).map(|p| (p.x, p.y)), Some((10, 5)));
+    }
+
+    #[test]
+    fn single_point_drawn_centered() {
+        let point = [PlotPoint { x: 5, y: 7 }];
+        let curve = Curve::from_data(&point);
+        assert_eq!((curve.x_range.clone(), curve.y_range.clone()), (4..6, 6..8));
+        let (top_left, bottom_right) = (Point::new(0, 0), Point::new(20, 20));
+        let mut display = MockDisplay::new();
+        curve
+            .into_drawable_curve(&top_left, &bottom_right)
+            .unwrap()
+            .set_color(BinaryColor::On)
+            .draw(&mut display)
+            .unwrap();
+        assert_eq!(display.get_pixel(Point::new(10, 10)), Some(BinaryColor::On));
+    }
+
+    #[test]
+    fn flat_line_padded() {
+        let points = [PlotPoint { x: 0, y: 3 }, PlotPoint { x: 10, y: 3 }];
+        let curve = Curve::from_data(&points);
+        assert_eq!(
+            (curve.x_range.clone(), curve.y_range.clone()),
+            (0..10, 2..4)
+        );
+        let transform = curve
+            .transform(Point::new(0, 0), Point::new(20, 20))
+            .unwrap();
+        assert_eq!(transform.to_screen(&points[1]), Point::new(20, 10));
+    }
+
+    #[test_case(OutOfRange::Clip => (false, true); "clip")]
+    #[test_case(OutOfRange::Clamp => (false, true); "clamp")]
+    #[test_case(OutOfRange::Drop => (false, false); "drop")]
+    fn out_of_range(policy: OutOfRange) -> (bool, bool) {
+        let points = [PlotPoint { x: 5, y: 5 }, PlotPoint { x: 20, y: 5 }];
+        let (top_left, bottom_right) = (Point::new(0, 0), Point::new(20, 20));
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        Curve::new(&points, 0..10, 0..10)
+            .set_out_of_range(policy)
+            .into_drawable_curve(&top_left, &bottom_right)
+            .unwrap()
+            .set_color(BinaryColor::On)
+            .set_thickness(1)
+            .draw(&mut display)
+            .unwrap();
+        let drawn = |x: i32| (0..64).any(|y| display.get_pixel(Point::new(x, y)).is_some());
+        (drawn(21), drawn(20))
+    }
+
+    #[test]
+    fn rows_with_too_many_crossings_unfilled() {
+        // comb of 17 teeth standing on a base, rows across the teeth cross 34 edges
+        let teeth = (0..17).flat_map(|i| [Point::new(3 * i + 1, 2), Point::new(3 * i + 2, 30)]);
+        let comb = once(Point::new(0, 40))
+            .chain(teeth)
+            .chain(once(Point::new(52, 40)));
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        DrawableCurve::new(comb.map(Some))
+            .set_color(Rgb565::WHITE)
+            .set_thickness(1)
+            .set_fill_color(Rgb565::BLUE)
+            .draw(&mut display)
+            .unwrap();
+        let filled =
+            |y: i32| (0..64).any(|x| display.get_pixel(Point::new(x, y)) == Some(Rgb565::BLUE));
+        assert!(!filled(20));
+        assert!(filled(35));
+    }
+
+    #[test]
+    fn nearest_in_empty() {
+        assert!(Curve::from_data(&[]).nearest_point(0).is_none());
+    }
+
+    #[test]
+    fn closed_and_filled() {
+        let triangle = [Point::new(0, 0), Point::new(20, 0), Point::new(0, 20)];
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        DrawableCurve::new(triangle.iter().map(|p| Some(*p)))
+            .set_color(Rgb565::WHITE)
+            .set_thickness(1)
+            .set_closed()
+            .set_fill_color(Rgb565::BLUE)
+            .draw(&mut display)
+            .unwrap();
+        assert_eq!(display.get_pixel(Point::new(0, 10)), Some(Rgb565::WHITE));
+        assert_eq!(display.get_pixel(Point::new(5, 5)), Some(Rgb565::BLUE));
+        assert_eq!(display.get_pixel(Point::new(15, 15)), None);
+    }
+
+    #[test_case(5 => true; "first")]
+    #[test_case(25 => true; "every fourth")]
+    #[test_case(15 => false; "between markers")]
+    #[test_case(55 => true; "last")]
+    fn marker_every(x: i32) -> bool {
+        let line = (1..=11).map(|n| Some(Point::new(n * 5, 10)));
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        DrawableCurve::new(line)
+            .set_color(BinaryColor::On)
+            .set_thickness(1)
+            .set_marker_every(4)
+            .draw(&mut display)
+            .unwrap();
+        display.get_pixel(Point::new(x, 9)).is_some()
+    }
+
+    #[test]
+    fn empty_curve_with_markers() {
+        let mut display = MockDisplay::<BinaryColor>::new();
+        DrawableCurve::new(core::iter::empty())
+            .set_color(BinaryColor::On)
+            .set_marker_every(4)
+            .draw(&mut display)
+            .unwrap();
+        assert_eq!(display, MockDisplay::new());
+    }
+
+    #[test]
+    fn polyline_in_chunks() {
+        // zigzag with a gap in the middle, longer than the chunks below
+        let mut points = [PlotPoint { x: 0, y: 0 }; 24];
+        for (i, point) in points.iter_mut().enumerate() {
+            point.x = 2 * i as i32;
+            point.y = if i % 2 == 0 { 0 } else { 10 };
+        }
+        points[12].y = PlotPoint::GAP;
+        let curve = Curve::from_data(&points);
+        let transform = curve
+            .transform(Point::new(0, 0), Point::new(46, 40))
+            .unwrap();
+        let draw = |buffer: &mut [Point]| {
+            let mut display = MockDisplay::<BinaryColor>::new();
+            display.set_allow_overdraw(true);
+            transform
+                .drawable_curve(curve.iter())
+                .set_color(BinaryColor::On)
+                .set_thickness(1)
+                .draw_polyline(&mut display, buffer)
+                .unwrap();
+            display
+        };
+        let expected = draw(&mut [Point::zero(); 32]);
+        draw(&mut [Point::zero(); 5]).assert_eq(&expected);
+        draw(&mut [Point::zero(); 2]).assert_eq(&expected);
+        draw(&mut []).assert_eq(&expected);
+    }
+
+    #[test]
+    fn distant_fill_clipped_to_display() {
+        // edges far beyond the display used to overflow and walk every row between the vertices
+        let triangle = [
+            Point::new(-2_000_000_000, 60),
+            Point::new(10, -2_000_000_000),
+            Point::new(2_000_000_000, 60),
+        ];
+        let mut display = MockDisplay::new();
+        DrawableCurve::new(triangle.iter().map(|p| Some(*p)))
+            .set_color(Rgb565::WHITE)
+            .set_thickness(0)
+            .set_closed()
+            .set_fill_color(Rgb565::BLUE)
+            .draw(&mut display)
+            .unwrap();
+        assert_eq!(display.get_pixel(Point::new(0, 30)), Some(Rgb565::BLUE));
+        assert_eq!(display.get_pixel(Point::new(63, 59)), Some(Rgb565::BLUE));
+        assert_eq!(display.get_pixel(Point::new(63, 60)), None);
+    }
+
+    #[test]
+    fn distant_threshold_crossing() {
+        let line = [Point::new(-2_000_000_000, 60), Point::new(2_000_000_000, 0)];
+        let curve =
+            DrawableCurve::new(line.iter().map(|p| Some(*p))).set_threshold(30, BinaryColor::On);
+        let mut segments = curve.colored_segments(BinaryColor::Off);
+        assert_eq!(
+            segments.next(),
+            Some((line[0], Point::new(0, 30), BinaryColor::Off))
+        );
+        assert_eq!(
+            segments.next(),
+            Some((Point::new(0, 30), line[1], BinaryColor::On))
+        );
     }
 }
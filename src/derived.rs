@@ -0,0 +1,287 @@
+//! Curves derived from other curves on the fly
+//!
+//! Adapters in this module iterate over points of existing curve and compute new points during iteration,
+//! so derived traces can be drawn as overlays without any extra buffer.
+//! To draw them, map the points with [PlotTransform](crate::transform::PlotTransform) of the source curve
+//! ([Difference], [CumulativeSum] and [Pointwise] change the range of values, so they need transform with their own Y range):
+//! ```rust
+//! # use embedded_plots::curve::{Curve, PlotPoint};
+//! # use embedded_graphics::{geometry::Point, pixelcolor::BinaryColor, mock_display::MockDisplay, Drawable};
+//! # let mut display: MockDisplay<BinaryColor> = MockDisplay::new();
+//! # display.set_allow_overdraw(true);
+//! # let data = [PlotPoint { x: 0, y: 0 }, PlotPoint { x: 1, y: 3 }, PlotPoint { x: 2, y: 1 }];
+//! let curve = Curve::from_data(&data);
+//! let transform = curve.transform(Point::new(0, 0), Point::new(60, 60)).unwrap();
+//! transform
+//!     .drawable_curve(curve.moving_average(2))
+//!     .set_color(BinaryColor::On)
+//!     .set_thickness(1)
+//!     .draw(&mut display)
+//!     .unwrap();
+//! ```
+use crate::curve::{PlotPoint, XYData};
+
+/// Running mean of the last `window` points of the source data, gaps are not taken into account
+#[derive(Clone)]
+pub struct MovingAverage<'a> {
+    points: XYData<'a>,
+    window: usize,
+    index: usize,
+    sum: i64,
+    count: i64,
+}
+
+impl<'a> MovingAverage<'a> {
+    pub fn new(points: &'a [PlotPoint], window: usize) -> MovingAverage<'a> {
+        MovingAverage::with_data(XYData::Points(points), window)
+    }
+
+    pub(crate) fn with_data(points: XYData<'a>, window: usize) -> MovingAverage<'a> {
+        MovingAverage {
+            points,
+            window: window.max(1),
+            index: 0,
+            sum: 0,
+            count: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for MovingAverage<'a> {
+    type Item = PlotPoint;
+
+    fn next(&mut self) -> Option<PlotPoint> {
+        let point = self.points.get(self.index)?;
+        if !point.is_gap() {
+            self.sum += point.y as i64;
+            self.count += 1;
+        }
+        if let Some(leaving) = self.index.checked_sub(self.window) {
+            let leaving = self.points.get(leaving)?;
+            if !leaving.is_gap() {
+                self.sum -= leaving.y as i64;
+                self.count -= 1;
+            }
+        }
+        self.index += 1;
+
+        if point.is_gap() || self.count == 0 {
+            return Some(PlotPoint::gap(point.x));
+        }
+        Some(PlotPoint {
+            x: point.x,
+            y: (self.sum / self.count) as i32,
+        })
+    }
+}
+
+/// First difference of the source data - change of Y since the previous point, placed at X of the current point.
+/// For evenly sampled data it's proportional to the rate of change, e.g. flow from volume.
+/// First point and points next to gaps have no previous value and become gaps.
+#[derive(Clone)]
+pub struct Difference<'a> {
+    points: XYData<'a>,
+    index: usize,
+}
+
+impl<'a> Difference<'a> {
+    pub fn new(points: &'a [PlotPoint]) -> Difference<'a> {
+        Difference::with_data(XYData::Points(points))
+    }
+
+    pub(crate) fn with_data(points: XYData<'a>) -> Difference<'a> {
+        Difference { points, index: 0 }
+    }
+}
+
+impl<'a> Iterator for Difference<'a> {
+    type Item = PlotPoint;
+
+    fn next(&mut self) -> Option<PlotPoint> {
+        let point = self.points.get(self.index)?;
+        let previous = self
+            .index
+            .checked_sub(1)
+            .and_then(|index| self.points.get(index));
+        self.index += 1;
+        match previous {
+            Some(previous) if !previous.is_gap() && !point.is_gap() => Some(PlotPoint {
+                x: point.x,
+                y: saturate(point.y as i64 - previous.y as i64),
+            }),
+            _ => Some(PlotPoint::gap(point.x)),
+        }
+    }
+}
+
+/// Running sum of the source data, e.g. volume from flow. Gaps are passed through and don't change the sum.
+#[derive(Clone)]
+pub struct CumulativeSum<'a> {
+    points: XYData<'a>,
+    index: usize,
+    sum: i64,
+}
+
+impl<'a> CumulativeSum<'a> {
+    pub fn new(points: &'a [PlotPoint]) -> CumulativeSum<'a> {
+        CumulativeSum::with_data(XYData::Points(points))
+    }
+
+    pub(crate) fn with_data(points: XYData<'a>) -> CumulativeSum<'a> {
+        CumulativeSum {
+            points,
+            index: 0,
+            sum: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for CumulativeSum<'a> {
+    type Item = PlotPoint;
+
+    fn next(&mut self) -> Option<PlotPoint> {
+        let point = self.points.get(self.index)?;
+        self.index += 1;
+        if point.is_gap() {
+            return Some(point);
+        }
+        self.sum = self.sum.saturating_add(point.y as i64);
+        Some(PlotPoint {
+            x: point.x,
+            y: saturate(self.sum),
+        })
+    }
+}
+
+/// Operation combining Y values of two curves, see [Curve::combine](crate::curve::Curve::combine)
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CurveOperation {
+    /// first minus second, e.g. error between measured value and setpoint
+    Difference,
+    /// first plus second
+    Sum,
+    /// first divided by second, multiplied by given scale to keep precision, e.g. 100 for percents.
+    /// Division by zero becomes a gap.
+    Ratio(i32),
+}
+
+/// Pointwise combination of two curves sharing X values, created by [Curve::combine](crate::curve::Curve::combine).
+/// Points are paired by their index, points with different X or a gap in either curve become gaps.
+/// Iteration ends with the shorter curve.
+#[derive(Clone)]
+pub struct Pointwise<'a> {
+    first: XYData<'a>,
+    second: XYData<'a>,
+    operation: CurveOperation,
+    index: usize,
+}
+
+impl<'a> Pointwise<'a> {
+    pub(crate) fn with_data(
+        first: XYData<'a>,
+        second: XYData<'a>,
+        operation: CurveOperation,
+    ) -> Pointwise<'a> {
+        Pointwise {
+            first,
+            second,
+            operation,
+            index: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for Pointwise<'a> {
+    type Item = PlotPoint;
+
+    fn next(&mut self) -> Option<PlotPoint> {
+        let first = self.first.get(self.index)?;
+        let second = self.second.get(self.index)?;
+        self.index += 1;
+        if first.is_gap() || second.is_gap() || first.x != second.x {
+            return Some(PlotPoint::gap(first.x));
+        }
+        let (a, b) = (first.y as i64, second.y as i64);
+        let y = match self.operation {
+            CurveOperation::Difference => a - b,
+            CurveOperation::Sum => a + b,
+            CurveOperation::Ratio(_) if b == 0 => return Some(PlotPoint::gap(first.x)),
+            CurveOperation::Ratio(scale) => a * scale as i64 / b,
+        };
+        Some(PlotPoint {
+            x: first.x,
+            y: saturate(y),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.first.len().min(self.second.len()) - self.index;
+        (left, Some(left))
+    }
+}
+
+/// clamp derived value to `i32`, keeping clear of the gap marker
+fn saturate(value: i64) -> i32 {
+    value.clamp(PlotPoint::GAP as i64 + 1, i32::MAX as i64) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::curve::{Curve, PlotPoint};
+    use crate::derived::{CumulativeSum, CurveOperation, Difference};
+    use heapless::Vec;
+
+    const DATA: [PlotPoint; 5] = [
+        PlotPoint { x: 0, y: 1 },
+        PlotPoint { x: 1, y: 4 },
+        PlotPoint { x: 2, y: 2 },
+        PlotPoint {
+            x: 3,
+            y: PlotPoint::GAP,
+        },
+        PlotPoint { x: 4, y: 5 },
+    ];
+
+    #[test]
+    fn difference() {
+        let y: Vec<i32, 8> = Difference::new(&DATA).map(|p| p.y).collect();
+        assert_eq!(
+            &y[..],
+            &[PlotPoint::GAP, 3, -2, PlotPoint::GAP, PlotPoint::GAP]
+        );
+    }
+
+    #[test]
+    fn cumulative_sum() {
+        let y: Vec<i32, 8> = CumulativeSum::new(&DATA).map(|p| p.y).collect();
+        assert_eq!(&y[..], &[1, 5, 7, PlotPoint::GAP, 12]);
+    }
+
+    #[test]
+    fn pointwise() {
+        let setpoint = [
+            PlotPoint { x: 0, y: 2 },
+            PlotPoint { x: 1, y: 2 },
+            PlotPoint { x: 2, y: 0 },
+            PlotPoint { x: 3, y: 2 },
+            PlotPoint { x: 5, y: 2 },
+            PlotPoint { x: 6, y: 2 },
+        ];
+        let (data, setpoint) = (Curve::from_data(&DATA), Curve::from_data(&setpoint));
+        let y: Vec<i32, 8> = data
+            .combine(&setpoint, CurveOperation::Difference)
+            .map(|p| p.y)
+            .collect();
+        assert_eq!(&y[..], &[-1, 2, 2, PlotPoint::GAP, PlotPoint::GAP]);
+        let y: Vec<i32, 8> = data
+            .combine(&setpoint, CurveOperation::Ratio(100))
+            .map(|p| p.y)
+            .collect();
+        assert_eq!(
+            &y[..],
+            &[50, 200, PlotPoint::GAP, PlotPoint::GAP, PlotPoint::GAP]
+        );
+    }
+}
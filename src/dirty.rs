@@ -0,0 +1,184 @@
+//! Tracking of the display region touched while drawing, for displays with partial updates
+use core::{convert::Infallible, marker::PhantomData};
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, Point, Size},
+    pixelcolor::PixelColor,
+    primitives::Rectangle,
+    Drawable, Pixel,
+};
+
+/// draw target wrapper remembering bounding box of all pixels written to the display
+pub(crate) struct DirtyTracker<'a, D> {
+    display: &'a mut D,
+    dirty: Option<(Point, Point)>,
+}
+
+impl<'a, D> DirtyTracker<'a, D>
+where
+    D: DrawTarget,
+{
+    pub(crate) fn new(display: &'a mut D) -> DirtyTracker<'a, D> {
+        DirtyTracker {
+            display,
+            dirty: None,
+        }
+    }
+
+    /// bounding box of touched pixels, empty rectangle if nothing was drawn
+    pub(crate) fn dirty(&self) -> Rectangle {
+        match self.dirty {
+            Some((top_left, bottom_right)) => Rectangle::with_corners(top_left, bottom_right),
+            None => Rectangle::zero(),
+        }
+    }
+
+    fn mark_area(&mut self, area: &Rectangle) {
+        let area = area.intersection(&self.display.bounding_box());
+        if let Some(bottom_right) = area.bottom_right() {
+            mark(&mut self.dirty, area.top_left);
+            mark(&mut self.dirty, bottom_right);
+        }
+    }
+}
+
+fn mark(dirty: &mut Option<(Point, Point)>, point: Point) {
+    *dirty = Some(match *dirty {
+        Some((top_left, bottom_right)) => (
+            top_left.component_min(point),
+            bottom_right.component_max(point),
+        ),
+        None => (point, point),
+    });
+}
+
+/// display discarding everything drawn on it, used to measure drawables
+struct NullDisplay<C>(PhantomData<C>);
+
+impl<C> Dimensions for NullDisplay<C> {
+    fn bounding_box(&self) -> Rectangle {
+        // large enough for any plot, small enough to never overflow
+        Rectangle::with_center(Point::zero(), Size::new_equal(1 << 20))
+    }
+}
+
+impl<C: PixelColor> DrawTarget for NullDisplay<C> {
+    type Color = C;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        pixels.into_iter().for_each(drop);
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, _area: &Rectangle, _color: Self::Color) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// bounding box of all pixels the drawable would touch, without drawing it anywhere
+pub(crate) fn drawn_area<T>(drawable: &T) -> Rectangle
+where
+    T: Drawable,
+{
+    let mut display = NullDisplay(PhantomData);
+    let mut tracker = DirtyTracker::new(&mut display);
+    drawable.draw(&mut tracker).ok();
+    tracker.dirty()
+}
+
+impl<'a, D> Dimensions for DirtyTracker<'a, D>
+where
+    D: DrawTarget,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.display.bounding_box()
+    }
+}
+
+impl<'a, D> DrawTarget for DirtyTracker<'a, D>
+where
+    D: DrawTarget,
+{
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounds = self.display.bounding_box();
+        let top_left = bounds.top_left;
+        let bottom_right = bounds.bottom_right().unwrap_or(top_left);
+        let dirty = &mut self.dirty;
+        self.display
+            .draw_iter(pixels.into_iter().inspect(|Pixel(point, _)| {
+                // pixels outside of the display are discarded by the display, so they are not dirty
+                if point.component_max(top_left) == *point
+                    && point.component_min(bottom_right) == *point
+                {
+                    mark(dirty, *point);
+                }
+            }))
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.mark_area(area);
+        self.display.fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.mark_area(area);
+        self.display.fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.mark_area(&self.display.bounding_box());
+        self.display.clear(color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dirty::DirtyTracker;
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::BinaryColor,
+        prelude::*,
+        primitives::{Line, PrimitiveStyle, Rectangle},
+    };
+
+    #[test]
+    fn nothing_drawn() {
+        let mut display = MockDisplay::<BinaryColor>::new();
+        let tracker = DirtyTracker::new(&mut display);
+        assert_eq!(tracker.dirty(), Rectangle::zero());
+    }
+
+    #[test]
+    fn union_of_drawn_primitives() {
+        let mut display = MockDisplay::<BinaryColor>::new();
+        let mut tracker = DirtyTracker::new(&mut display);
+        Line::new(Point::new(2, 3), Point::new(5, 3))
+            .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+            .draw(&mut tracker)
+            .unwrap();
+        tracker
+            .fill_solid(
+                &Rectangle::new(Point::new(10, 10), Size::new(2, 2)),
+                BinaryColor::On,
+            )
+            .unwrap();
+        assert_eq!(
+            tracker.dirty(),
+            Rectangle::with_corners(Point::new(2, 3), Point::new(11, 11))
+        );
+    }
+}
@@ -0,0 +1,230 @@
+//! Downsampling of large datasets before drawing
+//!
+//! Plotting thousands of logged samples on a display that is just over a hundred pixels wide is slow
+//! and most of the points end up on the same column anyway.
+//! [lttb] reduces the data to the requested number of points while keeping the visual shape of the curve.
+//! Excursions between the kept points can be shown with [min_max](crate::envelope::min_max) envelope.
+use crate::curve::PlotPoint;
+
+/// Reduce `input` into `output` buffer using Largest-Triangle-Three-Buckets algorithm.
+///
+/// Points are expected to be sorted by X. First and last point are always kept,
+/// remaining ones are split into `output.len() - 2` buckets and from each bucket the point forming
+/// the largest triangle with previously selected point and average of the next bucket is chosen.
+///
+/// Data is split at gaps and every run of gaps is kept as single gap, so missing samples stay visible.
+/// Output is shared between the runs of data by their length, each of them keeping at least one point.
+/// When there are too many runs to fit into `output`, every bucket containing a gap produces a gap.
+///
+/// Returns number of points written to `output`. If `input` fits into `output`, it is copied as is.
+pub fn lttb(input: &[PlotPoint], output: &mut [PlotPoint]) -> usize {
+    let n = input.len();
+    let m = output.len();
+    if n <= m {
+        output[..n].copy_from_slice(input);
+        return n;
+    }
+    let (mut gap_runs, mut data_runs, mut data_points) = (0, 0, 0);
+    for run in runs(input) {
+        if run[0].is_gap() {
+            gap_runs += 1;
+        } else {
+            data_runs += 1;
+            data_points += run.len();
+        }
+    }
+    if data_runs == 0 || gap_runs + data_runs > m {
+        return buckets(input, output);
+    }
+
+    // every run of data gets one point, spare room is shared by the length of the runs
+    let spare = (m - gap_runs - data_runs) as u64;
+    let total = (data_points - data_runs) as u64;
+    let mut shared = 0;
+    let mut start = 0;
+    let mut written = 0;
+    for run in runs(input) {
+        let end = start + run.len();
+        if run[0].is_gap() {
+            output[written] = PlotPoint::gap(run[0].x);
+            written += 1;
+        } else {
+            let before = shared * spare / total.max(1);
+            shared += run.len() as u64 - 1;
+            let extra = (shared * spare / total.max(1) - before) as usize;
+            let len = (1 + extra).min(run.len());
+            written += if len > 1 {
+                buckets(run, &mut output[written..written + len])
+            } else {
+                // single point keeps the end of the data, or the middle of the run in between gaps
+                output[written] = match (start, end) {
+                    (0, _) => run[0],
+                    (_, end) if end == n => run[run.len() - 1],
+                    _ => run[run.len() / 2],
+                };
+                1
+            };
+        }
+        start = end;
+    }
+    written
+}
+
+/// split points into runs of data and runs of gaps
+fn runs(input: &[PlotPoint]) -> impl Iterator<Item = &[PlotPoint]> {
+    let mut rest = input;
+    core::iter::from_fn(move || {
+        let gap = rest.first()?.is_gap();
+        let (run, tail) = rest.split_at(rest.iter().take_while(|p| p.is_gap() == gap).count());
+        rest = tail;
+        Some(run)
+    })
+}
+
+/// LTTB over the whole `input`, bucket containing a gap produces a gap
+fn buckets(input: &[PlotPoint], output: &mut [PlotPoint]) -> usize {
+    let n = input.len();
+    let m = output.len();
+    if n <= m {
+        output[..n].copy_from_slice(input);
+        return n;
+    }
+    if m < 3 {
+        // no room for buckets, keep just the ends of the data
+        let ends = [input[0], input[n - 1]];
+        output.copy_from_slice(&ends[..m]);
+        return m;
+    }
+
+    let buckets = m - 2;
+    let bucket_bounds = |i: usize| (1 + i * (n - 2) / buckets)..(1 + (i + 1) * (n - 2) / buckets);
+
+    output[0] = input[0];
+    let mut selected = input[0];
+    for i in 0..buckets {
+        let next = if i + 1 < buckets {
+            &input[bucket_bounds(i + 1)]
+        } else {
+            &input[n - 1..]
+        };
+        let (sum_x, sum_y, count) = next
+            .iter()
+            .filter(|p| !p.is_gap())
+            .fold((0i64, 0i64, 0i64), |(x, y, c), p| {
+                (x + p.x as i64, y + p.y as i64, c + 1)
+            });
+        let bucket = &input[bucket_bounds(i)];
+        let candidate = if bucket.iter().any(|p| p.is_gap()) {
+            None
+        } else if count == 0 {
+            // nothing to aim at, fall back to the middle of the bucket
+            bucket.get(bucket.len() / 2)
+        } else {
+            // doubled area of the triangle, i32 coordinates overflow i64 products
+            let (avg_x, avg_y) = ((sum_x / count) as i128, (sum_y / count) as i128);
+            let (ax, ay) = (selected.x as i128, selected.y as i128);
+            bucket.iter().max_by_key(|p| {
+                let (px, py) = (p.x as i128, p.y as i128);
+                ((ax - avg_x) * (py - ay) - (ax - px) * (avg_y - ay)).abs()
+            })
+        };
+        output[i + 1] = match candidate {
+            Some(p) => {
+                selected = *p;
+                *p
+            }
+            None => PlotPoint::gap(bucket[0].x),
+        };
+    }
+    output[m - 1] = input[n - 1];
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::curve::PlotPoint;
+    use crate::downsample::lttb;
+
+    fn line(n: i32) -> [PlotPoint; 100] {
+        let mut points = [PlotPoint { x: 0, y: 0 }; 100];
+        for (i, p) in points.iter_mut().enumerate().take(n as usize) {
+            p.x = i as i32;
+        }
+        points
+    }
+
+    #[test]
+    fn short_input_copied() {
+        let input = line(100);
+        let mut output = [PlotPoint { x: -1, y: -1 }; 200];
+        assert_eq!(lttb(&input[..5], &mut output), 5);
+        assert_eq!(output[4].x, 4);
+    }
+
+    #[test]
+    fn keeps_ends_and_spike() {
+        let mut input = line(100);
+        input[42].y = 1000;
+        let mut output = [PlotPoint { x: 0, y: 0 }; 10];
+        assert_eq!(lttb(&input, &mut output), 10);
+        assert_eq!(output[0].x, 0);
+        assert_eq!(output[9].x, 99);
+        assert!(output.iter().any(|p| p.x == 42 && p.y == 1000));
+    }
+
+    #[test]
+    fn gap_bucket_preserved() {
+        let mut input = line(100);
+        for p in input[20..60].iter_mut() {
+            p.y = PlotPoint::GAP;
+        }
+        let mut output = [PlotPoint { x: 0, y: 0 }; 6];
+        lttb(&input, &mut output);
+        assert!(output.iter().any(|p| p.is_gap()));
+    }
+
+    #[test]
+    fn sparse_bucket_before_gap_keeps_data() {
+        let mut input = line(100);
+        for p in input[25..74].iter_mut() {
+            p.y = PlotPoint::GAP;
+        }
+        input[30].y = 7;
+        input[31].y = 8;
+        let mut output = [PlotPoint { x: 0, y: 0 }; 6];
+        lttb(&input, &mut output);
+        assert_eq!((output[2].x, output[2].y), (31, 8));
+    }
+
+    #[test]
+    fn single_gap_kept_between_data() {
+        let mut input = line(100);
+        input[50].y = PlotPoint::GAP;
+        let mut output = [PlotPoint { x: 0, y: 0 }; 6];
+        assert_eq!(lttb(&input, &mut output), 6);
+        assert_eq!((output[0].x, output[5].x), (0, 99));
+        assert!(output.iter().any(|p| p.is_gap() && p.x == 50));
+    }
+
+    #[test]
+    fn fragmented_data_keeps_gaps() {
+        let mut input = line(100);
+        for p in input.iter_mut().step_by(3) {
+            p.y = PlotPoint::GAP;
+        }
+        let mut output = [PlotPoint { x: 0, y: 0 }; 6];
+        assert_eq!(lttb(&input, &mut output), 6);
+        assert!(output[1..5].iter().all(|p| p.is_gap()));
+    }
+
+    #[test]
+    fn extreme_values_dont_overflow() {
+        let mut input = line(100);
+        for (i, p) in input.iter_mut().enumerate() {
+            p.x = (i as i32 - 50) * (i32::MAX / 50);
+            p.y = if i % 2 == 0 { i32::MAX } else { i32::MIN + 1 };
+        }
+        let mut output = [PlotPoint { x: 0, y: 0 }; 3];
+        assert_eq!(lttb(&input, &mut output), 3);
+    }
+}
@@ -0,0 +1,67 @@
+//! Axes along the bottom and left edges of a plot, layout of spectrum and distribution plots
+use embedded_graphics::{mono_font::MonoTextStyle, prelude::*};
+
+use crate::axis::{Axis, DrawableAxis, Placement};
+
+/// X axis along the bottom edge and Y axis along the left edge of the plot
+pub(crate) struct EdgeAxes<'a, C>
+where
+    C: PixelColor,
+{
+    pub x_axis: Axis<'a>,
+    pub y_axis: Axis<'a>,
+    pub color: C,
+    pub text_style: Option<MonoTextStyle<'a, C>>,
+}
+
+impl<'a, C> EdgeAxes<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// axes placed along the edges of given area
+    pub fn place(
+        &self,
+        top_left: Point,
+        bottom_right: Point,
+    ) -> (DrawableAxis<'a, C>, DrawableAxis<'a, C>) {
+        let mut x_axis = self
+            .x_axis
+            .clone()
+            .into_drawable_axis(Placement::X {
+                x1: top_left.x,
+                x2: bottom_right.x,
+                y: bottom_right.y,
+            })
+            .set_color(self.color);
+        let mut y_axis = self
+            .y_axis
+            .clone()
+            .into_drawable_axis(Placement::Y {
+                y1: top_left.y,
+                y2: bottom_right.y,
+                x: top_left.x,
+            })
+            .set_color(self.color);
+        // axes fall back to their color and 5x8 font on their own
+        if let Some(text_style) = self.text_style {
+            x_axis = x_axis.set_text_style(text_style);
+            y_axis = y_axis.set_text_style(text_style);
+        }
+        (x_axis, y_axis)
+    }
+
+    /// area between the axes - given plot bounds without space for labels
+    pub fn plot_area(&self, top_left: Point, bottom_right: Point) -> (Point, Point) {
+        let (x_axis, y_axis) = self.place(top_left, bottom_right);
+        (
+            Point {
+                x: top_left.x + y_axis.label_extent() as i32,
+                y: top_left.y,
+            },
+            Point {
+                x: bottom_right.x,
+                y: bottom_right.y - x_axis.label_extent() as i32,
+            },
+        )
+    }
+}
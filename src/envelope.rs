@@ -0,0 +1,304 @@
+//! Min/max envelope of downsampled data
+//!
+//! Decimated line, e.g. from [lttb](crate::downsample::lttb), hides short spikes and noise between the kept points.
+//! [min_max] reduces the data to per-bucket extremes, which can be shaded behind the decimated curve
+//! with [set_envelope](crate::single_plot::DrawableSinglePlot::set_envelope) or drawn directly with [Envelope].
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+use crate::curve::PlotPoint;
+use crate::dirty::DirtyTracker;
+use crate::transform::PlotTransform;
+
+/// Extremes of one bucket of the source data
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnvelopePoint {
+    pub x: i32,
+    pub min: i32,
+    pub max: i32,
+}
+
+impl EnvelopePoint {
+    /// create point marking a bucket without any samples at given `x`
+    pub fn gap(x: i32) -> EnvelopePoint {
+        EnvelopePoint {
+            x,
+            min: PlotPoint::GAP,
+            max: PlotPoint::GAP,
+        }
+    }
+
+    pub fn is_gap(&self) -> bool {
+        self.min == PlotPoint::GAP
+    }
+}
+
+/// Reduce `input` into `output.len()` equal buckets and write minimum and maximum of each of them,
+/// placed at X of the middle point of the bucket. Points are expected to be sorted by X.
+/// Bucket containing only gaps produces a gap.
+///
+/// Returns number of points written to `output`. If `input` fits into `output`, every point is its own bucket.
+pub fn min_max(input: &[PlotPoint], output: &mut [EnvelopePoint]) -> usize {
+    let n = input.len();
+    let m = output.len().min(n);
+    for (i, envelope) in output[..m].iter_mut().enumerate() {
+        let bucket = &input[i * n / m..(i + 1) * n / m];
+        let x = bucket[bucket.len() / 2].x;
+        *envelope = bucket
+            .iter()
+            .filter(|p| !p.is_gap())
+            .fold(None, |extremes: Option<(i32, i32)>, p| match extremes {
+                Some((min, max)) => Some((min.min(p.y), max.max(p.y))),
+                None => Some((p.y, p.y)),
+            })
+            .map_or(EnvelopePoint::gap(x), |(min, max)| EnvelopePoint {
+                x,
+                min,
+                max,
+            });
+    }
+    m
+}
+
+/// Display-agnostic shaded area between minimum and maximum of the envelope points
+#[derive(Clone, Copy)]
+pub struct Envelope<'a> {
+    points: &'a [EnvelopePoint],
+}
+
+impl<'a> Envelope<'a> {
+    pub fn new(points: &'a [EnvelopePoint]) -> Envelope<'a> {
+        Envelope { points }
+    }
+
+    /// turn envelope into drawable object placed with provided transform
+    pub fn into_drawable<'t, C>(&self, transform: &'t PlotTransform) -> DrawableEnvelope<'a, 't, C>
+    where
+        C: PixelColor + Default,
+    {
+        DrawableEnvelope {
+            points: self.points,
+            transform,
+            color: None,
+        }
+    }
+}
+
+/// Drawable envelope, constructed for specific display
+pub struct DrawableEnvelope<'a, 't, C>
+where
+    C: PixelColor + Default,
+{
+    points: &'a [EnvelopePoint],
+    transform: &'t PlotTransform,
+    color: Option<C>,
+}
+
+/// builder methods to modify envelope decoration
+impl<'a, 't, C> DrawableEnvelope<'a, 't, C>
+where
+    C: PixelColor + Default,
+{
+    pub fn set_color(mut self, color: C) -> DrawableEnvelope<'a, 't, C> {
+        self.color = Some(color);
+        self
+    }
+}
+
+impl<'a, 't, C> Drawable for DrawableEnvelope<'a, 't, C>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    /// bounding box of pixels touched by the envelope, for flushing only changed region to the display
+    type Output = Rectangle;
+
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<Rectangle, D::Error> {
+        let display = &mut DirtyTracker::new(display);
+        let transform = self.transform;
+        let screen = self.points.iter().map(|p| {
+            if p.is_gap() {
+                EnvelopePoint::gap(transform.x_to_screen(p.x))
+            } else {
+                EnvelopePoint {
+                    x: transform.x_to_screen(p.x),
+                    min: transform.y_to_screen(p.min),
+                    max: transform.y_to_screen(p.max),
+                }
+            }
+        });
+        fill_band(display, screen, self.color.unwrap_or_default())?;
+        Ok(display.dirty())
+    }
+}
+
+/// fill single screen column between two rows, in any order
+pub(crate) fn fill_column<D>(
+    display: &mut D,
+    x: i32,
+    from: i32,
+    to: i32,
+    color: D::Color,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+{
+    display.fill_solid(
+        &Rectangle::with_corners(Point::new(x, from), Point::new(x, to)),
+        color,
+    )
+}
+
+/// fill band between minimums and maximums of points already placed on the screen, columns between
+/// consecutive points are interpolated and gaps break the band. Shared by the envelope, min/max band
+/// of the history and column min/max curves, so all of them shade the same pixels.
+pub(crate) fn fill_band<D>(
+    display: &mut D,
+    points: impl Iterator<Item = EnvelopePoint>,
+    color: D::Color,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+{
+    let mut points = points.peekable();
+    while let Some(point) = points.next() {
+        if point.is_gap() {
+            continue;
+        }
+        match points.peek().filter(|next| !next.is_gap()) {
+            // columns up to the next point are interpolated, the next point draws its own
+            Some(next) => {
+                let width = next.x - point.x;
+                let step = width.signum();
+                for offset in (0..width.abs()).map(|offset| offset * step) {
+                    let between = |from: i32, to: i32| from + (to - from) * offset / width;
+                    fill_column(
+                        display,
+                        point.x + offset,
+                        between(point.max, next.max),
+                        between(point.min, next.min),
+                        color,
+                    )?;
+                }
+            }
+            None => fill_column(display, point.x, point.max, point.min, color)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::curve::PlotPoint;
+    use crate::envelope::{min_max, Envelope, EnvelopePoint};
+    use crate::transform::PlotTransform;
+    use embedded_graphics::{
+        mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::*, primitives::Rectangle,
+    };
+
+    fn draw(points: &[EnvelopePoint]) -> MockDisplay<BinaryColor> {
+        let transform =
+            PlotTransform::new(0..10, 0..10, Point::zero(), Point::new(40, 40)).unwrap();
+        let mut display = MockDisplay::new();
+        Envelope::new(points)
+            .into_drawable(&transform)
+            .set_color(BinaryColor::On)
+            .draw(&mut display)
+            .unwrap();
+        display
+    }
+
+    #[test]
+    fn bucket_extremes() {
+        let mut input = [PlotPoint { x: 0, y: 0 }; 12];
+        for (i, p) in input.iter_mut().enumerate() {
+            p.x = i as i32;
+        }
+        input[2].y = 7;
+        input[3].y = -3;
+        for p in input[8..].iter_mut() {
+            p.y = PlotPoint::GAP;
+        }
+        let mut output = [EnvelopePoint::gap(0); 3];
+        assert_eq!(min_max(&input, &mut output), 3);
+        assert_eq!(
+            output[0],
+            EnvelopePoint {
+                x: 2,
+                min: -3,
+                max: 7
+            }
+        );
+        assert_eq!(
+            output[1],
+            EnvelopePoint {
+                x: 6,
+                min: 0,
+                max: 0
+            }
+        );
+        assert!(output[2].is_gap());
+    }
+
+    #[test]
+    fn single_point_draws_column() {
+        let display = draw(&[EnvelopePoint {
+            x: 5,
+            min: 2,
+            max: 8,
+        }]);
+        assert_eq!(
+            display.affected_area(),
+            Rectangle::with_corners(Point::new(20, 8), Point::new(20, 32))
+        );
+    }
+
+    #[test]
+    fn drawn_area_returned() {
+        let transform =
+            PlotTransform::new(0..10, 0..10, Point::zero(), Point::new(40, 40)).unwrap();
+        let mut display = MockDisplay::new();
+        let area = Envelope::new(&[EnvelopePoint {
+            x: 5,
+            min: 2,
+            max: 8,
+        }])
+        .into_drawable(&transform)
+        .set_color(BinaryColor::On)
+        .draw(&mut display)
+        .unwrap();
+        assert_eq!(area, display.affected_area());
+    }
+
+    #[test]
+    fn columns_interpolated_between_points() {
+        let display = draw(&[
+            EnvelopePoint {
+                x: 0,
+                min: 0,
+                max: 10,
+            },
+            EnvelopePoint {
+                x: 10,
+                min: 5,
+                max: 5,
+            },
+        ]);
+        assert_eq!(
+            display.affected_area(),
+            Rectangle::with_corners(Point::zero(), Point::new(40, 40))
+        );
+        assert!((0..=40).all(|x| display.get_pixel(Point::new(x, 20)) == Some(BinaryColor::On)));
+        assert_eq!(display.get_pixel(Point::new(40, 19)), None);
+    }
+
+    #[test]
+    fn gap_breaks_envelope() {
+        let point = |x| EnvelopePoint { x, min: 4, max: 6 };
+        let display = draw(&[point(0), EnvelopePoint::gap(5), point(10)]);
+        assert_eq!(display.get_pixel(Point::new(0, 20)), Some(BinaryColor::On));
+        assert_eq!(display.get_pixel(Point::new(40, 20)), Some(BinaryColor::On));
+        assert!((1..40).all(|x| display.get_pixel(Point::new(x, 20)).is_none()));
+    }
+}
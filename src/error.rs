@@ -0,0 +1,39 @@
+//! Errors reported while drawing
+//!
+//! [Drawable::draw](embedded_graphics::Drawable::draw) can only return the error of the display,
+//! so invalid plot geometry makes it skip the curves silently. Fallible variants like
+//! [try_draw](crate::single_plot::DrawableSinglePlot::try_draw) report both through [PlotError].
+use core::fmt;
+
+/// Error of the plot itself or of the display it's drawn on
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlotError<E> {
+    /// plot can't be drawn as configured, e.g. its ranges or area are empty
+    Invalid(&'static str),
+    /// error of the display, passed through
+    Display(E),
+}
+
+impl<E> PlotError<E> {
+    /// error of the display, if that's what failed
+    pub fn display_error(self) -> Option<E> {
+        match self {
+            PlotError::Invalid(_) => None,
+            PlotError::Display(error) => Some(error),
+        }
+    }
+}
+
+impl<E> fmt::Display for PlotError<E>
+where
+    E: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlotError::Invalid(message) => f.write_str(message),
+            PlotError::Display(error) => write!(f, "display error: {:?}", error),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Labeled vertical lines marking events, e.g. resets or alarms, at X positions of the plot
+//!
+//! [EventMarker] is a [PlotOverlay], so it shares the coordinate transform of the plot it's added to
+//! and is clipped to the plot area. Line and label are drawn the same way as vertical [RefLine].
+use embedded_graphics::{draw_target::DrawTargetExt, mono_font::MonoTextStyle, prelude::*};
+
+use crate::overlay::{OverlayError, OverlayTarget, PlotOverlay};
+use crate::ref_line::RefLine;
+use crate::style::LinePattern;
+use crate::transform::PlotTransform;
+
+/// Vertical line at X value of the event with a short label next to its top end
+#[derive(Clone, Copy)]
+pub struct EventMarker<'a, C>
+where
+    C: PixelColor + Default,
+{
+    x: i32,
+    label: &'a str,
+    color: Option<C>,
+    pattern: Option<LinePattern>,
+    text_style: Option<MonoTextStyle<'a, C>>,
+}
+
+/// builder methods to modify marker decoration
+impl<'a, C> EventMarker<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// create marker of event at given X value, e.g. `EventMarker::new(time, "reset")`
+    pub fn new(x: i32, label: &'a str) -> EventMarker<'a, C> {
+        EventMarker {
+            x,
+            label,
+            color: None,
+            pattern: None,
+            text_style: None,
+        }
+    }
+
+    /// set line color, also used for the label if text style is not set
+    pub fn set_color(mut self, color: C) -> EventMarker<'a, C> {
+        self.color = Some(color);
+        self
+    }
+
+    /// set dash pattern of the line, solid if not set
+    pub fn set_pattern(mut self, pattern: LinePattern) -> EventMarker<'a, C> {
+        self.pattern = Some(pattern);
+        self
+    }
+
+    pub fn set_text_style(mut self, style: MonoTextStyle<'a, C>) -> EventMarker<'a, C> {
+        self.text_style = Some(style);
+        self
+    }
+}
+
+impl<'a, C> PlotOverlay<C> for EventMarker<'a, C>
+where
+    C: PixelColor + Default,
+{
+    fn draw(
+        &self,
+        transform: &PlotTransform,
+        target: &mut OverlayTarget<'_, C>,
+    ) -> Result<(), OverlayError> {
+        let mut line = RefLine::vertical(self.x)
+            .set_label(self.label)
+            .into_drawable(transform)
+            .set_color(self.color.unwrap_or_default())
+            .set_pattern(self.pattern.unwrap_or_default());
+        if let Some(text_style) = self.text_style {
+            line = line.set_text_style(text_style);
+        }
+        line.draw(&mut target.clipped(&transform.area()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::axis::Scale;
+    use crate::curve::{Curve, PlotPoint};
+    use crate::event_marker::EventMarker;
+    use crate::single_plot::SinglePlot;
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::{Rgb565, RgbColor},
+        prelude::*,
+    };
+
+    #[test]
+    fn line_at_event() {
+        let points = [PlotPoint { x: 0, y: 0 }, PlotPoint { x: 10, y: 0 }];
+        let curves = [(Curve::from_data(&points), Rgb565::WHITE)];
+        let marker = EventMarker::new(5, "rst").set_color(Rgb565::RED);
+        let plot = SinglePlot::new(&curves, Scale::Auto, Scale::Auto)
+            .unwrap()
+            .set_y_range(0..10)
+            .into_drawable(Point::zero(), Point::new(63, 63))
+            .add_overlay(&marker)
+            .unwrap();
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        plot.draw(&mut display).unwrap();
+        let event = plot.data_to_screen(PlotPoint { x: 5, y: 5 }).unwrap();
+        assert_eq!(display.get_pixel(event), Some(Rgb565::RED));
+    }
+}
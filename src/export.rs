@@ -0,0 +1,300 @@
+//! Export of plots to BMP images on the host, available with `std` feature
+//!
+//! [Bitmap] is an in-memory display plots are drawn onto in tests or tools. Written as BMP file, it serves
+//! as documentation screenshot or golden image, and [Bitmap::from_bmp] reads such file back,
+//! so regression tests can compare the drawing with the stored image pixel by pixel.
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::vec::Vec;
+
+use embedded_graphics::{
+    mock_display::MockDisplay,
+    pixelcolor::{Rgb888, RgbColor},
+    prelude::*,
+    primitives::Rectangle,
+};
+
+/// size of file header and info header of the written files
+const HEADER_SIZE: usize = 54;
+
+/// Owned image buffer plots can be drawn onto, pixels outside of it are ignored
+#[derive(Clone, PartialEq, Debug)]
+pub struct Bitmap<C> {
+    size: Size,
+    pixels: Vec<C>,
+}
+
+impl<C> Bitmap<C>
+where
+    C: PixelColor,
+{
+    /// create image of given size filled with background color
+    pub fn new(size: Size, background: C) -> Bitmap<C> {
+        Bitmap {
+            size,
+            pixels: vec![background; (size.width * size.height) as usize],
+        }
+    }
+
+    /// draw the drawable onto image reaching from the display origin to its bottom right corner,
+    /// so it's at the same position as on the display
+    pub fn from_drawable<T>(drawable: &T, background: C) -> Bitmap<C>
+    where
+        T: Drawable<Color = C> + Dimensions,
+    {
+        let area = drawable.bounding_box();
+        let size = match area.bottom_right() {
+            Some(corner) => Size::new(corner.x.max(-1) as u32 + 1, corner.y.max(-1) as u32 + 1),
+            None => Size::zero(),
+        };
+        let mut bitmap = Bitmap::new(size, background);
+        // drawing into memory can't fail
+        drawable.draw(&mut bitmap).ok();
+        bitmap
+    }
+
+    /// copy pixels drawn on the mock display, pixels not drawn are set to background color
+    pub fn from_mock_display(display: &MockDisplay<C>, background: C) -> Bitmap<C> {
+        let mut bitmap = Bitmap::new(display.size(), background);
+        let pixels = display
+            .bounding_box()
+            .points()
+            .filter_map(|point| Some(Pixel(point, display.get_pixel(point)?)));
+        bitmap.draw_iter(pixels).ok();
+        bitmap
+    }
+
+    /// color of the pixel, none outside of the image
+    pub fn pixel(&self, point: Point) -> Option<C> {
+        self.index(point).map(|index| self.pixels[index])
+    }
+
+    fn index(&self, point: Point) -> Option<usize> {
+        if self.bounding_box().contains(point) {
+            Some((point.y as u32 * self.size.width + point.x as u32) as usize)
+        } else {
+            None
+        }
+    }
+}
+
+impl<C> Bitmap<C>
+where
+    C: PixelColor + Into<Rgb888>,
+{
+    /// write the image as uncompressed 24-bit BMP
+    pub fn write_bmp<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let row = self.row_size();
+        let data = row * self.size.height as usize;
+        let mut header = [0u8; HEADER_SIZE];
+        header[0..2].copy_from_slice(b"BM");
+        header[2..6].copy_from_slice(&((HEADER_SIZE + data) as u32).to_le_bytes());
+        header[10..14].copy_from_slice(&(HEADER_SIZE as u32).to_le_bytes());
+        header[14..18].copy_from_slice(&40u32.to_le_bytes());
+        header[18..22].copy_from_slice(&self.size.width.to_le_bytes());
+        header[22..26].copy_from_slice(&self.size.height.to_le_bytes());
+        header[26..28].copy_from_slice(&1u16.to_le_bytes());
+        header[28..30].copy_from_slice(&24u16.to_le_bytes());
+        header[34..38].copy_from_slice(&(data as u32).to_le_bytes());
+        writer.write_all(&header)?;
+        let mut line = vec![0u8; row];
+        // rows are stored bottom-up, pixels as BGR
+        for y in (0..self.size.height as usize).rev() {
+            let start = y * self.size.width as usize;
+            let pixels = &self.pixels[start..start + self.size.width as usize];
+            for (bytes, pixel) in line.chunks_exact_mut(3).zip(pixels) {
+                let color: Rgb888 = (*pixel).into();
+                bytes.copy_from_slice(&[color.b(), color.g(), color.r()]);
+            }
+            writer.write_all(&line)?;
+        }
+        Ok(())
+    }
+
+    /// write the image as BMP file, e.g. documentation screenshot or golden image
+    pub fn save_bmp<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_bmp(&mut writer)?;
+        writer.flush()
+    }
+
+    /// image converted to 24-bit colors, as it is stored in BMP file
+    pub fn to_rgb888(&self) -> Bitmap<Rgb888> {
+        Bitmap {
+            size: self.size,
+            pixels: self.pixels.iter().map(|pixel| (*pixel).into()).collect(),
+        }
+    }
+
+    /// rows are padded to multiple of 4 bytes
+    fn row_size(&self) -> usize {
+        (self.size.width as usize * 3 + 3) & !3
+    }
+}
+
+impl Bitmap<Rgb888> {
+    /// read uncompressed 24-bit BMP, as written by [Bitmap::write_bmp], rows may be stored bottom-up or top-down
+    pub fn from_bmp(bytes: &[u8]) -> Result<Bitmap<Rgb888>, &'static str> {
+        let u32_at = |at: usize| -> Result<u32, &'static str> {
+            let field = bytes.get(at..at + 4).ok_or("Truncated BMP header")?;
+            Ok(u32::from_le_bytes([field[0], field[1], field[2], field[3]]))
+        };
+        if bytes.get(0..2) != Some(b"BM") {
+            return Err("Not a BMP file");
+        }
+        if bytes.get(28..30) != Some(&24u16.to_le_bytes()) || u32_at(30)? != 0 {
+            return Err("Unsupported BMP format");
+        }
+        let offset = u32_at(10)? as usize;
+        let (width, height) = (u32_at(18)? as i32, u32_at(22)? as i32);
+        // negative height marks rows stored top-down
+        let top_down = height < 0;
+        let height = height.checked_abs().ok_or("Invalid BMP size")?;
+        if width <= 0 || height == 0 {
+            return Err("Invalid BMP size");
+        }
+        let (width, height) = (width as usize, height as usize);
+        // check the data is all there before allocating the image
+        let row = width
+            .checked_mul(3)
+            .and_then(|bytes| bytes.checked_add(3))
+            .ok_or("Invalid BMP size")?
+            & !3;
+        let end = row
+            .checked_mul(height)
+            .and_then(|data| data.checked_add(offset))
+            .ok_or("Invalid BMP size")?;
+        if end > bytes.len() {
+            return Err("Truncated BMP data");
+        }
+        let mut bitmap = Bitmap::new(Size::new(width as u32, height as u32), Rgb888::BLACK);
+        for y in 0..height {
+            let stored = if top_down { y } else { height - 1 - y };
+            let start = offset + stored * row;
+            let line = &bytes[start..start + width * 3];
+            let pixels = &mut bitmap.pixels[y * width..];
+            for (pixel, bgr) in pixels.iter_mut().zip(line.chunks_exact(3)) {
+                *pixel = Rgb888::new(bgr[2], bgr[1], bgr[0]);
+            }
+        }
+        Ok(bitmap)
+    }
+}
+
+impl<C> OriginDimensions for Bitmap<C> {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl<C> DrawTarget for Bitmap<C>
+where
+    C: PixelColor,
+{
+    type Color = C;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<C>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let Some(index) = self.index(point) {
+                self.pixels[index] = color;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<C> Drawable for Bitmap<C>
+where
+    C: PixelColor,
+{
+    type Color = C;
+    /// area of the display covered by the image
+    type Output = Rectangle;
+
+    /// draw the image onto the display, with its origin at the origin of the display
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<Rectangle, D::Error> {
+        let area = self.bounding_box();
+        display.fill_contiguous(&area, self.pixels.iter().copied())?;
+        Ok(area)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::export::Bitmap;
+    use crate::gauge::Gauge;
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::{Rgb565, Rgb888},
+        prelude::*,
+    };
+    use std::vec::Vec;
+
+    #[test]
+    fn bmp_round_trip() {
+        let gauge = Gauge::new(40, 0..100)
+            .unwrap()
+            .into_drawable(Point::new(2, 2), Point::new(42, 20))
+            .set_color(Rgb565::GREEN)
+            .set_axis_color(Rgb565::WHITE);
+        let bitmap = Bitmap::from_drawable(&gauge, Rgb565::BLACK);
+        assert_eq!(bitmap.size(), Size::new(43, 21));
+        let mut file = Vec::new();
+        bitmap.write_bmp(&mut file).unwrap();
+        assert_eq!(file.len(), 54 + 132 * 21);
+        assert_eq!(Bitmap::from_bmp(&file), Ok(bitmap.to_rgb888()));
+    }
+
+    #[test]
+    fn top_down_bmp() {
+        let gauge = Gauge::new(40, 0..100)
+            .unwrap()
+            .into_drawable(Point::new(2, 2), Point::new(42, 20))
+            .set_color(Rgb888::GREEN);
+        let bitmap = Bitmap::from_drawable(&gauge, Rgb888::BLACK);
+        let mut file = Vec::new();
+        bitmap.write_bmp(&mut file).unwrap();
+        let (header, data) = file.split_at(54);
+        let mut flipped = header.to_vec();
+        flipped[22..26].copy_from_slice(&(-21i32).to_le_bytes());
+        data.chunks_exact(132)
+            .rev()
+            .for_each(|row| flipped.extend_from_slice(row));
+        assert_eq!(Bitmap::from_bmp(&flipped), Ok(bitmap));
+    }
+
+    #[test]
+    fn bogus_size_rejected() {
+        let bitmap = Bitmap::new(Size::new(4, 4), Rgb888::BLACK);
+        let mut file = Vec::new();
+        bitmap.write_bmp(&mut file).unwrap();
+        let mut huge = file.clone();
+        huge[18..22].copy_from_slice(&0x4000_0000u32.to_le_bytes());
+        huge[22..26].copy_from_slice(&0x4000_0000u32.to_le_bytes());
+        assert!(Bitmap::from_bmp(&huge).is_err());
+        let mut empty = file;
+        empty[18..22].copy_from_slice(&0u32.to_le_bytes());
+        assert!(Bitmap::from_bmp(&empty).is_err());
+    }
+
+    #[test]
+    fn same_as_mock_display() {
+        let gauge = Gauge::new(40, 0..100)
+            .unwrap()
+            .into_drawable(Point::new(2, 2), Point::new(42, 20))
+            .set_color(Rgb888::GREEN);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        gauge.draw(&mut display).unwrap();
+        let drawn = Bitmap::from_mock_display(&display, Rgb888::BLACK);
+        let bitmap = Bitmap::from_drawable(&gauge, Rgb888::BLACK);
+        for point in bitmap.bounding_box().points() {
+            assert_eq!(drawn.pixel(point), bitmap.pixel(point));
+        }
+    }
+}
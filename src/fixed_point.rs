@@ -0,0 +1,123 @@
+//! Fixed point (Q16.16) data support, enabled with `fixed` feature
+//!
+//! Fractional sensor values can be plotted without pulling in float math, which matters on
+//! targets without FPU like Cortex-M0. Since mapping between ranges is linear, scaling raw bits
+//! of Q16.16 values gives exactly the same result as scaling the values themselves, so
+//! fixed point data is stored in regular [PlotPoint]s as raw bits and only tick labels need to know about it.
+//! ```rust
+//! # use embedded_plots::axis::Axis;
+//! # use embedded_plots::curve::Curve;
+//! # use embedded_plots::fixed_point::{format_q16, FixedPlotPoint, Q16};
+//! let data = [
+//!     FixedPlotPoint::new(Q16::from_num(0), Q16::from_num(1) / 2).to_raw(),
+//!     FixedPlotPoint::new(Q16::from_num(1), Q16::from_num(5) / 4).to_raw(),
+//! ];
+//! let curve = Curve::from_data(&data);
+//! let y_axis = Axis::new(curve.y_range.clone()).set_label_formatter(format_q16);
+//! ```
+use core::fmt::{Result, Write};
+use core::ops::Range;
+
+use crate::curve::PlotPoint;
+
+/// Q16.16 fixed point number
+pub type Q16 = fixed::types::I16F16;
+
+/// single point of the curve with fixed point coordinates
+#[derive(Clone, Copy)]
+pub struct FixedPlotPoint {
+    pub x: Q16,
+    pub y: Q16,
+}
+
+impl FixedPlotPoint {
+    pub fn new(x: Q16, y: Q16) -> FixedPlotPoint {
+        FixedPlotPoint { x, y }
+    }
+
+    /// store point as raw bits, suitable for [Curve](crate::curve::Curve)
+    pub fn to_raw(self) -> PlotPoint {
+        PlotPoint {
+            x: self.x.to_bits(),
+            y: self.y.to_bits(),
+        }
+    }
+
+    /// restore point stored as raw bits
+    pub fn from_raw(point: PlotPoint) -> FixedPlotPoint {
+        FixedPlotPoint {
+            x: Q16::from_bits(point.x),
+            y: Q16::from_bits(point.y),
+        }
+    }
+}
+
+impl From<FixedPlotPoint> for PlotPoint {
+    fn from(point: FixedPlotPoint) -> PlotPoint {
+        point.to_raw()
+    }
+}
+
+/// convert range of fixed point values into range of raw bits, e.g. for [Curve::new](crate::curve::Curve::new)
+pub fn to_raw_range(range: Range<Q16>) -> Range<i32> {
+    range.start.to_bits()..range.end.to_bits()
+}
+
+/// label formatter printing raw bits as Q16.16 value with two decimal places,
+/// to be used with [Axis::set_label_formatter](crate::axis::Axis::set_label_formatter)
+pub fn format_q16(raw: i32, f: &mut dyn Write) -> Result {
+    write!(f, "{:.2}", Q16::from_bits(raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::curve::{Curve, PlotPoint};
+    use crate::fixed_point::{format_q16, to_raw_range, FixedPlotPoint, Q16};
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::*};
+    use heapless::String;
+    use test_case::test_case;
+
+    #[test_case(0 => "0.00")]
+    #[test_case(1 << 15 => "0.50"; "half")]
+    #[test_case(-(5 << 14) => "-1.25"; "negative")]
+    fn label(raw: i32) -> String<8> {
+        let mut label = String::new();
+        format_q16(raw, &mut label).unwrap();
+        label
+    }
+
+    #[test]
+    fn raw_round_trip() {
+        let point = FixedPlotPoint::new(Q16::from_num(3) / 4, Q16::from_num(-2));
+        let raw = point.to_raw();
+        assert_eq!((raw.x, raw.y), (3 << 14, -2 << 16));
+        let back = FixedPlotPoint::from_raw(raw);
+        assert_eq!((back.x, back.y), (point.x, point.y));
+    }
+
+    #[test]
+    fn drawn_like_scaled_integers() {
+        let fixed = [
+            FixedPlotPoint::new(Q16::from_num(0), Q16::from_num(1) / 2).to_raw(),
+            FixedPlotPoint::new(Q16::from_num(1), Q16::from_num(5) / 4).to_raw(),
+        ];
+        let scaled = [PlotPoint { x: 0, y: 50 }, PlotPoint { x: 100, y: 125 }];
+        let draw = |curve: Curve| {
+            let mut display = MockDisplay::new();
+            display.set_allow_overdraw(true);
+            curve
+                .into_drawable_curve(&Point::zero(), &Point::new(40, 40))
+                .unwrap()
+                .set_color(BinaryColor::On)
+                .draw(&mut display)
+                .unwrap();
+            display
+        };
+        let fixed_curve = Curve::new(
+            &fixed,
+            to_raw_range(Q16::from_num(0)..Q16::from_num(1)),
+            to_raw_range(Q16::from_num(0)..Q16::from_num(2)),
+        );
+        assert_eq!(draw(fixed_curve), draw(Curve::new(&scaled, 0..100, 0..200)));
+    }
+}
@@ -0,0 +1,112 @@
+//! Compact decimal formatting of tick labels, enabled with `float` feature
+//!
+//! `core` float formatting pulls in several kilobytes of code, which hurts on small flash parts.
+//! Functions in this module round the value to fixed number of decimals with integer math
+//! and trim trailing zeros, so labels like `0.5`, `1` and `1.25` stay short.
+//! Fractional data is usually stored scaled, e.g. in tenths of degree, and printed with
+//! [format_tenths] and similar [LabelFormatter](crate::axis::LabelFormatter)s:
+//! ```rust
+//! # use embedded_plots::axis::Axis;
+//! # use embedded_plots::float_format::format_tenths;
+//! // temperature logged in tenths of °C
+//! let y_axis = Axis::new(195..260).set_label_formatter(format_tenths);
+//! ```
+use core::fmt::{Result, Write};
+
+/// largest number of decimals, so the scaled value still fits `u64`
+const MAX_DECIMALS: u8 = 9;
+
+/// write `value` divided by `10^decimals`, trailing zeros of the fraction are trimmed
+pub fn write_scaled(f: &mut dyn Write, value: i64, decimals: u8) -> Result {
+    let decimals = decimals.min(MAX_DECIMALS);
+    if value < 0 {
+        f.write_char('-')?;
+    }
+    write_unsigned(f, value.unsigned_abs(), decimals)
+}
+
+/// write float rounded to given number of decimals, trailing zeros of the fraction are trimmed.
+/// Values too large to be rounded precisely are written without the fraction.
+pub fn write_float(f: &mut dyn Write, value: f32, decimals: u8) -> Result {
+    if value.is_nan() {
+        return f.write_str("NaN");
+    }
+    if value.is_infinite() {
+        return f.write_str(if value < 0.0 { "-inf" } else { "inf" });
+    }
+    let mut decimals = decimals.min(MAX_DECIMALS);
+    let magnitude = if value < 0.0 { -value } else { value };
+    while decimals > 0 && magnitude * pow10(decimals) as f32 >= u64::MAX as f32 {
+        decimals -= 1;
+    }
+    // round half away from zero without `f32::round`, which needs std
+    let scaled = (magnitude * pow10(decimals) as f32 + 0.5) as u64;
+    if value < 0.0 && scaled != 0 {
+        f.write_char('-')?;
+    }
+    write_unsigned(f, scaled, decimals)
+}
+
+fn write_unsigned(f: &mut dyn Write, scaled: u64, decimals: u8) -> Result {
+    let scale = pow10(decimals);
+    write!(f, "{}", scaled / scale)?;
+    let mut fraction = scaled % scale;
+    if fraction == 0 {
+        return Ok(());
+    }
+    let mut digits = decimals as usize;
+    while fraction / 10 * 10 == fraction {
+        fraction /= 10;
+        digits -= 1;
+    }
+    write!(f, ".{:0width$}", fraction, width = digits)
+}
+
+fn pow10(exponent: u8) -> u64 {
+    10u64.pow(exponent as u32)
+}
+
+/// label formatter for values stored in tenths, e.g. 215 is written as `21.5`
+pub fn format_tenths(value: i32, f: &mut dyn Write) -> Result {
+    write_scaled(f, value as i64, 1)
+}
+
+/// label formatter for values stored in hundredths, e.g. 150 is written as `1.5`
+pub fn format_hundredths(value: i32, f: &mut dyn Write) -> Result {
+    write_scaled(f, value as i64, 2)
+}
+
+/// label formatter for values stored in thousandths, e.g. millivolts written as volts
+pub fn format_thousandths(value: i32, f: &mut dyn Write) -> Result {
+    write_scaled(f, value as i64, 3)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::float_format::{write_float, write_scaled};
+    use heapless::String;
+    use test_case::test_case;
+
+    #[test_case(215, 1 => "21.5")]
+    #[test_case(150, 2 => "1.5")]
+    #[test_case(-5, 2 => "-0.05")]
+    #[test_case(3000, 3 => "3")]
+    #[test_case(7, 0 => "7")]
+    fn scaled(value: i64, decimals: u8) -> String<24> {
+        let mut text = String::new();
+        write_scaled(&mut text, value, decimals).unwrap();
+        text
+    }
+
+    #[test_case(1.25, 2 => "1.25")]
+    #[test_case(0.5, 3 => "0.5")]
+    #[test_case(2.996, 2 => "3")]
+    #[test_case(-0.001, 2 => "0")]
+    #[test_case(-12.34, 1 => "-12.3")]
+    #[test_case(f32::NAN, 2 => "NaN")]
+    fn float(value: f32, decimals: u8) -> String<24> {
+        let mut text = String::new();
+        write_float(&mut text, value, decimals).unwrap();
+        text
+    }
+}
@@ -0,0 +1,291 @@
+//! Linear bar gauge, e.g. battery level, tank level or volume meter
+//!
+//! [Gauge] fills a bar proportionally to the value within its range, with tick marks drawn by the regular
+//! [Axis] using the same [Scale]. Zones of the range can be colored differently, so the bar turns
+//! e.g. red once it reaches the warning level.
+use core::ops::Range;
+
+use embedded_graphics::{mono_font::MonoTextStyle, prelude::*, primitives::Rectangle};
+
+use crate::axis::{Axis, DrawableAxis, Placement, Scale};
+use crate::dirty::DirtyTracker;
+use crate::placement::IntoDrawableArea;
+use crate::range_conv::Scalable;
+
+/// Direction the bar grows in
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GaugeOrientation {
+    /// bar grows to the right, ticks are drawn below it
+    Horizontal,
+    /// bar grows upwards, ticks are drawn on its left
+    Vertical,
+}
+
+impl Default for GaugeOrientation {
+    fn default() -> Self {
+        GaugeOrientation::Horizontal
+    }
+}
+
+/// Display-agnostic gauge showing single value within a range
+#[derive(Clone)]
+pub struct Gauge<'a> {
+    value: i32,
+    range: Range<i32>,
+    scale: Option<Scale<'a>>,
+    orientation: Option<GaugeOrientation>,
+}
+
+impl<'a> Gauge<'a> {
+    /// create gauge of the value, values outside of the range fill the bar completely or leave it empty
+    pub fn new(value: i32, range: Range<i32>) -> Result<Gauge<'a>, &'static str> {
+        if range.start == range.end {
+            Err("Empty gauge range")
+        } else {
+            Ok(Gauge {
+                value,
+                range,
+                scale: None,
+                orientation: None,
+            })
+        }
+    }
+
+    /// set density of tick marks, [Scale::Auto] if not set
+    pub fn set_scale(mut self, scale: Scale<'a>) -> Gauge<'a> {
+        self.scale = Some(scale);
+        self
+    }
+
+    /// set direction of the bar, horizontal if not set
+    pub fn set_orientation(mut self, orientation: GaugeOrientation) -> Gauge<'a> {
+        self.orientation = Some(orientation);
+        self
+    }
+
+    /// convert to drawable form for specific display
+    pub fn into_drawable<C>(self, top_left: Point, bottom_right: Point) -> DrawableGauge<'a, C>
+    where
+        C: PixelColor + Default,
+    {
+        DrawableGauge {
+            gauge: self,
+            color: None,
+            track_color: None,
+            axis_color: None,
+            text_style: None,
+            zones: &[],
+            ticks_hidden: false,
+            top_left,
+            bottom_right,
+        }
+    }
+}
+
+impl<'a, C> IntoDrawableArea<DrawableGauge<'a, C>> for Gauge<'a>
+where
+    C: PixelColor + Default,
+{
+    fn into_drawable_between(self, top_left: Point, bottom_right: Point) -> DrawableGauge<'a, C> {
+        self.into_drawable(top_left, bottom_right)
+    }
+}
+
+/// Drawable gauge, constructed for specific display
+pub struct DrawableGauge<'a, C>
+where
+    C: PixelColor + Default,
+{
+    gauge: Gauge<'a>,
+    color: Option<C>,
+    track_color: Option<C>,
+    axis_color: Option<C>,
+    text_style: Option<MonoTextStyle<'a, C>>,
+    zones: &'a [(Range<i32>, C)],
+    ticks_hidden: bool,
+    top_left: Point,
+    bottom_right: Point,
+}
+
+/// builder methods to modify gauge decoration
+impl<'a, C> DrawableGauge<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// set color of the filled part of the bar
+    pub fn set_color(mut self, color: C) -> DrawableGauge<'a, C> {
+        self.color = Some(color);
+        self
+    }
+
+    /// set color of the empty part of the bar, if not set, it is left transparent
+    pub fn set_track_color(mut self, color: C) -> DrawableGauge<'a, C> {
+        self.track_color = Some(color);
+        self
+    }
+
+    /// if not set, main color will be used
+    pub fn set_axis_color(mut self, color: C) -> DrawableGauge<'a, C> {
+        self.axis_color = Some(color);
+        self
+    }
+
+    /// set style of tick labels, if not set, axis color and 5x8 font are used
+    pub fn set_text_style(mut self, style: MonoTextStyle<'a, C>) -> DrawableGauge<'a, C> {
+        self.text_style = Some(style);
+        self
+    }
+
+    /// set zones of the range with their own colors, e.g. warning and critical levels -
+    /// part of the filled bar within the zone is drawn with its color
+    pub fn set_zones(mut self, zones: &'a [(Range<i32>, C)]) -> DrawableGauge<'a, C> {
+        self.zones = zones;
+        self
+    }
+
+    /// draw the bar only, without tick marks and labels
+    pub fn set_ticks_hidden(mut self) -> DrawableGauge<'a, C> {
+        self.ticks_hidden = true;
+        self
+    }
+
+    /// tick axis along the bar and area of the bar itself
+    fn layout(&self) -> (DrawableAxis<'a, C>, Rectangle) {
+        let color = self.axis_color.or(self.color).unwrap_or_default();
+        let axis =
+            Axis::new(self.gauge.range.clone()).set_scale(self.gauge.scale.unwrap_or_default());
+        let placement = |offset: i32| match self.gauge.orientation.unwrap_or_default() {
+            GaugeOrientation::Horizontal => Placement::X {
+                x1: self.top_left.x,
+                x2: self.bottom_right.x,
+                y: self.bottom_right.y - offset,
+            },
+            GaugeOrientation::Vertical => Placement::Y {
+                y1: self.top_left.y,
+                y2: self.bottom_right.y,
+                x: self.top_left.x + offset,
+            },
+        };
+        let styled = |placement| {
+            let axis = axis.clone().into_drawable_axis(placement).set_color(color);
+            // axis falls back to its color and 5x8 font on its own
+            match self.text_style {
+                Some(text_style) => axis.set_text_style(text_style),
+                None => axis,
+            }
+        };
+        let extent = if self.ticks_hidden {
+            0
+        } else {
+            styled(placement(0)).label_extent() as i32
+        };
+        let bar = match self.gauge.orientation.unwrap_or_default() {
+            GaugeOrientation::Horizontal => Rectangle::with_corners(
+                self.top_left,
+                Point::new(self.bottom_right.x, self.bottom_right.y - extent - 1),
+            ),
+            GaugeOrientation::Vertical => Rectangle::with_corners(
+                Point::new(self.top_left.x + extent + 1, self.top_left.y),
+                self.bottom_right,
+            ),
+        };
+        (styled(placement(extent)), bar)
+    }
+
+    /// part of the bar between given values
+    fn segment(&self, bar: &Rectangle, from: i32, to: i32) -> Rectangle {
+        let range = &self.gauge.range;
+        let (low, high) = (range.start.min(range.end), range.start.max(range.end));
+        let bottom_right = bar.bottom_right().unwrap_or(bar.top_left);
+        match self.gauge.orientation.unwrap_or_default() {
+            GaugeOrientation::Horizontal => {
+                let pixels = bar.top_left.x..bottom_right.x;
+                let x = |value: i32| value.clamp(low, high).scale_between_ranges(range, &pixels);
+                Rectangle::with_corners(
+                    Point::new(x(from), bar.top_left.y),
+                    Point::new(x(to), bottom_right.y),
+                )
+            }
+            GaugeOrientation::Vertical => {
+                let pixels = bottom_right.y..bar.top_left.y;
+                let y = |value: i32| value.clamp(low, high).scale_between_ranges(range, &pixels);
+                Rectangle::with_corners(
+                    Point::new(bar.top_left.x, y(from)),
+                    Point::new(bottom_right.x, y(to)),
+                )
+            }
+        }
+    }
+}
+
+impl<'a, C> Dimensions for DrawableGauge<'a, C>
+where
+    C: PixelColor + Default,
+{
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::with_corners(self.top_left, self.bottom_right)
+    }
+}
+
+impl<'a, C> Drawable for DrawableGauge<'a, C>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    /// bounding box of pixels touched by the gauge, for flushing only changed region to the display
+    type Output = Rectangle;
+
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<Rectangle, D::Error> {
+        let display = &mut DirtyTracker::new(display);
+        let (axis, bar) = self.layout();
+        if let Some(color) = self.track_color {
+            display.fill_solid(&bar, color)?;
+        }
+        let Range { start, end } = self.gauge.range;
+        let value = self.gauge.value.clamp(start.min(end), start.max(end));
+        // values at the start of the range leave the bar empty
+        if value != start {
+            display.fill_solid(
+                &self.segment(&bar, start, value),
+                self.color.unwrap_or_default(),
+            )?;
+        }
+        let (low, high) = (start.min(value), start.max(value));
+        for (zone, color) in self.zones {
+            let (from, to) = (zone.start.max(low), zone.end.min(high));
+            if from < to {
+                display.fill_solid(&self.segment(&bar, from, to), *color)?;
+            }
+        }
+        if !self.ticks_hidden {
+            axis.draw(display)?;
+        }
+        Ok(display.dirty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gauge::{Gauge, GaugeOrientation};
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb565, prelude::*};
+
+    #[test]
+    fn zone_colors_filled_part() {
+        let zones = [(80..100, Rgb565::RED)];
+        let gauge = Gauge::new(90, 0..100)
+            .unwrap()
+            .set_orientation(GaugeOrientation::Horizontal)
+            .into_drawable(Point::zero(), Point::new(50, 9))
+            .set_color(Rgb565::GREEN)
+            .set_zones(&zones)
+            .set_ticks_hidden();
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        gauge.draw(&mut display).unwrap();
+        assert_eq!(display.get_pixel(Point::new(10, 5)), Some(Rgb565::GREEN));
+        assert_eq!(display.get_pixel(Point::new(42, 5)), Some(Rgb565::RED));
+        assert_eq!(display.get_pixel(Point::new(48, 5)), None);
+    }
+}
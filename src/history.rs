@@ -0,0 +1,362 @@
+//! Long-term history made of per-interval summaries
+//!
+//! Instead of keeping every raw sample, [History] collects minimum, maximum and average of samples
+//! gathered during an interval (e.g. one hour) and keeps only the last N of those summaries.
+//! [DrawableHistory] renders average as a line with min/max band around it,
+//! which is the usual way to show a week of data on a couple hundreds of pixels.
+use core::ops::Range;
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::Point,
+    pixelcolor::PixelColor,
+    primitives::{Line, Primitive, PrimitiveStyle, Rectangle},
+    Drawable,
+};
+use heapless::Deque;
+use itertools::Itertools;
+
+use crate::curve::{value_range, PlotPoint};
+use crate::dirty::DirtyTracker;
+use crate::envelope::{fill_band, EnvelopePoint};
+use crate::placement::IntoDrawableArea;
+use crate::transform::PlotTransform;
+
+/// summary of all samples collected during single interval
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Summary {
+    pub min: i32,
+    pub max: i32,
+    pub avg: i32,
+}
+
+impl Summary {
+    /// summarize given values, None if there are none
+    pub fn of(mut values: impl Iterator<Item = i32>) -> Option<Summary> {
+        let mut accumulator = Accumulator::new(values.next()?);
+        values.for_each(|value| accumulator.add(value));
+        Some(accumulator.summary())
+    }
+}
+
+/// running statistics of the interval that is currently being collected
+#[derive(Clone, Copy)]
+struct Accumulator {
+    min: i32,
+    max: i32,
+    sum: i64,
+    count: i64,
+}
+
+impl Accumulator {
+    fn new(value: i32) -> Accumulator {
+        Accumulator {
+            min: value,
+            max: value,
+            sum: value as i64,
+            count: 1,
+        }
+    }
+
+    fn add(&mut self, value: i32) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.sum += value as i64;
+        self.count += 1;
+    }
+
+    fn summary(&self) -> Summary {
+        Summary {
+            min: self.min,
+            max: self.max,
+            avg: (self.sum / self.count) as i32,
+        }
+    }
+}
+
+/// heapless storage for summaries of last N intervals, oldest summary is dropped when capacity is reached
+pub struct History<const N: usize> {
+    summaries: Deque<Summary, N>,
+    current: Option<Accumulator>,
+}
+
+impl<const N: usize> Default for History<N> {
+    fn default() -> Self {
+        History::new()
+    }
+}
+
+impl<const N: usize> History<N> {
+    /// create empty history
+    pub fn new() -> History<N> {
+        History {
+            summaries: Deque::new(),
+            current: None,
+        }
+    }
+
+    /// feed raw sample into the interval that is currently being collected
+    pub fn add_sample(&mut self, value: i32) {
+        match &mut self.current {
+            None => self.current = Some(Accumulator::new(value)),
+            Some(acc) => acc.add(value),
+        }
+    }
+
+    /// finish current interval and store its summary, intervals without samples are not stored
+    pub fn close_interval(&mut self) {
+        if let Some(acc) = self.current.take() {
+            if self.summaries.is_full() {
+                self.summaries.pop_front();
+            }
+            self.summaries.push_back(acc.summary()).ok();
+        }
+    }
+
+    /// summary of the interval that is still being collected, if any sample arrived
+    pub fn current(&self) -> Option<Summary> {
+        self.current.map(|acc| acc.summary())
+    }
+
+    /// number of stored summaries
+    pub fn len(&self) -> usize {
+        self.summaries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.summaries.is_empty()
+    }
+
+    /// iterate over stored summaries, from the oldest to the newest
+    pub fn summaries(&self) -> impl Iterator<Item = &Summary> + Clone {
+        self.summaries.iter()
+    }
+
+    /// range spanning all stored minimums and maximums, padded when all of them are the same
+    pub fn y_range(&self) -> Range<i32> {
+        value_range(
+            self.summaries
+                .iter()
+                .flat_map(|s| core::iter::once(s.min).chain(core::iter::once(s.max))),
+        )
+    }
+
+    /// create history plot that can be drawn on specific display,
+    /// oldest summary is placed on the left edge and newest on the right one
+    pub fn into_drawable<C>(
+        &self,
+        top_left: Point,
+        bottom_right: Point,
+    ) -> DrawableHistory<'_, C, N>
+    where
+        C: PixelColor + Default,
+    {
+        DrawableHistory {
+            history: self,
+            y_range: self.y_range(),
+            top_left,
+            bottom_right,
+            color: None,
+            band_color: None,
+            thickness: None,
+        }
+    }
+}
+
+impl<'a, C, const N: usize> IntoDrawableArea<DrawableHistory<'a, C, N>> for &'a History<N>
+where
+    C: PixelColor + Default,
+{
+    fn into_drawable_between(
+        self,
+        top_left: Point,
+        bottom_right: Point,
+    ) -> DrawableHistory<'a, C, N> {
+        self.into_drawable(top_left, bottom_right)
+    }
+}
+
+/// Drawable history plot, constructed for specific display
+pub struct DrawableHistory<'a, C, const N: usize>
+where
+    C: PixelColor + Default,
+{
+    history: &'a History<N>,
+    y_range: Range<i32>,
+    top_left: Point,
+    bottom_right: Point,
+    color: Option<C>,
+    band_color: Option<C>,
+    thickness: Option<usize>,
+}
+
+/// builder methods to modify history plot decoration
+impl<'a, C, const N: usize> DrawableHistory<'a, C, N>
+where
+    C: PixelColor + Default,
+{
+    /// set color of the average line
+    pub fn set_color(mut self, color: C) -> DrawableHistory<'a, C, N> {
+        self.color = Some(color);
+        self
+    }
+
+    /// set color of min/max band, if not set, band is not drawn
+    pub fn set_band_color(mut self, color: C) -> DrawableHistory<'a, C, N> {
+        self.band_color = Some(color);
+        self
+    }
+
+    /// set thickness of the average line
+    pub fn set_thickness(mut self, thickness: usize) -> DrawableHistory<'a, C, N> {
+        self.thickness = Some(thickness);
+        self
+    }
+
+    /// override Y range deduced from stored summaries, useful to keep scale stable between redraws
+    pub fn set_y_range(mut self, y_range: Range<i32>) -> DrawableHistory<'a, C, N> {
+        self.y_range = y_range;
+        self
+    }
+
+    /// transform mapping summary index and value onto the display
+    pub fn transform(&self) -> Result<PlotTransform, &'static str> {
+        PlotTransform::new(
+            0..(self.history.len() as i32 - 1),
+            self.y_range.clone(),
+            self.top_left,
+            self.bottom_right,
+        )
+    }
+}
+
+impl<'a, C, const N: usize> Drawable for DrawableHistory<'a, C, N>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    /// bounding box of pixels touched by the plot, for flushing only changed region to the display
+    type Output = Rectangle;
+
+    /// draw min/max band first and the average line on top of it
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<Rectangle, D::Error> {
+        let display = &mut DirtyTracker::new(display);
+        // at least two summaries are needed to span the plot horizontally
+        let transform = match self.transform() {
+            Ok(t) => t,
+            Err(_) => return Ok(display.dirty()),
+        };
+        let scale = |index: usize, value: i32| {
+            transform.to_screen(&PlotPoint {
+                x: index as i32,
+                y: value,
+            })
+        };
+        let color = self.color.unwrap_or_default();
+        let thickness = self.thickness.unwrap_or(2);
+
+        if let Some(band_color) = self.band_color {
+            let band = self.history.summaries().enumerate().map(|(i, s)| {
+                let (min, max) = (scale(i, s.min), scale(i, s.max));
+                EnvelopePoint {
+                    x: min.x,
+                    min: min.y,
+                    max: max.y,
+                }
+            });
+            fill_band(display, band, band_color)?;
+        }
+
+        let style = PrimitiveStyle::with_stroke(color, thickness as u32);
+        self.history
+            .summaries()
+            .enumerate()
+            .map(|(i, s)| scale(i, s.avg))
+            .tuple_windows()
+            .try_for_each(|(prev, point)| -> Result<(), D::Error> {
+                Line::new(prev, point).into_styled(style).draw(display)
+            })?;
+        Ok(display.dirty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::history::{History, Summary};
+    use embedded_graphics::{
+        mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::*, primitives::Rectangle,
+    };
+
+    #[test]
+    fn summary_of_interval() {
+        let mut history: History<4> = History::new();
+        for v in [3, -1, 7, 3].iter() {
+            history.add_sample(*v);
+        }
+        history.close_interval();
+        let s = history.summaries().next().unwrap();
+        assert_eq!((s.min, s.max, s.avg), (-1, 7, 3));
+    }
+
+    #[test]
+    fn oldest_dropped_when_full() {
+        let mut history: History<2> = History::new();
+        for v in 0..3 {
+            history.add_sample(v);
+            history.close_interval();
+        }
+        history.close_interval();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.summaries().next().unwrap().avg, 1);
+        assert_eq!(history.y_range(), 1..2);
+    }
+
+    #[test]
+    fn summary_of_values() {
+        let s = Summary::of([4, 10, -2].iter().copied()).unwrap();
+        assert_eq!((s.min, s.max, s.avg), (-2, 10, 4));
+        assert!(Summary::of(core::iter::empty()).is_none());
+    }
+
+    #[test]
+    fn constant_signal_drawn_in_the_middle() {
+        let mut history: History<4> = History::new();
+        for _ in 0..3 {
+            history.add_sample(5);
+            history.close_interval();
+        }
+        assert_eq!(history.y_range(), 4..6);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        let area = history
+            .into_drawable(Point::new(0, 0), Point::new(20, 20))
+            .set_color(BinaryColor::On)
+            .set_thickness(1)
+            .draw(&mut display)
+            .unwrap();
+        assert_eq!(area, Rectangle::new(Point::new(0, 10), Size::new(21, 1)));
+    }
+
+    #[test]
+    fn band_between_extremes() {
+        let mut history: History<4> = History::new();
+        for _ in 0..3 {
+            history.add_sample(0);
+            history.add_sample(10);
+            history.close_interval();
+        }
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        history
+            .into_drawable(Point::new(0, 0), Point::new(30, 20))
+            .set_color(BinaryColor::Off)
+            .set_band_color(BinaryColor::On)
+            .draw(&mut display)
+            .unwrap();
+        let shaded = |x: i32, y: i32| display.get_pixel(Point::new(x, y)) == Some(BinaryColor::On);
+        assert!((0..=30).all(|x| shaded(x, 0) && shaded(x, 20)));
+        assert_eq!(display.get_pixel(Point::new(15, 21)), None);
+    }
+}
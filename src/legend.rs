@@ -0,0 +1,259 @@
+//! Legend naming the curves, each label preceded by a short sample of the curve line
+//!
+//! Samples are drawn with the color, thickness and dash pattern of the curve, so traces stay
+//! identifiable on monochromatic displays where they differ only by the pattern.
+use embedded_graphics::{
+    mono_font::{ascii::FONT_5X8, MonoTextStyle},
+    prelude::*,
+    primitives::{Line, PrimitiveStyleBuilder, Rectangle},
+    text::{Baseline, Text},
+};
+
+use crate::dirty::DirtyTracker;
+use crate::stats_box::Corner;
+use crate::style::{draw_line_runs, draw_patterned_line, DefaultStyle, LinePattern};
+
+/// Label of a single curve together with the style its line is drawn with
+#[derive(Clone, Copy)]
+pub struct LegendEntry<'a, C>
+where
+    C: PixelColor,
+{
+    label: &'a str,
+    color: C,
+    thickness: Option<usize>,
+    pattern: Option<LinePattern>,
+}
+
+impl<'a, C> LegendEntry<'a, C>
+where
+    C: PixelColor,
+{
+    pub fn new(label: &'a str, color: C) -> LegendEntry<'a, C> {
+        LegendEntry {
+            label,
+            color,
+            thickness: None,
+            pattern: None,
+        }
+    }
+
+    /// set thickness of the line sample, 1 px if not set
+    pub fn set_thickness(mut self, thickness: usize) -> LegendEntry<'a, C> {
+        self.thickness = Some(thickness);
+        self
+    }
+
+    /// set dash pattern of the line sample, solid if not set
+    pub fn set_pattern(mut self, pattern: LinePattern) -> LegendEntry<'a, C> {
+        self.pattern = Some(pattern);
+        self
+    }
+}
+
+impl<'a, C> LegendEntry<'a, C>
+where
+    C: DefaultStyle,
+{
+    /// entry of the n-th curve decorated with [DefaultStyle]
+    pub fn with_default_style(label: &'a str, index: usize) -> LegendEntry<'a, C> {
+        LegendEntry::new(label, C::curve_color(index))
+            .set_thickness(C::curve_thickness())
+            .set_pattern(C::curve_pattern(index))
+    }
+}
+
+/// Display-agnostic legend listing the entries one under another
+#[derive(Clone)]
+pub struct Legend<'a, C, I>
+where
+    C: PixelColor,
+    I: Iterator<Item = LegendEntry<'a, C>> + Clone,
+{
+    entries: I,
+    corner: Option<Corner>,
+}
+
+impl<'a, C, I> Legend<'a, C, I>
+where
+    C: PixelColor + Default,
+    I: Iterator<Item = LegendEntry<'a, C>> + Clone,
+{
+    pub fn new(entries: I) -> Legend<'a, C, I> {
+        Legend {
+            entries,
+            corner: None,
+        }
+    }
+
+    /// set corner of the area where the legend is drawn, top right by default
+    pub fn set_corner(mut self, corner: Corner) -> Legend<'a, C, I> {
+        self.corner = Some(corner);
+        self
+    }
+
+    /// turn legend into drawable object placed in the corner of given area, e.g. the area of plot transform
+    pub fn into_drawable(self, area: Rectangle) -> DrawableLegend<'a, C, I> {
+        DrawableLegend {
+            legend: self,
+            area,
+            color: None,
+            box_color: None,
+            text_style: None,
+            sample_length: None,
+        }
+    }
+}
+
+/// Drawable legend, constructed for specific display
+pub struct DrawableLegend<'a, C, I>
+where
+    C: PixelColor + Default,
+    I: Iterator<Item = LegendEntry<'a, C>> + Clone,
+{
+    legend: Legend<'a, C, I>,
+    area: Rectangle,
+    color: Option<C>,
+    box_color: Option<C>,
+    text_style: Option<MonoTextStyle<'a, C>>,
+    sample_length: Option<u32>,
+}
+
+/// builder methods to modify legend decoration
+impl<'a, C, I> DrawableLegend<'a, C, I>
+where
+    C: PixelColor + Default,
+    I: Iterator<Item = LegendEntry<'a, C>> + Clone,
+{
+    /// set color of the legend border, also used for the labels if text style is not set
+    pub fn set_color(mut self, color: C) -> DrawableLegend<'a, C, I> {
+        self.color = Some(color);
+        self
+    }
+
+    /// set fill color of the legend, if not set, legend is transparent
+    pub fn set_box_color(mut self, color: C) -> DrawableLegend<'a, C, I> {
+        self.box_color = Some(color);
+        self
+    }
+
+    pub fn set_text_style(mut self, style: MonoTextStyle<'a, C>) -> DrawableLegend<'a, C, I> {
+        self.text_style = Some(style);
+        self
+    }
+
+    /// set length of the line samples, 12 px if not set - long enough to show a full dash pattern
+    pub fn set_sample_length(mut self, length: u32) -> DrawableLegend<'a, C, I> {
+        self.sample_length = Some(length);
+        self
+    }
+}
+
+impl<'a, C, I> Drawable for DrawableLegend<'a, C, I>
+where
+    C: PixelColor + Default,
+    I: Iterator<Item = LegendEntry<'a, C>> + Clone,
+{
+    type Color = C;
+    /// bounding box of pixels touched by the legend, for flushing only changed region to the display
+    type Output = Rectangle;
+
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<Rectangle, D::Error> {
+        let display = &mut DirtyTracker::new(display);
+        let color = self.color.unwrap_or_default();
+        let character_style = self
+            .text_style
+            .unwrap_or_else(|| MonoTextStyle::new(&FONT_5X8, color));
+        let sample_length = self.sample_length.unwrap_or(12);
+        let entries = self.legend.entries.clone();
+
+        let row_height = entries
+            .clone()
+            .map(|entry| entry.thickness.unwrap_or(1) as u32)
+            .fold(character_style.font.character_size.height, u32::max)
+            + 1;
+        let (rows, label_width) = entries.clone().fold((0, 0), |(rows, width), entry| {
+            let label = Text::new(entry.label, Point::zero(), character_style);
+            (rows + 1, width.max(label.bounding_box().size.width))
+        });
+        if rows == 0 {
+            return Ok(display.dirty());
+        }
+        // labels start 3 pixels after the end of the samples
+        let size = Size::new(sample_length + 3 + label_width, rows * row_height);
+        let area = self.area;
+        let bottom_right = area.top_left + area.size - Point::new(1, 1);
+        // content is kept 4 pixels away from the area edge, so there is room for the legend border
+        let x = match self.legend.corner.unwrap_or_default() {
+            Corner::TopLeft | Corner::BottomLeft => area.top_left.x + 4,
+            Corner::TopRight | Corner::BottomRight => bottom_right.x - 4 - size.width as i32,
+        };
+        let y = match self.legend.corner.unwrap_or_default() {
+            Corner::TopLeft | Corner::TopRight => area.top_left.y + 4,
+            Corner::BottomLeft | Corner::BottomRight => bottom_right.y - 4 - size.height as i32,
+        };
+
+        let mut box_style = PrimitiveStyleBuilder::new()
+            .stroke_color(color)
+            .stroke_width(1);
+        if let Some(box_color) = self.box_color {
+            box_style = box_style.fill_color(box_color);
+        }
+        Rectangle::new(Point::new(x - 2, y - 2), size + Size::new(4, 4))
+            .into_styled(box_style.build())
+            .draw(display)?;
+
+        for (row, entry) in entries.enumerate() {
+            let center_y = y + (row as u32 * row_height + row_height / 2) as i32;
+            let sample = Line::new(
+                Point::new(x, center_y),
+                Point::new(x + sample_length as i32 - 1, center_y),
+            );
+            let thickness = entry.thickness.unwrap_or(1);
+            let pattern = entry.pattern.unwrap_or_default();
+            if pattern == LinePattern::SOLID {
+                draw_line_runs(display, sample, entry.color, thickness)?;
+            } else {
+                draw_patterned_line(display, sample, pattern, entry.color, thickness, &mut 0)?;
+            }
+            Text::with_baseline(
+                entry.label,
+                Point::new(x + sample_length as i32 + 3, center_y),
+                character_style,
+                Baseline::Middle,
+            )
+            .draw(display)?;
+        }
+        Ok(display.dirty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::legend::{Legend, LegendEntry};
+    use crate::stats_box::Corner;
+    use crate::style::LinePattern;
+    use embedded_graphics::{
+        mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::*, primitives::Rectangle,
+    };
+
+    #[test]
+    fn samples_keep_pattern() {
+        let entries = [
+            LegendEntry::new("a", BinaryColor::On),
+            LegendEntry::new("b", BinaryColor::On).set_pattern(LinePattern::DASHED),
+        ];
+        let legend = Legend::new(entries.iter().copied())
+            .set_corner(Corner::TopLeft)
+            .into_drawable(Rectangle::new(Point::zero(), Size::new(64, 64)))
+            .set_color(BinaryColor::On);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        legend.draw(&mut display).unwrap();
+        // rows are 9 px high, samples start 4 px from the corner
+        let solid = (4..16).filter(|x| display.get_pixel(Point::new(*x, 8)).is_some());
+        let dashed = (4..16).filter(|x| display.get_pixel(Point::new(*x, 17)).is_some());
+        assert_eq!(solid.count(), 12);
+        assert_eq!(dashed.count(), 8);
+    }
+}
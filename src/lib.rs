@@ -42,7 +42,7 @@
 //! let curve = Curve::from_data(data.as_slice());
 //!
 //! let curves = [(curve, RgbColor::YELLOW)];
-//! 
+//!
 //! let plot = SinglePlot::new(
 //!     &curves,
 //!     Scale::RangeFraction(3),
@@ -90,9 +90,8 @@
 //! ## Current limitations and future plans
 //! This is very beginning of the development, however it is functional to the point where single plot can be drawn.
 //!
-//! Main issue for now is that you need to predict on how much space will be occupied by axis ticks,
-//! numbers and titles, points passed to `.into_drawable()` are the boundaries for which curve is scaled.
-//! This will be fixed, please be prepared for it since it might be a breaking change for you.
+//! Points passed to `.into_drawable()` are the boundaries of the whole plot, space occupied by axis ticks,
+//! numbers and titles is reserved automatically and the curves are scaled to what is left.
 //!
 //! #### Main features planned soon:
 //! * Drawing multiple curves that share the same X and Y domains on a single plot (take curves slice instead of single curve)
@@ -112,9 +111,75 @@
 //! If you just want to help but don't have anything specific in mind, please take a look at [issue tracker](https://gitlab.com/mchodzikiewicz/embedded-plots/-/issues) and pick one.
 
 #![no_std]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+#[macro_use]
+extern crate std;
+
+#[cfg(feature = "text")]
+pub mod annotation;
+#[cfg(feature = "async")]
+pub mod async_draw;
 pub mod axis;
+pub mod band;
+pub mod bar_chart;
+pub mod cdf;
+pub mod colormap;
+pub mod cursor;
 pub mod curve;
+pub mod derived;
+pub mod downsample;
+pub mod envelope;
+pub mod error;
+pub mod event_marker;
+#[cfg(feature = "std")]
+pub mod export;
+#[cfg(feature = "fixed")]
+pub mod fixed_point;
+#[cfg(feature = "float")]
+pub mod float_format;
+pub mod gauge;
+#[cfg(feature = "heapless")]
+pub mod history;
+#[cfg(feature = "text")]
+pub mod legend;
+pub mod logic_trace;
+pub mod overlay;
+#[cfg(feature = "heapless")]
+pub mod owned_curve;
+#[cfg(feature = "heapless")]
+pub mod persistence;
+pub mod pie_chart;
+pub mod placement;
+pub mod radial_gauge;
+#[cfg(feature = "text")]
+pub mod readout;
+pub mod ref_line;
+pub mod rotation;
 /// plot that draws single data series
 pub mod single_plot;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+pub mod spectrum;
+#[cfg(feature = "text")]
+pub mod stats_box;
+pub mod style;
+pub mod sweep;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+pub mod theme;
+pub mod timeline;
+pub mod transform;
+pub mod trigger;
+#[cfg(feature = "text")]
+pub mod value_labels;
+#[cfg(feature = "alloc")]
+pub mod vec_curve;
+pub mod viewport;
 
+mod dirty;
+mod edge_axes;
 mod range_conv;
+mod rows;
+mod spline;
@@ -0,0 +1,314 @@
+//! Logic-analyzer style plot of digital signals, e.g. GPIO lines or bus captured on the device
+//!
+//! Each [DigitalChannel] is a bitstream drawn as a square wave in its own lane, with its label on the left.
+//! All channels share one time axis, so edges of different signals line up for protocol debugging.
+use core::{convert::TryFrom, ops::Range};
+
+use embedded_graphics::{mono_font::MonoTextStyle, prelude::*, primitives::Rectangle};
+
+use crate::axis::{Axis, DrawableAxis, Scale};
+use crate::dirty::DirtyTracker;
+use crate::placement::{corners, IntoDrawableArea};
+use crate::range_conv::Scalable;
+use crate::rows::{row_bounds, LabeledRows};
+
+/// Single digital signal, samples are packed into bytes, least significant bit first
+#[derive(Clone, Copy)]
+pub struct DigitalChannel<'a> {
+    label: &'a str,
+    bits: &'a [u8],
+    len: usize,
+}
+
+impl<'a> DigitalChannel<'a> {
+    /// create channel of first `len` samples packed in `bits`
+    pub fn new(label: &'a str, bits: &'a [u8], len: usize) -> DigitalChannel<'a> {
+        DigitalChannel {
+            label,
+            bits,
+            len: len.min(bits.len() * 8),
+        }
+    }
+
+    /// create channel using all bits of given bytes
+    pub fn from_bytes(label: &'a str, bits: &'a [u8]) -> DigitalChannel<'a> {
+        DigitalChannel::new(label, bits, bits.len() * 8)
+    }
+
+    /// name of the channel drawn next to its lane
+    pub fn label(&self) -> &'a str {
+        self.label
+    }
+
+    /// number of samples of the channel
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// level of n-th sample
+    pub fn bit(&self, index: usize) -> Option<bool> {
+        if index < self.len {
+            Some(self.bits[index / 8] & (1 << (index % 8)) != 0)
+        } else {
+            None
+        }
+    }
+}
+
+/// Display-agnostic trace of digital channels sharing one time axis
+#[derive(Clone)]
+pub struct LogicTrace<'a> {
+    channels: &'a [DigitalChannel<'a>],
+    x_start: i32,
+    x_step: i32,
+    scale: Option<Scale<'a>>,
+}
+
+impl<'a> LogicTrace<'a> {
+    /// create trace of the channels, n-th sample is placed at `x_start + n * x_step` on the time axis
+    pub fn new(
+        channels: &'a [DigitalChannel<'a>],
+        x_start: i32,
+        x_step: i32,
+    ) -> Result<LogicTrace<'a>, &'static str> {
+        if x_step <= 0 {
+            Err("Invalid sample step")
+        } else if channels.iter().all(DigitalChannel::is_empty) {
+            Err("No samples")
+        } else if time_end(channels, x_start, x_step).is_none() {
+            Err("Time range overflow")
+        } else {
+            Ok(LogicTrace {
+                channels,
+                x_start,
+                x_step,
+                scale: None,
+            })
+        }
+    }
+
+    /// set density of time axis ticks, [Scale::Auto] if not set
+    pub fn set_scale(mut self, scale: Scale<'a>) -> LogicTrace<'a> {
+        self.scale = Some(scale);
+        self
+    }
+
+    /// time covered by the longest channel
+    pub fn time_range(&self) -> Range<i32> {
+        let end = time_end(self.channels, self.x_start, self.x_step);
+        self.x_start..end.unwrap_or(i32::MAX)
+    }
+
+    /// convert to drawable form for specific display
+    pub fn into_drawable<C>(self, top_left: Point, bottom_right: Point) -> DrawableLogicTrace<'a, C>
+    where
+        C: PixelColor + Default,
+    {
+        DrawableLogicTrace {
+            trace: self,
+            color: None,
+            axis_color: None,
+            text_style: None,
+            top_left,
+            bottom_right,
+        }
+    }
+}
+
+impl<'a, C> IntoDrawableArea<DrawableLogicTrace<'a, C>> for LogicTrace<'a>
+where
+    C: PixelColor + Default,
+{
+    fn into_drawable_between(
+        self,
+        top_left: Point,
+        bottom_right: Point,
+    ) -> DrawableLogicTrace<'a, C> {
+        self.into_drawable(top_left, bottom_right)
+    }
+}
+
+/// time after the last sample of the longest channel, none if it doesn't fit the time axis
+fn time_end(channels: &[DigitalChannel], x_start: i32, x_step: i32) -> Option<i32> {
+    let samples = channels.iter().map(DigitalChannel::len).max().unwrap_or(0);
+    i32::try_from(samples)
+        .ok()?
+        .checked_mul(x_step)?
+        .checked_add(x_start)
+}
+
+/// Drawable logic trace, constructed for specific display
+pub struct DrawableLogicTrace<'a, C>
+where
+    C: PixelColor + Default,
+{
+    trace: LogicTrace<'a>,
+    color: Option<C>,
+    axis_color: Option<C>,
+    text_style: Option<MonoTextStyle<'a, C>>,
+    top_left: Point,
+    bottom_right: Point,
+}
+
+/// builder methods to modify trace decoration
+impl<'a, C> DrawableLogicTrace<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// set color of the waveforms
+    pub fn set_color(mut self, color: C) -> DrawableLogicTrace<'a, C> {
+        self.color = Some(color);
+        self
+    }
+
+    /// if not set, main color will be used
+    pub fn set_axis_color(mut self, color: C) -> DrawableLogicTrace<'a, C> {
+        self.axis_color = Some(color);
+        self
+    }
+
+    /// set style of channel and tick labels, if not set, axis color and 5x8 font are used
+    pub fn set_text_style(mut self, style: MonoTextStyle<'a, C>) -> DrawableLogicTrace<'a, C> {
+        self.text_style = Some(style);
+        self
+    }
+
+    fn labeled_rows(&self) -> LabeledRows<'a, C> {
+        LabeledRows {
+            top_left: self.top_left,
+            bottom_right: self.bottom_right,
+            axis_color: self.axis_color.or(self.color).unwrap_or_default(),
+            text_style: self.text_style,
+        }
+    }
+
+    /// time axis along the bottom edge and area of the lanes
+    fn layout(&self) -> (DrawableAxis<'a, C>, Rectangle) {
+        let axis =
+            Axis::new(self.trace.time_range()).set_scale(self.trace.scale.unwrap_or_default());
+        let labels = self.trace.channels.iter().map(DigitalChannel::label);
+        self.labeled_rows().layout(axis, labels)
+    }
+
+    /// screen Y of high and low level in n-th lane
+    fn levels(&self, lanes: &Rectangle, lane: usize) -> (i32, i32) {
+        row_bounds(lanes, self.trace.channels.len(), lane, 4)
+    }
+
+    /// screen X of n-th sample
+    fn sample_x(&self, lanes: &Rectangle, sample: usize) -> i32 {
+        let (left, right) = corners(lanes);
+        let x = self.trace.x_start + sample as i32 * self.trace.x_step;
+        x.scale_between_ranges(&self.trace.time_range(), &(left.x..right.x))
+    }
+}
+
+impl<'a, C> Dimensions for DrawableLogicTrace<'a, C>
+where
+    C: PixelColor + Default,
+{
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::with_corners(self.top_left, self.bottom_right)
+    }
+}
+
+impl<'a, C> Drawable for DrawableLogicTrace<'a, C>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    /// bounding box of pixels touched by the trace, for flushing only changed region to the display
+    type Output = Rectangle;
+
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<Rectangle, D::Error> {
+        let display = &mut DirtyTracker::new(display);
+        let (axis, lanes) = self.layout();
+        let color = self.color.unwrap_or_default();
+        let run = |display: &mut DirtyTracker<D>, from: Point, to: Point| {
+            display.fill_solid(&Rectangle::with_corners(from, to), color)
+        };
+        for (lane, channel) in self.trace.channels.iter().enumerate() {
+            let (high, low) = self.levels(&lanes, lane);
+            let level = |bit: bool| if bit { high } else { low };
+            for (sample, bit) in (0..channel.len()).filter_map(|i| Some((i, channel.bit(i)?))) {
+                let (start, end) = (
+                    self.sample_x(&lanes, sample),
+                    self.sample_x(&lanes, sample + 1),
+                );
+                run(
+                    display,
+                    Point::new(start, level(bit)),
+                    Point::new(end, level(bit)),
+                )?;
+                // edge towards the level of the next sample
+                if channel.bit(sample + 1) == Some(!bit) {
+                    run(display, Point::new(end, high), Point::new(end, low))?;
+                }
+            }
+        }
+        #[cfg(feature = "text")]
+        self.labeled_rows().draw_labels(
+            display,
+            &lanes,
+            self.trace.channels.iter().map(DigitalChannel::label),
+        )?;
+        axis.draw(display)?;
+        Ok(display.dirty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::logic_trace::{DigitalChannel, LogicTrace};
+    use core::ops::Range;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb565, prelude::*};
+    use test_case::test_case;
+
+    #[test_case(0 => Some(true))]
+    #[test_case(3 => Some(false))]
+    #[test_case(9 => Some(true); "second byte")]
+    #[test_case(10 => None; "past the length")]
+    fn bits(index: usize) -> Option<bool> {
+        DigitalChannel::new("CLK", &[0b0000_0111, 0b0000_0010], 10).bit(index)
+    }
+
+    #[test_case(0, 1 => Ok(0..16); "fits")]
+    #[test_case(i32::MAX - 8, 1 => Err("Time range overflow"); "past the end")]
+    #[test_case(0, i32::MAX / 8 => Err("Time range overflow"); "step too long")]
+    fn time_range(x_start: i32, x_step: i32) -> Result<Range<i32>, &'static str> {
+        let channels = [DigitalChannel::from_bytes("A", &[0xff, 0x0f])];
+        LogicTrace::new(&channels, x_start, x_step).map(|trace| trace.time_range())
+    }
+
+    #[test]
+    fn square_waves_in_lanes() {
+        let channels = [
+            DigitalChannel::from_bytes("A", &[0b0000_1111]),
+            DigitalChannel::from_bytes("B", &[0b1111_0000]),
+        ];
+        let trace = LogicTrace::new(&channels, 0, 1)
+            .unwrap()
+            .into_drawable(Point::zero(), Point::new(63, 63))
+            .set_color(Rgb565::GREEN)
+            .set_axis_color(Rgb565::WHITE);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        trace.draw(&mut display).unwrap();
+        let (_, lanes) = trace.layout();
+        let (first, last) = (trace.sample_x(&lanes, 1), trace.sample_x(&lanes, 6));
+        let (a_high, a_low) = trace.levels(&lanes, 0);
+        let (b_high, b_low) = trace.levels(&lanes, 1);
+        for (x, y) in [
+            (first, a_high),
+            (last, a_low),
+            (first, b_low),
+            (last, b_high),
+        ] {
+            assert_eq!(display.get_pixel(Point::new(x, y)), Some(Rgb565::GREEN));
+        }
+    }
+}
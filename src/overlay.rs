@@ -0,0 +1,177 @@
+//! Custom drawing on top of the plot in data coordinates
+//!
+//! Types implementing [PlotOverlay] are registered with
+//! [add_overlay](crate::single_plot::DrawableSinglePlot::add_overlay) and drawn over the curves with the plot's
+//! transform, so markers, icons or text can be placed at data coordinates without forking the crate.
+//! Overlays draw into [OverlayTarget], a type-erased view of the display accepting any embedded-graphics drawable.
+use embedded_graphics::{pixelcolor::PixelColor, prelude::*, primitives::Rectangle};
+
+use crate::transform::PlotTransform;
+
+/// maximal number of overlays registered with a single plot
+pub const MAX_OVERLAYS: usize = 4;
+
+/// Custom decoration drawn over the plot, e.g. marker of the last alarm
+pub trait PlotOverlay<C>
+where
+    C: PixelColor,
+{
+    /// draw the overlay, `transform` maps data coordinates onto the display
+    fn draw(
+        &self,
+        transform: &PlotTransform,
+        target: &mut OverlayTarget<'_, C>,
+    ) -> Result<(), OverlayError>;
+}
+
+/// closures can be used as overlays directly
+impl<C, F> PlotOverlay<C> for F
+where
+    C: PixelColor,
+    F: Fn(&PlotTransform, &mut OverlayTarget<'_, C>) -> Result<(), OverlayError>,
+{
+    fn draw(
+        &self,
+        transform: &PlotTransform,
+        target: &mut OverlayTarget<'_, C>,
+    ) -> Result<(), OverlayError> {
+        self(transform, target)
+    }
+}
+
+/// Display failed while drawing the overlay, the original error of the display is returned
+/// from drawing of the plot
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct OverlayError(());
+
+/// object safe part of the display, remembering its error
+trait PixelSink<C>
+where
+    C: PixelColor,
+{
+    fn draw_pixels(&mut self, pixels: &mut dyn Iterator<Item = Pixel<C>>) -> bool;
+
+    fn bounding_box(&self) -> Rectangle;
+}
+
+struct Capture<'d, D>
+where
+    D: DrawTarget,
+{
+    display: &'d mut D,
+    error: Option<D::Error>,
+}
+
+impl<'d, D> PixelSink<D::Color> for Capture<'d, D>
+where
+    D: DrawTarget,
+{
+    fn draw_pixels(&mut self, pixels: &mut dyn Iterator<Item = Pixel<D::Color>>) -> bool {
+        match self.display.draw_iter(pixels) {
+            Ok(()) => true,
+            Err(error) => {
+                self.error = Some(error);
+                false
+            }
+        }
+    }
+
+    fn bounding_box(&self) -> Rectangle {
+        self.display.bounding_box()
+    }
+}
+
+/// Display the overlays are drawn on
+pub struct OverlayTarget<'d, C>
+where
+    C: PixelColor,
+{
+    sink: &'d mut dyn PixelSink<C>,
+}
+
+impl<'d, C> Dimensions for OverlayTarget<'d, C>
+where
+    C: PixelColor,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.sink.bounding_box()
+    }
+}
+
+impl<'d, C> DrawTarget for OverlayTarget<'d, C>
+where
+    C: PixelColor,
+{
+    type Color = C;
+    type Error = OverlayError;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), OverlayError>
+    where
+        I: IntoIterator<Item = Pixel<C>>,
+    {
+        if self.sink.draw_pixels(&mut pixels.into_iter()) {
+            Ok(())
+        } else {
+            Err(OverlayError(()))
+        }
+    }
+}
+
+/// draw the overlay on the display, passing through errors of the display
+pub(crate) fn draw_overlay<D>(
+    overlay: &dyn PlotOverlay<D::Color>,
+    transform: &PlotTransform,
+    display: &mut D,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+{
+    let mut capture = Capture {
+        display,
+        error: None,
+    };
+    // overlay error always comes from the display, which is captured
+    overlay
+        .draw(transform, &mut OverlayTarget { sink: &mut capture })
+        .ok();
+    match capture.error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::axis::Scale;
+    use crate::curve::{Curve, PlotPoint};
+    use crate::overlay::{OverlayError, OverlayTarget};
+    use crate::single_plot::SinglePlot;
+    use crate::transform::PlotTransform;
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::{Rgb565, RgbColor},
+        prelude::*,
+    };
+
+    #[test]
+    fn marker_at_data_point() {
+        let points = [PlotPoint { x: 0, y: 0 }, PlotPoint { x: 10, y: 0 }];
+        let curves = [(Curve::from_data(&points), Rgb565::WHITE)];
+        let marker = PlotPoint { x: 5, y: 8 };
+        let overlay = |transform: &PlotTransform, target: &mut OverlayTarget<'_, Rgb565>| {
+            Pixel(transform.to_screen(&marker), Rgb565::RED).draw(target)?;
+            Ok::<(), OverlayError>(())
+        };
+        let plot = SinglePlot::new(&curves, Scale::Auto, Scale::Auto)
+            .unwrap()
+            .set_y_range(0..10)
+            .into_drawable(Point::zero(), Point::new(63, 63))
+            .add_overlay(&overlay)
+            .unwrap();
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        plot.draw(&mut display).unwrap();
+        let screen = plot.data_to_screen(marker).unwrap();
+        assert_eq!(display.get_pixel(screen), Some(Rgb565::RED));
+    }
+}
@@ -0,0 +1,160 @@
+//! Curve that owns its points in heapless storage
+//!
+//! Applications accumulating samples don't have to manage their own arrays and lifetimes,
+//! points are pushed into [OwnedCurve] and borrowed as a regular [Curve] for drawing.
+//! Time series sampled at fixed rate can use [OwnedYCurve] instead, which stores only Y values.
+use core::ops::Range;
+
+use heapless::Vec;
+
+use crate::curve::{Curve, CurveBuffer, PlotPoint, XYData};
+
+/// curve data stored in fixed capacity buffer of N points
+pub type OwnedCurve<const N: usize> = CurveBuffer<Vec<PlotPoint, N>>;
+
+impl<const N: usize> OwnedCurve<N> {
+    /// add point at the end of the curve, point is given back if the curve is full
+    pub fn push(&mut self, point: PlotPoint) -> Result<(), PlotPoint> {
+        self.storage_mut().push(point)
+    }
+
+    /// remove all points, manual ranges are kept
+    pub fn clear(&mut self) {
+        self.storage_mut().clear();
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+}
+
+/// Y values of evenly spaced data stored in fixed capacity buffer of N values,
+/// n-th value is placed at `x = n * dx + x0`
+#[derive(Clone, Default)]
+pub struct OwnedYCurve<const N: usize> {
+    y: Vec<i32, N>,
+    x0: i32,
+    dx: i32,
+    /// manual ranges, if not set, they are deduced from the values
+    ranges: Option<(Range<i32>, Range<i32>)>,
+}
+
+impl<const N: usize> OwnedYCurve<N> {
+    /// create empty curve with ranges automatically deduced from pushed values
+    pub fn new(x0: i32, dx: i32) -> OwnedYCurve<N> {
+        OwnedYCurve {
+            y: Vec::new(),
+            x0,
+            dx,
+            ranges: None,
+        }
+    }
+
+    /// create empty curve with manual ranges
+    pub fn with_ranges(
+        x0: i32,
+        dx: i32,
+        x_range: Range<i32>,
+        y_range: Range<i32>,
+    ) -> OwnedYCurve<N> {
+        OwnedYCurve {
+            ranges: Some((x_range, y_range)),
+            ..OwnedYCurve::new(x0, dx)
+        }
+    }
+
+    /// add value at the end of the curve, value is given back if the curve is full
+    pub fn push(&mut self, y: i32) -> Result<(), i32> {
+        self.y.push(y)
+    }
+
+    /// remove all values, X spacing and manual ranges are kept
+    pub fn clear(&mut self) {
+        self.y.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.y.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.y.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.y.len() == N
+    }
+
+    pub fn values(&self) -> &[i32] {
+        &self.y
+    }
+
+    /// borrow the data as a curve that can be drawn or put on a plot
+    pub fn as_curve(&self) -> Curve<'_> {
+        let data = XYData::Y {
+            y: &self.y,
+            x0: self.x0,
+            dx: self.dx,
+        };
+        match &self.ranges {
+            Some((x_range, y_range)) => Curve::with_xy_data(data, x_range.clone(), y_range.clone()),
+            None => Curve::from_xy_data(data),
+        }
+    }
+}
+
+impl<'a, const N: usize> From<&'a OwnedYCurve<N>> for Curve<'a> {
+    fn from(curve: &'a OwnedYCurve<N>) -> Curve<'a> {
+        curve.as_curve()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::curve::PlotPoint;
+    use crate::owned_curve::{OwnedCurve, OwnedYCurve};
+    use core::ops::Range;
+    use test_case::test_case;
+
+    #[test]
+    fn push_until_full() {
+        let mut curve: OwnedCurve<2> = OwnedCurve::new();
+        assert!(curve.push(PlotPoint { x: 0, y: 5 }).is_ok());
+        assert!(curve.push(PlotPoint { x: 1, y: 7 }).is_ok());
+        assert!(curve.is_full());
+        let rejected = curve.push(PlotPoint { x: 2, y: 9 }).unwrap_err();
+        assert_eq!((rejected.x, rejected.y), (2, 9));
+        assert_eq!(curve.len(), 2);
+        curve.clear();
+        assert!(curve.is_empty());
+    }
+
+    #[test_case(None => (0..1, 5..7); "deduced from points")]
+    #[test_case(Some((0..10, 0..100)) => (0..10, 0..100); "manual")]
+    fn curve_ranges(ranges: Option<(Range<i32>, Range<i32>)>) -> (Range<i32>, Range<i32>) {
+        let mut curve: OwnedCurve<4> = match ranges {
+            Some((x_range, y_range)) => OwnedCurve::with_ranges(x_range, y_range),
+            None => OwnedCurve::new(),
+        };
+        assert!(curve.push(PlotPoint { x: 0, y: 5 }).is_ok());
+        assert!(curve.push(PlotPoint { x: 1, y: 7 }).is_ok());
+        let curve = curve.as_curve();
+        assert_eq!(curve.len(), 2);
+        (curve.x_range, curve.y_range)
+    }
+
+    #[test]
+    fn evenly_spaced_values() {
+        let mut curve: OwnedYCurve<3> = OwnedYCurve::new(10, 5);
+        for y in [1, 4, 2, 8] {
+            let _ = curve.push(y);
+        }
+        assert_eq!(curve.values(), &[1, 4, 2]);
+        let points: heapless::Vec<(i32, i32), 3> = curve
+            .as_curve()
+            .iter()
+            .map(|point| (point.x, point.y))
+            .collect();
+        assert_eq!(points.as_slice(), &[(10, 1), (15, 4), (20, 2)]);
+    }
+}
@@ -0,0 +1,165 @@
+//! Persistence of streaming curves, emulating phosphor of analog oscilloscopes
+//!
+//! [Persistence] keeps last few traces of the curve, drawn from the oldest in the dimmest color
+//! to the newest in the brightest one, so jitter and variation between frames stay visible.
+use embedded_graphics::{
+    pixelcolor::{PixelColor, Rgb888, RgbColor},
+    prelude::*,
+    primitives::Rectangle,
+};
+use heapless::{Deque, Vec};
+
+use crate::curve::PlotPoint;
+use crate::dirty::DirtyTracker;
+use crate::transform::PlotTransform;
+
+/// last T traces of up to N points each, oldest trace is dropped when capacity is reached
+#[derive(Clone, Default)]
+pub struct Persistence<const T: usize, const N: usize> {
+    traces: Deque<Vec<PlotPoint, N>, T>,
+}
+
+impl<const T: usize, const N: usize> Persistence<T, N> {
+    pub fn new() -> Persistence<T, N> {
+        Persistence {
+            traces: Deque::new(),
+        }
+    }
+
+    /// store the newest trace, points beyond capacity of the trace are dropped
+    pub fn push_trace(&mut self, points: impl IntoIterator<Item = PlotPoint>) {
+        if self.traces.is_full() {
+            self.traces.pop_front();
+        }
+        let trace: Vec<PlotPoint, N> = points.into_iter().take(N).collect();
+        // there is always room after dropping the oldest trace
+        self.traces.push_back(trace).ok();
+    }
+
+    /// forget all traces, e.g. after changing the viewport
+    pub fn clear(&mut self) {
+        self.traces.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.traces.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.traces.is_empty()
+    }
+
+    /// stored traces, from the newest to the oldest
+    pub fn traces(&self) -> impl Iterator<Item = &[PlotPoint]> {
+        self.traces.iter().rev().map(|trace| &trace[..])
+    }
+
+    /// turn traces into drawable object mapped by provided transform, `colors` go from the newest trace
+    /// to the oldest one, e.g. from bright to dim - traces older than the palette are not drawn
+    pub fn into_drawable<'a, C>(
+        &'a self,
+        transform: &'a PlotTransform,
+        colors: &'a [C],
+    ) -> DrawablePersistence<'a, C, T, N>
+    where
+        C: PixelColor + Default,
+    {
+        DrawablePersistence {
+            persistence: self,
+            transform,
+            colors,
+            thickness: None,
+        }
+    }
+}
+
+/// color dimmed to given brightness, 255 keeping it unchanged and 0 giving black,
+/// handy for building palette of fading traces on color displays
+pub fn dim<C>(color: C, brightness: u8) -> C
+where
+    C: Into<Rgb888> + From<Rgb888>,
+{
+    let color = color.into();
+    let scale = |component: u8| (component as u16 * brightness as u16 / 255) as u8;
+    C::from(Rgb888::new(
+        scale(color.r()),
+        scale(color.g()),
+        scale(color.b()),
+    ))
+}
+
+/// Drawable traces, constructed for specific display
+pub struct DrawablePersistence<'a, C, const T: usize, const N: usize>
+where
+    C: PixelColor + Default,
+{
+    persistence: &'a Persistence<T, N>,
+    transform: &'a PlotTransform,
+    colors: &'a [C],
+    thickness: Option<usize>,
+}
+
+/// builder methods to modify traces decoration
+impl<'a, C, const T: usize, const N: usize> DrawablePersistence<'a, C, T, N>
+where
+    C: PixelColor + Default,
+{
+    /// set thickness of all traces
+    pub fn set_thickness(mut self, thickness: usize) -> DrawablePersistence<'a, C, T, N> {
+        self.thickness = Some(thickness);
+        self
+    }
+}
+
+impl<'a, C, const T: usize, const N: usize> Drawable for DrawablePersistence<'a, C, T, N>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    /// bounding box of pixels touched by the traces, for flushing only changed region to the display
+    type Output = Rectangle;
+
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<Rectangle, D::Error> {
+        let display = &mut DirtyTracker::new(display);
+        let thickness = self.thickness.unwrap_or(1);
+        let traces = self
+            .persistence
+            .traces()
+            .zip(self.colors)
+            .collect::<Vec<_, T>>();
+        // oldest first, so the newest traces end up on top
+        for (trace, color) in traces.iter().rev() {
+            self.transform
+                .drawable_curve(trace.iter().copied())
+                .set_color(**color)
+                .set_thickness(thickness)
+                .draw(display)?;
+        }
+        Ok(display.dirty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::curve::PlotPoint;
+    use crate::persistence::{dim, Persistence};
+    use embedded_graphics::pixelcolor::{Rgb565, RgbColor};
+
+    #[test]
+    fn oldest_trace_dropped() {
+        let mut persistence: Persistence<2, 4> = Persistence::new();
+        for y in 0..3 {
+            persistence.push_trace((0..6).map(|x| PlotPoint { x, y }));
+        }
+        assert_eq!(persistence.len(), 2);
+        let newest = persistence.traces().next().unwrap();
+        assert_eq!(newest.len(), 4);
+        assert_eq!(newest[0].y, 2);
+    }
+
+    #[test]
+    fn dimmed() {
+        assert_eq!(dim(Rgb565::WHITE, 255), Rgb565::WHITE);
+        assert_eq!(dim(Rgb565::WHITE, 0), Rgb565::BLACK);
+    }
+}
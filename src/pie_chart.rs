@@ -0,0 +1,239 @@
+//! Pie and donut charts, e.g. share of memory or storage used on status dashboards
+#[cfg(feature = "text")]
+use core::fmt::Write;
+
+#[cfg(feature = "text")]
+use embedded_graphics::text::{Alignment, Baseline, Text, TextStyleBuilder};
+use embedded_graphics::{
+    geometry::Angle,
+    mono_font::MonoTextStyle,
+    prelude::*,
+    primitives::{Arc, PrimitiveStyle, PrimitiveStyleBuilder, Rectangle, Sector, StrokeAlignment},
+};
+#[cfg(feature = "text")]
+use heapless::String;
+
+use crate::dirty::DirtyTracker;
+use crate::placement::{area_drawable, IntoDrawableArea};
+
+/// slices narrower than this (in degrees) are not labeled, the label wouldn't fit in
+#[cfg(feature = "text")]
+const MIN_LABELED_SWEEP: i32 = 20;
+
+/// Display-agnostic pie chart
+#[derive(Clone, Copy)]
+pub struct PieChart<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// value and color of each slice, slices are drawn clockwise starting at 12 o'clock
+    slices: &'a [(u32, C)],
+}
+
+impl<'a, C> PieChart<'a, C>
+where
+    C: PixelColor + Default,
+{
+    pub fn new(slices: &'a [(u32, C)]) -> PieChart<'a, C> {
+        PieChart { slices }
+    }
+
+    /// sum of all slice values
+    pub fn total(&self) -> u64 {
+        self.slices.iter().map(|(value, _)| *value as u64).sum()
+    }
+
+    /// start and sweep of each slice in thousandths of a degree, measured clockwise from 3 o'clock.
+    /// Each slice starts exactly where the previous one ends, so rounding leaves no gaps between them.
+    fn sweeps(&self) -> impl Iterator<Item = (i32, i32, &(u32, C))> + '_ {
+        let total = self.total().max(1);
+        let angle = move |sum: u64| -90_000 + (360_000 * sum / total) as i32;
+        self.slices.iter().scan(0u64, move |sum, slice| {
+            let start = angle(*sum);
+            *sum += slice.0 as u64;
+            Some((start, angle(*sum) - start, slice))
+        })
+    }
+
+    /// turn chart into drawable object, the chart is centered in the area between provided points
+    pub fn into_drawable(self, top_left: Point, bottom_right: Point) -> DrawablePieChart<'a, C> {
+        DrawablePieChart {
+            chart: self,
+            inner_radius: None,
+            label_style: None,
+            top_left,
+            bottom_right,
+        }
+    }
+}
+
+impl<'a, C> IntoDrawableArea<DrawablePieChart<'a, C>> for PieChart<'a, C>
+where
+    C: PixelColor + Default,
+{
+    fn into_drawable_between(
+        self,
+        top_left: Point,
+        bottom_right: Point,
+    ) -> DrawablePieChart<'a, C> {
+        self.into_drawable(top_left, bottom_right)
+    }
+}
+
+/// Drawable pie chart, constructed for specific display
+pub struct DrawablePieChart<'a, C>
+where
+    C: PixelColor + Default,
+{
+    chart: PieChart<'a, C>,
+    inner_radius: Option<u32>,
+    label_style: Option<MonoTextStyle<'a, C>>,
+    top_left: Point,
+    bottom_right: Point,
+}
+
+/// builder methods to modify chart decoration
+impl<'a, C> DrawablePieChart<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// leave hole of given radius in the middle, turning pie into donut
+    pub fn set_inner_radius(mut self, radius: u32) -> DrawablePieChart<'a, C> {
+        self.inner_radius = Some(radius);
+        self
+    }
+
+    /// label slices with their percentage of the total, drawn with given style
+    pub fn set_percentage_labels(mut self, style: MonoTextStyle<'a, C>) -> DrawablePieChart<'a, C> {
+        self.label_style = Some(style);
+        self
+    }
+}
+
+area_drawable!(DrawablePieChart);
+
+impl<'a, C> Drawable for DrawablePieChart<'a, C>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    /// bounding box of pixels touched by the chart, for flushing only changed region to the display
+    type Output = Rectangle;
+
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<Rectangle, D::Error> {
+        let display = &mut DirtyTracker::new(display);
+        let total = self.chart.total();
+        if total == 0 {
+            return Ok(display.dirty());
+        }
+        let area = Rectangle::with_corners(self.top_left, self.bottom_right);
+        let center = area.center();
+        let diameter = area.size.width.min(area.size.height);
+        let radius = diameter / 2;
+        let inner_radius = self.inner_radius.unwrap_or(0).min(radius);
+
+        for (start, sweep, (value, color)) in self.chart.sweeps() {
+            if *value == 0 {
+                continue;
+            }
+            let (start, sweep) = (
+                Angle::from_degrees(start as f32 / 1000.0),
+                Angle::from_degrees(sweep as f32 / 1000.0),
+            );
+            if inner_radius == 0 {
+                Sector::with_center(center, diameter, start, sweep)
+                    .into_styled(PrimitiveStyle::with_fill(*color))
+                    .draw(display)?;
+            } else {
+                let style = PrimitiveStyleBuilder::new()
+                    .stroke_color(*color)
+                    .stroke_width(radius - inner_radius)
+                    .stroke_alignment(StrokeAlignment::Inside)
+                    .build();
+                Arc::with_center(center, diameter, start, sweep)
+                    .into_styled(style)
+                    .draw(display)?;
+            }
+        }
+
+        #[cfg(feature = "text")]
+        if let Some(label_style) = self.label_style {
+            let text_style = TextStyleBuilder::new()
+                .alignment(Alignment::Center)
+                .baseline(Baseline::Middle)
+                .build();
+            let label_radius = ((radius + inner_radius) / 2) as i32;
+            for (start, sweep, (value, _)) in self.chart.sweeps() {
+                if sweep < MIN_LABELED_SWEEP * 1000 {
+                    continue;
+                }
+                let mut buf: String<5> = String::new();
+                write!(buf, "{}%", (*value as u64 * 100 + total / 2) / total).ok();
+                let middle = (start + sweep / 2) / 1000;
+                Text::with_text_style(
+                    &buf,
+                    center + polar_offset(middle, label_radius),
+                    label_style,
+                    text_style,
+                )
+                .draw(display)?;
+            }
+        }
+        Ok(display.dirty())
+    }
+}
+
+/// offset of the point at given angle (degrees, clockwise from 3 o'clock) and distance from the center,
+/// using Bhaskara's sine approximation to stay in integer math
+pub(crate) fn polar_offset(degrees: i32, distance: i32) -> Point {
+    Point::new(
+        distance * sine_permille(degrees + 90) / 1000,
+        distance * sine_permille(degrees) / 1000,
+    )
+}
+
+/// sine of the angle in degrees, multiplied by 1000
+fn sine_permille(degrees: i32) -> i32 {
+    let degrees = degrees.rem_euclid(360);
+    let (x, sign) = if degrees < 180 {
+        (degrees, 1)
+    } else {
+        (degrees - 180, -1)
+    };
+    sign * 4000 * x * (180 - x) / (40500 - x * (180 - x))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pie_chart::{polar_offset, sine_permille, PieChart};
+    use embedded_graphics::{pixelcolor::BinaryColor, prelude::Point};
+    use test_case::test_case;
+
+    #[test_case(0 => 0; "zero")]
+    #[test_case(30 => 500; "thirty")]
+    #[test_case(90 => 1000; "right angle")]
+    #[test_case(270 => -1000; "negative")]
+    #[test_case(-90 => -1000; "negative angle")]
+    fn sine(degrees: i32) -> i32 {
+        sine_permille(degrees)
+    }
+
+    #[test_case(-90 => Point::new(0, -10); "top")]
+    #[test_case(0 => Point::new(10, 0); "right")]
+    #[test_case(90 => Point::new(0, 10); "bottom")]
+    fn offset(degrees: i32) -> Point {
+        polar_offset(degrees, 10)
+    }
+
+    #[test]
+    fn slices_tile_full_circle() {
+        let slices = [
+            (1, BinaryColor::On),
+            (1, BinaryColor::Off),
+            (1, BinaryColor::On),
+        ];
+        let chart = PieChart::new(&slices);
+        let sweeps = chart.sweeps().map(|(start, sweep, _)| (start, sweep));
+        assert!(sweeps.eq([(-90_000, 120_000), (30_000, 120_000), (150_000, 120_000)]));
+    }
+}
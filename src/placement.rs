@@ -0,0 +1,118 @@
+//! Conversions between plot bounds given by corner points and embedded-graphics rectangles
+use embedded_graphics::{
+    draw_target::DrawTargetExt, geometry::AnchorPoint, prelude::*, primitives::Rectangle,
+};
+
+/// Widgets laid out in an area of the display, converted to drawable `D` given the area
+/// by its corner points, as a rectangle or by an anchor point
+pub trait IntoDrawableArea<D>: Sized {
+    /// convert to drawable form filling the area between given corner points
+    fn into_drawable_between(self, top_left: Point, bottom_right: Point) -> D;
+
+    /// convert to drawable form filling given area of the display
+    fn into_drawable_in(self, area: Rectangle) -> D {
+        let (top_left, bottom_right) = corners(&area);
+        self.into_drawable_between(top_left, bottom_right)
+    }
+
+    /// convert to drawable form of given size with its anchor point placed at `position`
+    fn into_drawable_at(self, position: Point, anchor: AnchorPoint, size: Size) -> D {
+        self.into_drawable_in(anchored(position, anchor, size))
+    }
+}
+
+/// Drawable filling the area between its corner points, which can be drawn in other area instead
+pub trait DrawIn: Drawable<Output = Rectangle> + Sized {
+    /// same drawable filling the area between given corner points
+    fn moved_to(&self, top_left: Point, bottom_right: Point) -> Self;
+
+    /// draw filling given area of the display, nothing is drawn outside of it - handy when
+    /// the drawable is a widget placed among others. Returns bounding box of touched pixels.
+    fn draw_in<D>(&self, display: &mut D, area: Rectangle) -> Result<Rectangle, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        match area.bottom_right() {
+            Some(bottom_right) => self
+                .moved_to(area.top_left, bottom_right)
+                .draw(&mut display.clipped(&area)),
+            None => Ok(Rectangle::zero()),
+        }
+    }
+}
+
+/// implements [DrawIn] and [Dimensions] covering the whole area, labels included,
+/// for drawables laid out between their `top_left` and `bottom_right` fields
+macro_rules! area_drawable {
+    ($drawable:ident) => {
+        impl<'a, C> embedded_graphics::geometry::Dimensions for $drawable<'a, C>
+        where
+            C: embedded_graphics::pixelcolor::PixelColor + Default,
+        {
+            fn bounding_box(&self) -> embedded_graphics::primitives::Rectangle {
+                embedded_graphics::primitives::Rectangle::with_corners(
+                    self.top_left,
+                    self.bottom_right,
+                )
+            }
+        }
+
+        impl<'a, C> $crate::placement::DrawIn for $drawable<'a, C>
+        where
+            C: embedded_graphics::pixelcolor::PixelColor + Default,
+        {
+            fn moved_to(
+                &self,
+                top_left: embedded_graphics::geometry::Point,
+                bottom_right: embedded_graphics::geometry::Point,
+            ) -> Self {
+                $drawable {
+                    top_left,
+                    bottom_right,
+                    ..*self
+                }
+            }
+        }
+    };
+}
+pub(crate) use area_drawable;
+
+/// corner points of the area, empty area degenerates to its top left point
+pub(crate) fn corners(area: &Rectangle) -> (Point, Point) {
+    (area.top_left, area.bottom_right().unwrap_or(area.top_left))
+}
+
+/// area of given size placed so that its anchor point lands on `position`
+pub(crate) fn anchored(position: Point, anchor: AnchorPoint, size: Size) -> Rectangle {
+    let area = Rectangle::new(Point::zero(), size);
+    area.translate(position - area.anchor_point(anchor))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pie_chart::PieChart;
+    use crate::placement::DrawIn;
+    use embedded_graphics::{
+        mock_display::MockDisplay, pixelcolor::Rgb565, prelude::*, primitives::Rectangle,
+    };
+
+    #[test]
+    fn draw_in_stays_inside_area() {
+        let slices = [(1, Rgb565::RED), (3, Rgb565::GREEN)];
+        let chart = PieChart::new(&slices).into_drawable(Point::zero(), Point::new(63, 63));
+        let area = Rectangle::new(Point::new(10, 20), Size::new(30, 20));
+        assert_eq!(
+            chart
+                .moved_to(area.top_left, Point::new(39, 39))
+                .bounding_box(),
+            area
+        );
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        let drawn = chart.draw_in(&mut display, area).unwrap();
+        assert_eq!(display.affected_area(), drawn);
+        assert_eq!(drawn.intersection(&area), drawn);
+        let empty = Rectangle::new(area.top_left, Size::zero());
+        assert_eq!(chart.draw_in(&mut display, empty), Ok(Rectangle::zero()));
+    }
+}
@@ -0,0 +1,269 @@
+//! Radial gauge with a needle, e.g. speed, pressure or temperature dial
+//!
+//! Value zones in data coordinates are drawn as colored arcs of the dial, e.g. green, yellow and red range,
+//! and values beyond the range light up an over-limit marker, so safety-relevant readings stand out.
+use core::ops::Range;
+
+use embedded_graphics::{
+    geometry::Angle,
+    prelude::*,
+    primitives::{
+        Arc, Circle, Line, PrimitiveStyle, PrimitiveStyleBuilder, Rectangle, StrokeAlignment,
+    },
+};
+
+use crate::dirty::DirtyTracker;
+use crate::pie_chart::polar_offset;
+use crate::placement::IntoDrawableArea;
+use crate::range_conv::Scalable;
+
+/// Display-agnostic dial showing single value within a range
+#[derive(Clone)]
+pub struct RadialGauge {
+    value: i32,
+    range: Range<i32>,
+    sweep: Option<u32>,
+}
+
+impl RadialGauge {
+    /// create gauge of the value, values outside of the range pin the needle at the end of the dial
+    pub fn new(value: i32, range: Range<i32>) -> Result<RadialGauge, &'static str> {
+        if range.start == range.end {
+            Err("Empty gauge range")
+        } else {
+            Ok(RadialGauge {
+                value,
+                range,
+                sweep: None,
+            })
+        }
+    }
+
+    /// set angle covered by the dial in degrees, symmetric around 12 o'clock, 270 if not set
+    pub fn set_sweep(mut self, degrees: u32) -> RadialGauge {
+        self.sweep = Some(degrees.clamp(1, 360));
+        self
+    }
+
+    /// value is outside of the range of the dial
+    pub fn is_over_limit(&self) -> bool {
+        let Range { start, end } = self.range;
+        self.value < start.min(end) || self.value > start.max(end)
+    }
+
+    /// angle of the value on the dial, in degrees clockwise from 3 o'clock
+    fn angle(&self, value: i32) -> i32 {
+        let Range { start, end } = self.range;
+        let sweep = self.sweep.unwrap_or(270) as i32;
+        let value = value.clamp(start.min(end), start.max(end));
+        // dial starts on the left of the gap at the bottom
+        90 + (360 - sweep) / 2 + value.scale_between_ranges(&self.range, &(0..sweep))
+    }
+
+    /// convert to drawable form centered in the area between given points
+    pub fn into_drawable<'a, C>(
+        self,
+        top_left: Point,
+        bottom_right: Point,
+    ) -> DrawableRadialGauge<'a, C>
+    where
+        C: PixelColor + Default,
+    {
+        DrawableRadialGauge {
+            gauge: self,
+            color: None,
+            track_color: None,
+            over_limit_color: None,
+            zones: &[],
+            ring_width: None,
+            top_left,
+            bottom_right,
+        }
+    }
+}
+
+impl<'a, C> IntoDrawableArea<DrawableRadialGauge<'a, C>> for RadialGauge
+where
+    C: PixelColor + Default,
+{
+    fn into_drawable_between(
+        self,
+        top_left: Point,
+        bottom_right: Point,
+    ) -> DrawableRadialGauge<'a, C> {
+        self.into_drawable(top_left, bottom_right)
+    }
+}
+
+/// Drawable radial gauge, constructed for specific display
+pub struct DrawableRadialGauge<'a, C>
+where
+    C: PixelColor + Default,
+{
+    gauge: RadialGauge,
+    color: Option<C>,
+    track_color: Option<C>,
+    over_limit_color: Option<C>,
+    zones: &'a [(Range<i32>, C)],
+    ring_width: Option<u32>,
+    top_left: Point,
+    bottom_right: Point,
+}
+
+/// builder methods to modify gauge decoration
+impl<'a, C> DrawableRadialGauge<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// set color of the needle
+    pub fn set_color(mut self, color: C) -> DrawableRadialGauge<'a, C> {
+        self.color = Some(color);
+        self
+    }
+
+    /// set color of the dial ring outside of the zones, if not set, only zones are drawn
+    pub fn set_track_color(mut self, color: C) -> DrawableRadialGauge<'a, C> {
+        self.track_color = Some(color);
+        self
+    }
+
+    /// set color of the needle and marker shown when the value is outside of the range,
+    /// needle color is used if not set
+    pub fn set_over_limit_color(mut self, color: C) -> DrawableRadialGauge<'a, C> {
+        self.over_limit_color = Some(color);
+        self
+    }
+
+    /// set value zones drawn as colored arcs of the dial, e.g. green, yellow and red range
+    pub fn set_zones(mut self, zones: &'a [(Range<i32>, C)]) -> DrawableRadialGauge<'a, C> {
+        self.zones = zones;
+        self
+    }
+
+    /// set width of the dial ring, fifth of the radius if not set
+    pub fn set_ring_width(mut self, width: u32) -> DrawableRadialGauge<'a, C> {
+        self.ring_width = Some(width);
+        self
+    }
+}
+
+impl<'a, C> Dimensions for DrawableRadialGauge<'a, C>
+where
+    C: PixelColor + Default,
+{
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::with_corners(self.top_left, self.bottom_right)
+    }
+}
+
+impl<'a, C> Drawable for DrawableRadialGauge<'a, C>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    /// bounding box of pixels touched by the gauge, for flushing only changed region to the display
+    type Output = Rectangle;
+
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<Rectangle, D::Error> {
+        let display = &mut DirtyTracker::new(display);
+        let area = self.bounding_box();
+        let center = area.center();
+        let diameter = area.size.width.min(area.size.height);
+        let radius = diameter / 2;
+        let ring_width = self
+            .ring_width
+            .unwrap_or(radius / 5)
+            .clamp(1, radius.max(1));
+        let gauge = &self.gauge;
+
+        let arc = |from: i32, to: i32, color: C| {
+            let style = PrimitiveStyleBuilder::new()
+                .stroke_color(color)
+                .stroke_width(ring_width)
+                .stroke_alignment(StrokeAlignment::Inside)
+                .build();
+            Arc::with_center(
+                center,
+                diameter,
+                Angle::from_degrees(from as f32),
+                Angle::from_degrees((to - from) as f32),
+            )
+            .into_styled(style)
+        };
+        if let Some(color) = self.track_color {
+            arc(
+                gauge.angle(gauge.range.start),
+                gauge.angle(gauge.range.end),
+                color,
+            )
+            .draw(display)?;
+        }
+        for (zone, color) in self.zones {
+            let (from, to) = (gauge.angle(zone.start), gauge.angle(zone.end));
+            if from != to {
+                arc(from, to, *color).draw(display)?;
+            }
+        }
+
+        let color = self.color.unwrap_or_default();
+        let needle_color = if gauge.is_over_limit() {
+            self.over_limit_color.unwrap_or(color)
+        } else {
+            color
+        };
+        let needle = gauge.angle(gauge.value);
+        let needle_length = (radius - ring_width / 2) as i32;
+        Line::new(center, center + polar_offset(needle, needle_length))
+            .into_styled(PrimitiveStyle::with_stroke(needle_color, 2))
+            .draw(display)?;
+        Circle::with_center(center, (ring_width * 2).max(3))
+            .into_styled(PrimitiveStyle::with_fill(color))
+            .draw(display)?;
+        if gauge.is_over_limit() {
+            // marker on the dial at the end the needle is pinned to
+            let marker = center + polar_offset(needle, radius as i32 - ring_width as i32 / 2);
+            Circle::with_center(marker, ring_width + 2)
+                .into_styled(PrimitiveStyle::with_fill(needle_color))
+                .draw(display)?;
+        }
+        Ok(display.dirty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::radial_gauge::RadialGauge;
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::{Rgb565, RgbColor},
+        prelude::*,
+    };
+    use test_case::test_case;
+
+    #[test_case(50 => false; "within range")]
+    #[test_case(100 => false; "at the end")]
+    #[test_case(120 => true; "above")]
+    #[test_case(-1 => true; "below")]
+    fn over_limit(value: i32) -> bool {
+        RadialGauge::new(value, 0..100).unwrap().is_over_limit()
+    }
+
+    #[test]
+    fn zones_and_marker_drawn() {
+        let zones = [(0..60, Rgb565::GREEN), (80..100, Rgb565::RED)];
+        let gauge = RadialGauge::new(150, 0..100)
+            .unwrap()
+            .into_drawable(Point::zero(), Point::new(40, 40))
+            .set_color(Rgb565::WHITE)
+            .set_over_limit_color(Rgb565::MAGENTA)
+            .set_zones(&zones);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        gauge.draw(&mut display).unwrap();
+        let pixels = (0..41).flat_map(|x| (0..41).map(move |y| Point::new(x, y)));
+        let colors = pixels.filter_map(|p| display.get_pixel(p));
+        for expected in [Rgb565::GREEN, Rgb565::RED, Rgb565::MAGENTA] {
+            assert!(colors.clone().any(|color| color == expected));
+        }
+    }
+}
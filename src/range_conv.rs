@@ -1,26 +1,146 @@
-use core::ops::{Add, Div, Mul, Range, Sub};
+use core::convert::TryFrom;
+use core::ops::Range;
 
-pub trait Scalable<T>
-where
-    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
-{
-    fn scale_between_ranges(&self, input_range: &Range<T>, output_range: &Range<T>) -> T;
+/// How scaled values are rounded to whole numbers, e.g. data coordinates to pixels
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rounding {
+    /// round towards zero, fastest, but pixels are placed asymmetrically around zero
+    Truncate,
+    /// round towards negative infinity, consistent placement regardless of the sign
+    Floor,
+    /// round to the nearest pixel, halves going up - the least jitter on slowly moving streaming plots
+    Nearest,
+    /// round to the nearest pixel and carry the rounding error into the next point of the curve,
+    /// like the error term of Bresenham's line. Value between two pixels alternates between them
+    /// in proportion to its position, so slow changes show before crossing a whole pixel.
+    /// Single values are rounded to the nearest pixel.
+    Accumulated,
 }
 
-impl<T> Scalable<T> for T
-where
-    T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
-{
+impl Default for Rounding {
+    fn default() -> Self {
+        Rounding::Truncate
+    }
+}
+
+pub trait Scalable<T> {
+    /// scale value, truncating the result towards zero
     fn scale_between_ranges(&self, input_range: &Range<T>, output_range: &Range<T>) -> T {
-        (*self - input_range.start) * (output_range.end - output_range.start)
-            / (input_range.end - input_range.start)
-            + output_range.start
+        self.scale_between_ranges_rounded(input_range, output_range, Rounding::Truncate)
+    }
+
+    fn scale_between_ranges_rounded(
+        &self,
+        input_range: &Range<T>,
+        output_range: &Range<T>,
+        rounding: Rounding,
+    ) -> T;
+}
+
+macro_rules! rounded_division {
+    ($name:ident, $t:ty) => {
+        /// divide according to the rounding mode, `None` if intermediate results overflow
+        fn $name(numerator: $t, denominator: $t, rounding: Rounding) -> Option<$t> {
+            // keep the denominator positive, so floor is Euclidean division
+            let (numerator, denominator) = if denominator < 0 {
+                (numerator.checked_neg()?, -denominator)
+            } else {
+                (numerator, denominator)
+            };
+            match rounding {
+                Rounding::Truncate => Some(numerator / denominator),
+                Rounding::Floor => Some(numerator.div_euclid(denominator)),
+                Rounding::Nearest | Rounding::Accumulated => Some(
+                    numerator
+                        .checked_mul(2)?
+                        .checked_add(denominator)?
+                        .div_euclid(denominator.checked_mul(2)?),
+                ),
+            }
+        }
+    };
+}
+
+rounded_division!(divide_i64, i64);
+rounded_division!(divide_i128, i128);
+
+/// Scaling is computed with 64-bit intermediates (128-bit when the product doesn't fit),
+/// so large ranges like millisecond timestamps mapped onto screen width don't overflow.
+/// Results outside of `i32` are saturated, empty input range maps everything onto the output start.
+impl Scalable<i32> for i32 {
+    fn scale_between_ranges_rounded(
+        &self,
+        input_range: &Range<i32>,
+        output_range: &Range<i32>,
+        rounding: Rounding,
+    ) -> i32 {
+        let input_span = input_range.end as i64 - input_range.start as i64;
+        if input_span == 0 {
+            return output_range.start;
+        }
+        let offset = *self as i64 - input_range.start as i64;
+        let output_span = output_range.end as i64 - output_range.start as i64;
+        let negative = (offset < 0) ^ (output_span < 0) ^ (input_span < 0);
+        let scaled = offset
+            .checked_mul(output_span)
+            .and_then(|product| divide_i64(product, input_span, rounding))
+            .map(|scaled| scaled as i128)
+            .or_else(|| {
+                divide_i128(
+                    offset as i128 * output_span as i128,
+                    input_span as i128,
+                    rounding,
+                )
+            })
+            .unwrap_or(if negative { i128::MIN } else { i128::MAX });
+        saturate(scaled.saturating_add(output_range.start as i128))
+    }
+}
+
+fn saturate(value: i128) -> i32 {
+    i32::try_from(value).unwrap_or(if value < 0 { i32::MIN } else { i32::MAX })
+}
+
+/// Rounding error carried between successive values scaled with [Rounding::Accumulated]
+#[derive(Clone, Copy, Default)]
+pub(crate) struct ErrorAccumulator {
+    /// error in units of the input span
+    error: i128,
+}
+
+impl ErrorAccumulator {
+    /// scale the value to the nearest whole number, after adding the error left by the previous one
+    pub(crate) fn scale(
+        &mut self,
+        value: i32,
+        input_range: &Range<i32>,
+        output_range: &Range<i32>,
+    ) -> i32 {
+        let input_span = input_range.end as i128 - input_range.start as i128;
+        if input_span == 0 {
+            return output_range.start;
+        }
+        let output_span = output_range.end as i128 - output_range.start as i128;
+        let offset = value as i128 - input_range.start as i128;
+        // keep the span positive, so rounding is the same in both directions
+        let (product, span) = if input_span < 0 {
+            (-offset * output_span, -input_span)
+        } else {
+            (offset * output_span, input_span)
+        };
+        let exact = product + self.error;
+        let scaled = (exact * 2 + span).div_euclid(span * 2);
+        self.error = exact - scaled * span;
+        saturate(scaled + output_range.start as i128)
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::reversed_empty_ranges)]
 mod tests {
-    use crate::range_conv::Scalable;
+    use crate::range_conv::{ErrorAccumulator, Rounding, Scalable};
     use core::ops::Range;
     use test_case::test_case;
 
@@ -29,9 +149,35 @@ mod tests {
     #[test_case(0..20, 0..10, 10 => 5; "half")]
     #[test_case(- 20..20, 0..10, 0 => 5; "negative input range")]
     #[test_case(0..10, - 20..20, 5 => 0; "negative output range")]
-    //#[test_case(0..10, 10..0, 2 => 8; "reversing")]
+    #[test_case(0..10, 10..0, 2 => 8; "reversing")]
+    #[test_case(10..0, 0..10, 2 => 8; "descending input range")]
     #[test_case(- 20..20, 0..20, - 10 => 5; "reversing negative range")]
+    #[test_case(0..i32::MAX, 0..480, i32::MAX / 2 => 239; "timestamps onto screen width")]
+    #[test_case(i32::MIN..i32::MAX, 0..100, 0 => 50; "full range")]
+    #[test_case(0..480, i32::MIN..i32::MAX, 240 => -1; "screen onto full range")]
+    #[test_case(0..1, 0..i32::MAX, 10 => i32::MAX; "saturated high")]
+    #[test_case(0..1, 0..i32::MIN, 10 => i32::MIN; "saturated low")]
+    #[test_case(5..5, 10..20, 7 => 10; "empty input range")]
     fn convert(in_range: Range<i32>, out_range: Range<i32>, val: i32) -> i32 {
         val.scale_between_ranges(&in_range, &out_range)
     }
+
+    #[test_case(Rounding::Truncate, 7 => (3, -3); "truncate")]
+    #[test_case(Rounding::Floor, 7 => (3, -4); "floor")]
+    #[test_case(Rounding::Nearest, 7 => (4, -3); "nearest rounds half up")]
+    #[test_case(Rounding::Nearest, 5 => (3, -2); "nearest ties go up")]
+    fn rounded(rounding: Rounding, val: i32) -> (i32, i32) {
+        (
+            val.scale_between_ranges_rounded(&(0..10), &(0..5), rounding),
+            (-val).scale_between_ranges_rounded(&(0..10), &(0..5), rounding),
+        )
+    }
+
+    #[test]
+    fn accumulated_error_averages_out() {
+        // 3.4 drawn as 3 or 4, summing up to the exact value over whole periods
+        let mut accumulator = ErrorAccumulator::default();
+        let scaled = (0..5).map(|_| accumulator.scale(34, &(0..100), &(0..10)));
+        assert_eq!(scaled.sum::<i32>(), 17);
+    }
 }
@@ -0,0 +1,205 @@
+//! Numeric readout of the latest value of a curve
+//!
+//! Dashboards often show the current value as large text next to the plot of its history.
+//! [Readout] takes the newest sample of the curve, so both stay in sync when redrawn together.
+use core::fmt::Write;
+
+use embedded_graphics::{
+    mono_font::{ascii::FONT_10X20, MonoTextStyle},
+    prelude::*,
+    primitives::Rectangle,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+use heapless::String;
+
+use crate::axis::LabelFormatter;
+use crate::curve::Curve;
+use crate::dirty::DirtyTracker;
+use crate::placement::IntoDrawableArea;
+
+/// Display-agnostic readout of a single value with unit
+#[derive(Clone, Copy)]
+pub struct Readout<'a> {
+    /// value to show, None if the curve has no samples yet
+    value: Option<i32>,
+    unit: Option<&'a str>,
+    formatter: Option<LabelFormatter>,
+}
+
+impl<'a> Readout<'a> {
+    /// readout of the latest sample of the curve, gaps are skipped
+    pub fn new(curve: &Curve<'_>) -> Readout<'a> {
+        Readout::with_value(curve.iter().rev().find(|p| !p.is_gap()).map(|p| p.y))
+    }
+
+    /// readout of any value, None is shown as dashes
+    pub fn with_value(value: Option<i32>) -> Readout<'a> {
+        Readout {
+            value,
+            unit: None,
+            formatter: None,
+        }
+    }
+
+    /// set unit written after the value, e.g. "°C" or " rpm"
+    pub fn set_unit(mut self, unit: &'a str) -> Readout<'a> {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// set function writing the value, e.g. the same formatter as Y axis labels use
+    pub fn set_label_formatter(mut self, formatter: LabelFormatter) -> Readout<'a> {
+        self.formatter = Some(formatter);
+        self
+    }
+
+    pub fn value(&self) -> Option<i32> {
+        self.value
+    }
+
+    /// convert to drawable form placed in the area between given points
+    pub fn into_drawable<C>(self, top_left: Point, bottom_right: Point) -> DrawableReadout<'a, C>
+    where
+        C: PixelColor + Default,
+    {
+        DrawableReadout {
+            readout: self,
+            color: None,
+            box_color: None,
+            text_style: None,
+            alignment: None,
+            top_left,
+            bottom_right,
+        }
+    }
+}
+
+impl<'a, C> IntoDrawableArea<DrawableReadout<'a, C>> for Readout<'a>
+where
+    C: PixelColor + Default,
+{
+    fn into_drawable_between(self, top_left: Point, bottom_right: Point) -> DrawableReadout<'a, C> {
+        self.into_drawable(top_left, bottom_right)
+    }
+}
+
+/// Drawable readout, constructed for specific display
+pub struct DrawableReadout<'a, C>
+where
+    C: PixelColor + Default,
+{
+    readout: Readout<'a>,
+    color: Option<C>,
+    box_color: Option<C>,
+    text_style: Option<MonoTextStyle<'a, C>>,
+    alignment: Option<Alignment>,
+    top_left: Point,
+    bottom_right: Point,
+}
+
+/// builder methods to modify readout decoration
+impl<'a, C> DrawableReadout<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// set color of the text, used if text style is not set
+    pub fn set_color(mut self, color: C) -> DrawableReadout<'a, C> {
+        self.color = Some(color);
+        self
+    }
+
+    /// set color filling the whole area before the text is drawn, so previous value is erased
+    /// when the readout is updated. If not set, the area is not cleared.
+    pub fn set_box_color(mut self, color: C) -> DrawableReadout<'a, C> {
+        self.box_color = Some(color);
+        self
+    }
+
+    /// set style of the text, if not set, color and 10x20 font are used
+    pub fn set_text_style(mut self, style: MonoTextStyle<'a, C>) -> DrawableReadout<'a, C> {
+        self.text_style = Some(style);
+        self
+    }
+
+    /// set horizontal alignment of the text in the area, right aligned by default so digits don't jump
+    pub fn set_alignment(mut self, alignment: Alignment) -> DrawableReadout<'a, C> {
+        self.alignment = Some(alignment);
+        self
+    }
+}
+
+impl<'a, C> Dimensions for DrawableReadout<'a, C>
+where
+    C: PixelColor + Default,
+{
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::with_corners(self.top_left, self.bottom_right)
+    }
+}
+
+impl<'a, C> Drawable for DrawableReadout<'a, C>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    /// bounding box of pixels touched by the readout, for flushing only changed region to the display
+    type Output = Rectangle;
+
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<Rectangle, D::Error> {
+        let display = &mut DirtyTracker::new(display);
+        let area = self.bounding_box();
+        if let Some(color) = self.box_color {
+            display.fill_solid(&area, color)?;
+        }
+        let character_style = self
+            .text_style
+            .unwrap_or_else(|| MonoTextStyle::new(&FONT_10X20, self.color.unwrap_or_default()));
+
+        // text that doesn't fit is cut, partial value is still better than nothing
+        let mut text: String<24> = String::new();
+        match (self.readout.value, self.readout.formatter) {
+            (Some(value), Some(formatter)) => formatter(value, &mut text).ok(),
+            (Some(value), None) => write!(text, "{}", value).ok(),
+            (None, _) => text.push_str("--").ok(),
+        };
+        if let Some(unit) = self.readout.unit {
+            text.push_str(unit).ok();
+        }
+
+        let alignment = self.alignment.unwrap_or(Alignment::Right);
+        let x = match alignment {
+            Alignment::Left => self.top_left.x,
+            Alignment::Center => area.center().x,
+            Alignment::Right => self.bottom_right.x,
+        };
+        let text_style = TextStyleBuilder::new()
+            .alignment(alignment)
+            .baseline(Baseline::Middle)
+            .build();
+        Text::with_text_style(
+            &text,
+            Point::new(x, area.center().y),
+            character_style,
+            text_style,
+        )
+        .draw(display)?;
+        Ok(display.dirty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::curve::{Curve, PlotPoint};
+    use crate::readout::Readout;
+
+    #[test]
+    fn latest_value_skips_gaps() {
+        let points = [
+            PlotPoint { x: 0, y: 4 },
+            PlotPoint { x: 1, y: 7 },
+            PlotPoint::gap(2),
+        ];
+        assert_eq!(Readout::new(&Curve::from_data(&points)).value(), Some(7));
+        assert_eq!(Readout::new(&Curve::from_data(&[])).value(), None);
+    }
+}
@@ -0,0 +1,262 @@
+//! Horizontal and vertical reference lines, e.g. alarm limits and setpoints on dashboards
+#[cfg(feature = "text")]
+use embedded_graphics::{
+    mono_font::ascii::FONT_5X8,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    prelude::*,
+    primitives::{Line, PrimitiveStyle},
+};
+
+use crate::style::{draw_patterned_line, LinePattern};
+use crate::transform::PlotTransform;
+
+/// Position of the reference line in data coordinates
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RefLinePosition {
+    /// line spanning whole plot width at given Y value
+    Horizontal(i32),
+    /// line spanning whole plot height at given X value
+    Vertical(i32),
+}
+
+/// Display-agnostic reference line
+#[derive(Clone, Copy)]
+pub struct RefLine<'a> {
+    position: RefLinePosition,
+    label: Option<&'a str>,
+}
+
+/// builder methods to modify reference line
+impl<'a> RefLine<'a> {
+    /// create line marking given Y value, e.g. alarm limit
+    pub fn horizontal(y_value: i32) -> RefLine<'a> {
+        RefLine {
+            position: RefLinePosition::Horizontal(y_value),
+            label: None,
+        }
+    }
+
+    /// create line marking given X value
+    pub fn vertical(x_value: i32) -> RefLine<'a> {
+        RefLine {
+            position: RefLinePosition::Vertical(x_value),
+            label: None,
+        }
+    }
+
+    /// set label drawn at the end of the line
+    pub fn set_label(mut self, label: &'a str) -> RefLine<'a> {
+        self.label = Some(label);
+        self
+    }
+
+    /// turn reference line into drawable object spanning the area of provided transform,
+    /// line is not drawn if its value is outside of the transform range
+    pub fn into_drawable<C>(self, transform: &PlotTransform) -> DrawableRefLine<'a, C>
+    where
+        C: PixelColor + Default,
+    {
+        let top_left = transform.top_left();
+        let bottom_right = transform.bottom_right();
+        let line = match self.position {
+            RefLinePosition::Horizontal(y) if transform.contains_y(y) => {
+                let y = transform.y_to_screen(y);
+                Some(Line::new(
+                    Point { x: top_left.x, y },
+                    Point {
+                        x: bottom_right.x,
+                        y,
+                    },
+                ))
+            }
+            RefLinePosition::Vertical(x) if transform.contains_x(x) => {
+                let x = transform.x_to_screen(x);
+                Some(Line::new(
+                    Point { x, y: top_left.y },
+                    Point {
+                        x,
+                        y: bottom_right.y,
+                    },
+                ))
+            }
+            _ => None,
+        };
+        DrawableRefLine {
+            #[cfg(feature = "text")]
+            ref_line: self,
+            line,
+            color: None,
+            thickness: None,
+            pattern: None,
+            text_style: None,
+        }
+    }
+}
+
+/// Drawable reference line, constructed for specific display
+pub struct DrawableRefLine<'a, C>
+where
+    C: PixelColor + Default,
+{
+    #[cfg(feature = "text")]
+    ref_line: RefLine<'a>,
+    line: Option<Line>,
+    color: Option<C>,
+    thickness: Option<usize>,
+    pattern: Option<LinePattern>,
+    text_style: Option<MonoTextStyle<'a, C>>,
+}
+
+/// builder methods to modify reference line decoration
+impl<'a, C> DrawableRefLine<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// set line color, also used for the label if text style is not set
+    pub fn set_color(mut self, color: C) -> DrawableRefLine<'a, C> {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn set_thickness(mut self, thickness: usize) -> DrawableRefLine<'a, C> {
+        self.thickness = Some(thickness);
+        self
+    }
+
+    /// set dash pattern, e.g. [LinePattern::DASHED] to tell limits apart from curves
+    pub fn set_pattern(mut self, pattern: LinePattern) -> DrawableRefLine<'a, C> {
+        self.pattern = Some(pattern);
+        self
+    }
+
+    pub fn set_text_style(mut self, style: MonoTextStyle<'a, C>) -> DrawableRefLine<'a, C> {
+        self.text_style = Some(style);
+        self
+    }
+}
+
+impl<'a, C> Drawable for DrawableRefLine<'a, C>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<(), D::Error> {
+        let line = match self.line {
+            Some(line) => line,
+            None => return Ok(()),
+        };
+        let color = self.color.unwrap_or_default();
+        let thickness = self.thickness.unwrap_or(1);
+        let pattern = self.pattern.unwrap_or_default();
+
+        if pattern == LinePattern::SOLID {
+            line.into_styled(PrimitiveStyle::with_stroke(color, thickness as u32))
+                .draw(display)?;
+        } else {
+            draw_patterned_line(display, line, pattern, color, thickness, &mut 0)?;
+        }
+
+        #[cfg(feature = "text")]
+        if let Some(label) = self.ref_line.label {
+            let character_style = self
+                .text_style
+                .unwrap_or_else(|| MonoTextStyle::new(&FONT_5X8, color));
+            // horizontal line is labeled above its right end, vertical one right to its top end
+            let (position, text_style) = match self.ref_line.position {
+                RefLinePosition::Horizontal(_) => (
+                    line.end - Point::new(1, thickness as i32),
+                    TextStyleBuilder::new()
+                        .alignment(Alignment::Right)
+                        .baseline(Baseline::Bottom)
+                        .build(),
+                ),
+                RefLinePosition::Vertical(_) => (
+                    line.start + Point::new(thickness as i32 + 1, 1),
+                    TextStyleBuilder::new()
+                        .alignment(Alignment::Left)
+                        .baseline(Baseline::Top)
+                        .build(),
+                ),
+            };
+            Text::with_text_style(label, position, character_style, text_style).draw(display)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ref_line::RefLine;
+    use crate::style::LinePattern;
+    use crate::transform::PlotTransform;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::*};
+    use test_case::test_case;
+
+    fn transform() -> PlotTransform {
+        PlotTransform::new(0..10, 0..10, Point::new(5, 5), Point::new(45, 45)).unwrap()
+    }
+
+    #[test]
+    fn horizontal_line_spans_plot_width() {
+        let y = transform().y_to_screen(5);
+        let mut display = MockDisplay::new();
+        RefLine::horizontal(5)
+            .into_drawable(&transform())
+            .set_color(BinaryColor::On)
+            .draw(&mut display)
+            .unwrap();
+        assert_eq!(display.get_pixel(Point::new(5, y)), Some(BinaryColor::On));
+        assert_eq!(display.get_pixel(Point::new(45, y)), Some(BinaryColor::On));
+        assert_eq!(display.get_pixel(Point::new(45, y + 1)), None);
+    }
+
+    #[test_case(RefLine::horizontal(11); "above the range")]
+    #[test_case(RefLine::vertical(-1); "left of the range")]
+    fn outside_of_range_not_drawn(line: RefLine) {
+        let mut display = MockDisplay::new();
+        line.into_drawable(&transform())
+            .set_color(BinaryColor::On)
+            .draw(&mut display)
+            .unwrap();
+        assert_eq!(display, MockDisplay::new());
+    }
+
+    #[test]
+    fn dashed_vertical_line() {
+        let x = transform().x_to_screen(2);
+        let mut display = MockDisplay::new();
+        RefLine::vertical(2)
+            .into_drawable(&transform())
+            .set_color(BinaryColor::On)
+            .set_pattern(LinePattern::DASHED)
+            .draw(&mut display)
+            .unwrap();
+        let column = (5..=45).map(|y| display.get_pixel(Point::new(x, y)));
+        let drawn = column.clone().filter(Option::is_some).count();
+        assert!(drawn > 10 && drawn < 31);
+        assert!(column.clone().any(|pixel| pixel.is_none()));
+    }
+
+    #[test]
+    #[cfg(feature = "text")]
+    fn label_above_right_end() {
+        let y = transform().y_to_screen(5);
+        let mut display = MockDisplay::new();
+        RefLine::horizontal(5)
+            .set_label("max")
+            .into_drawable(&transform())
+            .set_color(BinaryColor::On)
+            .draw(&mut display)
+            .unwrap();
+        let area = display.affected_area();
+        assert_eq!(area.top_left.y + area.size.height as i32 - 1, y);
+        assert!(area.top_left.y < y - 4);
+    }
+}
@@ -0,0 +1,154 @@
+//! Drawing on rotated displays without intermediate framebuffer
+//!
+//! [Rotated] wraps the display and rotates every pixel on the fly, so a landscape plot can be drawn
+//! on portrait-mounted display. Rectangle fills stay rectangle fills after rotation,
+//! so displays optimizing them don't lose performance.
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, Point, Size},
+    primitives::Rectangle,
+    Pixel,
+};
+
+/// Clockwise rotation of the drawn content
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rotation {
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+impl Default for Rotation {
+    fn default() -> Self {
+        Rotation::Deg0
+    }
+}
+
+/// Draw target drawing rotated content on the wrapped display,
+/// for 90° and 270° rotation width and height of the display are swapped
+pub struct Rotated<'a, D> {
+    display: &'a mut D,
+    rotation: Rotation,
+}
+
+impl<'a, D> Rotated<'a, D>
+where
+    D: DrawTarget,
+{
+    pub fn new(display: &'a mut D, rotation: Rotation) -> Rotated<'a, D> {
+        Rotated { display, rotation }
+    }
+
+    /// map point from rotated coordinates onto the wrapped display
+    pub fn to_display(&self, point: Point) -> Point {
+        rotate(self.rotation, &self.display.bounding_box(), point)
+    }
+
+    /// map rectangle from rotated coordinates onto the wrapped display
+    pub fn area_to_display(&self, area: &Rectangle) -> Rectangle {
+        rotate_area(self.rotation, &self.display.bounding_box(), area)
+    }
+}
+
+/// map rectangle from rotated coordinates onto display with given bounding box
+pub(crate) fn rotate_area(rotation: Rotation, display: &Rectangle, area: &Rectangle) -> Rectangle {
+    match area.bottom_right() {
+        Some(bottom_right) => Rectangle::with_corners(
+            rotate(rotation, display, area.top_left),
+            rotate(rotation, display, bottom_right),
+        ),
+        None => Rectangle::new(rotate(rotation, display, area.top_left), Size::zero()),
+    }
+}
+
+/// map point from rotated coordinates onto display with given bounding box
+fn rotate(rotation: Rotation, area: &Rectangle, point: Point) -> Point {
+    let origin = area.top_left;
+    let (width, height) = (area.size.width as i32, area.size.height as i32);
+    let Point { x, y } = point - origin;
+    origin
+        + match rotation {
+            Rotation::Deg0 => Point::new(x, y),
+            Rotation::Deg90 => Point::new(width - 1 - y, x),
+            Rotation::Deg180 => Point::new(width - 1 - x, height - 1 - y),
+            Rotation::Deg270 => Point::new(y, height - 1 - x),
+        }
+}
+
+impl<'a, D> Dimensions for Rotated<'a, D>
+where
+    D: DrawTarget,
+{
+    fn bounding_box(&self) -> Rectangle {
+        let area = self.display.bounding_box();
+        match self.rotation {
+            Rotation::Deg0 | Rotation::Deg180 => area,
+            Rotation::Deg90 | Rotation::Deg270 => {
+                Rectangle::new(area.top_left, Size::new(area.size.height, area.size.width))
+            }
+        }
+    }
+}
+
+impl<'a, D> DrawTarget for Rotated<'a, D>
+where
+    D: DrawTarget,
+{
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let area = self.display.bounding_box();
+        let rotation = self.rotation;
+        self.display.draw_iter(
+            pixels
+                .into_iter()
+                .map(|Pixel(point, color)| Pixel(rotate(rotation, &area, point), color)),
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.area_to_display(area);
+        self.display.fill_solid(&area, color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rotation::{Rotated, Rotation};
+    use embedded_graphics::{
+        mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::*, primitives::Rectangle,
+    };
+    use test_case::test_case;
+
+    #[test_case(Rotation::Deg0 => Point::new(1, 2); "none")]
+    #[test_case(Rotation::Deg90 => Point::new(61, 1); "quarter")]
+    #[test_case(Rotation::Deg180 => Point::new(62, 61); "half")]
+    #[test_case(Rotation::Deg270 => Point::new(2, 62); "three quarters")]
+    fn point(rotation: Rotation) -> Point {
+        let mut display = MockDisplay::<BinaryColor>::new();
+        Rotated::new(&mut display, rotation).to_display(Point::new(1, 2))
+    }
+
+    #[test]
+    fn fill_rotated() {
+        let mut display = MockDisplay::<BinaryColor>::new();
+        let mut rotated = Rotated::new(&mut display, Rotation::Deg90);
+        rotated
+            .fill_solid(
+                &Rectangle::new(Point::new(0, 0), Size::new(3, 1)),
+                BinaryColor::On,
+            )
+            .unwrap();
+        assert_eq!(
+            display.affected_area(),
+            Rectangle::new(Point::new(63, 0), Size::new(1, 3))
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Rows labeled on the left above a shared time axis, layout of logic traces and timelines
+#[cfg(feature = "text")]
+use embedded_graphics::{
+    mono_font::ascii::FONT_5X8,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+use embedded_graphics::{mono_font::MonoTextStyle, prelude::*, primitives::Rectangle};
+
+use crate::axis::{Axis, DrawableAxis, Placement};
+
+/// Area split into equal rows with labels on the left and time axis along the bottom edge
+pub(crate) struct LabeledRows<'a, C>
+where
+    C: PixelColor,
+{
+    pub top_left: Point,
+    pub bottom_right: Point,
+    pub axis_color: C,
+    pub text_style: Option<MonoTextStyle<'a, C>>,
+}
+
+impl<'a, C> LabeledRows<'a, C>
+where
+    C: PixelColor + Default,
+{
+    #[cfg(feature = "text")]
+    fn text_style(&self) -> MonoTextStyle<'a, C> {
+        self.text_style
+            .unwrap_or_else(|| MonoTextStyle::new(&FONT_5X8, self.axis_color))
+    }
+
+    /// space for the labels on the left of the rows
+    #[cfg(feature = "text")]
+    fn label_width<'l>(&self, labels: impl Iterator<Item = &'l str>) -> i32 {
+        let font = self.text_style().font;
+        match labels.map(|label| label.chars().count()).max() {
+            Some(chars) if chars > 0 => {
+                let advance = font.character_size.width + font.character_spacing;
+                (chars as u32 * advance) as i32 + 2
+            }
+            _ => 0,
+        }
+    }
+
+    #[cfg(not(feature = "text"))]
+    fn label_width<'l>(&self, _labels: impl Iterator<Item = &'l str>) -> i32 {
+        0
+    }
+
+    /// time axis along the bottom edge and area of the rows right of the labels
+    pub fn layout<'l>(
+        &self,
+        axis: Axis<'a>,
+        labels: impl Iterator<Item = &'l str>,
+    ) -> (DrawableAxis<'a, C>, Rectangle) {
+        let left = self.top_left.x + self.label_width(labels);
+        let styled = |y: i32| {
+            let axis = axis
+                .clone()
+                .into_drawable_axis(Placement::X {
+                    x1: left,
+                    x2: self.bottom_right.x,
+                    y,
+                })
+                .set_color(self.axis_color);
+            // axis falls back to its color and 5x8 font on its own
+            match self.text_style {
+                Some(text_style) => axis.set_text_style(text_style),
+                None => axis,
+            }
+        };
+        let extent = styled(self.bottom_right.y).label_extent() as i32;
+        let axis_y = self.bottom_right.y - extent;
+        let rows = Rectangle::with_corners(
+            Point::new(left, self.top_left.y),
+            Point::new(self.bottom_right.x, axis_y - 1),
+        );
+        (styled(axis_y), rows)
+    }
+
+    /// draw the labels left of their rows, centered vertically
+    #[cfg(feature = "text")]
+    pub fn draw_labels<'l, D>(
+        &self,
+        display: &mut D,
+        rows: &Rectangle,
+        labels: impl ExactSizeIterator<Item = &'l str>,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let count = labels.len();
+        for (index, label) in labels.enumerate() {
+            let (top, height) = row_span(rows, count, index);
+            Text::with_text_style(
+                label,
+                Point::new(self.top_left.x, top + (height - 1) / 2),
+                self.text_style(),
+                TextStyleBuilder::new()
+                    .alignment(Alignment::Left)
+                    .baseline(Baseline::Middle)
+                    .build(),
+            )
+            .draw(display)?;
+        }
+        Ok(())
+    }
+}
+
+/// screen Y of the top and height of n-th of `count` rows
+fn row_span(rows: &Rectangle, count: usize, index: usize) -> (i32, i32) {
+    let height = rows.size.height as i32 / count as i32;
+    (rows.top_left.y + height * index as i32, height)
+}
+
+/// screen Y of the top and bottom of n-th of `count` rows, inset by `1/inset` of the row height
+pub(crate) fn row_bounds(rows: &Rectangle, count: usize, index: usize, inset: i32) -> (i32, i32) {
+    let (top, height) = row_span(rows, count, index);
+    let margin = height / inset;
+    (top + margin, top + height - 1 - margin)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "text")]
+    use crate::rows::LabeledRows;
+    #[cfg(feature = "text")]
+    use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+    #[test]
+    #[cfg(feature = "text")]
+    fn label_width_counts_characters() {
+        let rows = LabeledRows {
+            top_left: Point::zero(),
+            bottom_right: Point::new(63, 63),
+            axis_color: BinaryColor::On,
+            text_style: None,
+        };
+        let width = |label: &str| rows.label_width(core::iter::once(label));
+        assert_eq!(width("°C"), width("ab"));
+        assert_eq!(width(""), 0);
+    }
+}
@@ -1,9 +1,40 @@
-use crate::axis::{Axis, Placement, Scale};
-use crate::curve::Curve;
-use embedded_graphics::mono_font::MonoTextStyleBuilder;
+use core::iter::successors;
+use core::ops::Range;
+use core::task::Poll;
+
+use crate::axis::{
+    Axis, DrawableAxis, LabelFormatter, Placement, Scale, TickDirection, TickLabelPlacement,
+    TickLabelSide, TitleOrientation,
+};
+use crate::band::Band;
+use crate::curve::{Curve, PlotPoint, RenderMode};
+use crate::dirty::DirtyTracker;
+use crate::envelope::{Envelope, EnvelopePoint};
+use crate::error::PlotError;
+#[cfg(feature = "text")]
+use crate::legend::{Legend, LegendEntry};
+use crate::overlay::{draw_overlay, PlotOverlay, MAX_OVERLAYS};
+use crate::placement::{DrawIn, IntoDrawableArea};
+use crate::rotation::{rotate_area, Rotated, Rotation};
+#[cfg(feature = "text")]
+use crate::stats_box::Corner;
+use crate::style::{DefaultStyle, LinePattern};
+use crate::theme::PlotTheme;
+use crate::transform::{PlotTransform, Rounding};
+use crate::viewport::Viewport;
+use embedded_graphics::mono_font::MonoFont;
+#[cfg(feature = "text")]
+use embedded_graphics::mono_font::{ascii::FONT_5X8, MonoTextStyleBuilder};
 use embedded_graphics::{
-    draw_target::DrawTarget, pixelcolor::PixelColor, prelude::Point, Drawable,
+    draw_target::{DrawTarget, DrawTargetExt},
+    geometry::Dimensions,
+    pixelcolor::PixelColor,
+    prelude::Point,
+    primitives::{Primitive, PrimitiveStyle, Rectangle},
+    transform::Transform,
+    Drawable,
 };
+use itertools::Itertools;
 /// Display agnostic single curve plot object
 #[derive(Clone, Copy)]
 pub struct SinglePlot<'a, C>
@@ -13,10 +44,56 @@ where
     /// curve to be drawn on the plot
     curves: &'a [(Curve<'a>, C)],
     /// range of X axis on which curve will be drawn
-    x_scale: Scale,
+    x_scale: Scale<'a>,
     /// range of Y axis on which curve will be drawn
-    y_scale: Scale,
+    y_scale: Scale<'a>,
+    /// visible part of the data, if not set, union of ranges of all curves is shown
+    viewport: Option<Viewport>,
+    /// explicit X range of the plot, overriding ranges of the curves
+    x_range: Option<(i32, i32)>,
+    /// explicit Y range of the plot, overriding ranges of the curves
+    y_range: Option<(i32, i32)>,
+    /// automatic Y range is extended to include zero
+    y_includes_zero: bool,
+    /// automatic ranges are extended by given percentage of their span on both ends
+    x_range_padding: Option<u32>,
+    y_range_padding: Option<u32>,
+    /// X tick labels are relative to this value, e.g. "seconds ago" of rolling time window
+    x_label_origin: Option<i32>,
+}
+
+/// union of the ranges, orientation of the first range is kept so inverted axes stay inverted
+fn range_union(mut ranges: impl Iterator<Item = Range<i32>>) -> Option<Range<i32>> {
+    let first = ranges.next()?;
+    let bounds = |range: &Range<i32>| (range.start.min(range.end), range.start.max(range.end));
+    let (low, high) = ranges.fold(bounds(&first), |(low, high), range| {
+        let (start, end) = bounds(&range);
+        (low.min(start), high.max(end))
+    });
+    if first.start > first.end {
+        Some(high..low)
+    } else {
+        Some(low..high)
+    }
+}
+
+/// range extended by given percentage of its span on both ends, keeping its orientation
+fn pad_range(range: Range<i32>, percent: u32) -> Range<i32> {
+    let (start, end) = (range.start as i64, range.end as i64);
+    let margin = (end - start) * percent as i64 / 100;
+    let clamp = |value: i64| value.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+    clamp(start - margin)..clamp(end + margin)
+}
+
+/// range extended to include zero, keeping its orientation
+fn include_zero(range: Range<i32>) -> Range<i32> {
+    if range.start > range.end {
+        range.start.max(0)..range.end.min(0)
+    } else {
+        range.start.min(0)..range.end.max(0)
+    }
 }
+
 impl<'a, C> SinglePlot<'a, C>
 where
     C: PixelColor + Default,
@@ -24,9 +101,9 @@ where
     /// create SinglePlot object with manual range
     pub fn new(
         curves: &'a [(Curve<'a>, C)],
-        x_scale: Scale,
-        y_scale: Scale,
-    ) -> Result<SinglePlot<C>, &str> {
+        x_scale: Scale<'a>,
+        y_scale: Scale<'a>,
+    ) -> Result<SinglePlot<'a, C>, &'static str> {
         if curves.len() < 1 {
             Err("No curves provided")
         } else {
@@ -34,9 +111,106 @@ where
                 curves,
                 x_scale,
                 y_scale,
+                viewport: None,
+                x_range: None,
+                y_range: None,
+                y_includes_zero: false,
+                x_range_padding: None,
+                y_range_padding: None,
+                x_label_origin: None,
             })
         }
     }
+
+    /// set visible part of the data, e.g. to zoom or pan through long capture without rebuilding curves.
+    /// Curves are clipped to the plot area when viewport is set.
+    pub fn set_viewport(mut self, viewport: Viewport) -> SinglePlot<'a, C> {
+        self.viewport = Some(viewport);
+        self
+    }
+
+    /// set X range shown on the plot regardless of the data, e.g. fixed time window.
+    /// Curves are clipped to the plot area when the range is set.
+    pub fn set_x_range(mut self, range: Range<i32>) -> SinglePlot<'a, C> {
+        self.x_range = Some((range.start, range.end));
+        self
+    }
+
+    /// show rolling time window of given length ending at `now` and label X axis relative to it,
+    /// e.g. -60..0 for the last minute of samples timestamped in seconds. Ticks and labels stay in place
+    /// as the window slides, instead of showing ever-growing absolute timestamps. Curves are clipped.
+    pub fn set_rolling_window(mut self, now: i32, length: u32) -> SinglePlot<'a, C> {
+        let start = now as i64 - length as i64;
+        self.x_range = Some((start.max(i32::MIN as i64) as i32, now));
+        self.x_label_origin = Some(now);
+        self
+    }
+
+    /// set Y range shown on the plot regardless of the data, e.g. always 0-100 %.
+    /// Curves are clipped to the plot area when the range is set.
+    pub fn set_y_range(mut self, range: Range<i32>) -> SinglePlot<'a, C> {
+        self.y_range = Some((range.start, range.end));
+        self
+    }
+
+    /// always show zero on automatic Y range, e.g. so magnitudes aren't exaggerated
+    pub fn set_y_includes_zero(mut self) -> SinglePlot<'a, C> {
+        self.y_includes_zero = true;
+        self
+    }
+
+    /// extend automatic X range by given percentage of its span on both ends
+    pub fn set_x_range_padding(mut self, percent: u32) -> SinglePlot<'a, C> {
+        self.x_range_padding = Some(percent);
+        self
+    }
+
+    /// extend automatic Y range by given percentage of its span on both ends,
+    /// so curves don't touch the top and bottom of the plot. Zero is included after padding.
+    pub fn set_y_range_padding(mut self, percent: u32) -> SinglePlot<'a, C> {
+        self.y_range_padding = Some(percent);
+        self
+    }
+
+    /// currently visible part of the data - viewport if set, otherwise explicit ranges
+    /// or union of ranges of all curves with data, padded and extended to zero if requested
+    pub fn viewport(&self) -> Viewport {
+        self.viewport.unwrap_or_else(|| {
+            // curves without data are skipped, unless there is nothing else
+            let any_data = self.curves.iter().any(|(curve, _)| !curve.is_empty());
+            let curves = || {
+                let curves = self.curves.iter();
+                curves.filter(move |(curve, _)| !any_data || !curve.is_empty())
+            };
+            let x_range = match self.x_range {
+                Some((start, end)) => start..end,
+                None => {
+                    let range = range_union(curves().map(|(curve, _)| curve.x_range.clone()))
+                        .unwrap_or_default();
+                    pad_range(range, self.x_range_padding.unwrap_or(0))
+                }
+            };
+            let y_range = match self.y_range {
+                Some((start, end)) => start..end,
+                None => {
+                    let range = range_union(curves().map(|(curve, _)| curve.y_range.clone()))
+                        .unwrap_or_default();
+                    let range = pad_range(range, self.y_range_padding.unwrap_or(0));
+                    if self.y_includes_zero {
+                        include_zero(range)
+                    } else {
+                        range
+                    }
+                }
+            };
+            Viewport::new(x_range, y_range)
+        })
+    }
+
+    /// curves may reach outside of the shown ranges and have to be clipped
+    fn clips_curves(&self) -> bool {
+        self.viewport.is_some() || self.x_range.is_some() || self.y_range.is_some()
+    }
     //TODO: add auto range plot constructor
     /// convert to drawable form for specific display
     pub fn into_drawable(self, top_left: Point, bottom_right: Point) -> DrawableSinglePlot<'a, C> {
@@ -47,11 +221,73 @@ where
             axis_color: None,
             thickness: None,
             axis_thickness: None,
+            tick_size: None,
+            tick_direction: None,
+            tick_thickness: None,
+            curve_pattern: None,
+            render_mode: None,
+            bands: &[],
+            envelope: None,
+            tick_font: None,
+            title_font: None,
+            y_title_orientation: None,
+            y_tick_label_side: None,
+            x_tick_label_placement: None,
+            background_color: None,
+            plot_area_color: None,
+            frame_color: None,
+            frame_thickness: None,
+            x_label_formatter: None,
+            y_label_formatter: None,
+            axis_arrowhead: None,
+            axes_at_origin: false,
+            equal_aspect: false,
+            x_categories: None,
+            smoothing: None,
+            marker_every: None,
+            threshold: None,
+            segment_color: None,
+            rotation: None,
+            compact_below: None,
+            rounding: None,
+            x_label: Some("X"),
+            y_label: Some("Y"),
+            x_axis_hidden: false,
+            y_axis_hidden: false,
+            ticks_hidden: false,
+            end_marks: false,
+            tick_labels_hidden: false,
+            grid: false,
+            grid_color: None,
+            grid_thickness: None,
+            grid_pattern: None,
+            #[cfg(feature = "text")]
+            legend: None,
+            #[cfg(feature = "text")]
+            legend_corner: None,
+            margins: None,
+            padding: None,
+            palette: None,
+            priorities: None,
+            overlays: [None; MAX_OVERLAYS],
             top_left,
             bottom_right,
         }
     }
 }
+
+impl<'a, C> IntoDrawableArea<DrawableSinglePlot<'a, C>> for SinglePlot<'a, C>
+where
+    C: PixelColor + Default,
+{
+    fn into_drawable_between(
+        self,
+        top_left: Point,
+        bottom_right: Point,
+    ) -> DrawableSinglePlot<'a, C> {
+        self.into_drawable(top_left, bottom_right)
+    }
+}
 /// Drawable single plot object, constructed for specific display
 pub struct DrawableSinglePlot<'a, C>
 where
@@ -63,6 +299,56 @@ where
     axis_color: Option<C>,
     thickness: Option<usize>,
     axis_thickness: Option<usize>,
+    tick_size: Option<usize>,
+    tick_direction: Option<TickDirection>,
+    tick_thickness: Option<usize>,
+    curve_pattern: Option<fn(usize) -> LinePattern>,
+    render_mode: Option<RenderMode>,
+    bands: &'a [(Band, C)],
+    envelope: Option<(&'a [EnvelopePoint], C)>,
+    tick_font: Option<&'a MonoFont<'a>>,
+    title_font: Option<&'a MonoFont<'a>>,
+    y_title_orientation: Option<TitleOrientation>,
+    y_tick_label_side: Option<TickLabelSide>,
+    x_tick_label_placement: Option<TickLabelPlacement>,
+    background_color: Option<C>,
+    plot_area_color: Option<C>,
+    frame_color: Option<C>,
+    frame_thickness: Option<usize>,
+    x_label_formatter: Option<LabelFormatter>,
+    y_label_formatter: Option<LabelFormatter>,
+    axis_arrowhead: Option<u32>,
+    axes_at_origin: bool,
+    equal_aspect: bool,
+    x_categories: Option<&'a [&'a str]>,
+    smoothing: Option<u32>,
+    marker_every: Option<usize>,
+    threshold: Option<(i32, C)>,
+    segment_color: Option<fn(usize, &PlotPoint, &PlotPoint) -> C>,
+    rotation: Option<(Rotation, Rectangle)>,
+    compact_below: Option<u32>,
+    rounding: Option<Rounding>,
+    x_label: Option<&'a str>,
+    y_label: Option<&'a str>,
+    x_axis_hidden: bool,
+    y_axis_hidden: bool,
+    ticks_hidden: bool,
+    end_marks: bool,
+    tick_labels_hidden: bool,
+    grid: bool,
+    grid_color: Option<C>,
+    grid_thickness: Option<usize>,
+    grid_pattern: Option<LinePattern>,
+    #[cfg(feature = "text")]
+    legend: Option<&'a [&'a str]>,
+    #[cfg(feature = "text")]
+    legend_corner: Option<Corner>,
+    /// space left empty at the top, right, bottom and left edge of the plot
+    margins: Option<(u32, u32, u32, u32)>,
+    padding: Option<u32>,
+    palette: Option<&'a [C]>,
+    priorities: Option<&'a [i32]>,
+    overlays: [Option<&'a dyn PlotOverlay<C>>; MAX_OVERLAYS],
     top_left: Point,
     bottom_right: Point,
 }
@@ -95,70 +381,1600 @@ where
         self.axis_thickness = Some(thickness);
         self
     }
-    //TODO: add axis ticks thickness
-}
+    /// set how far ticks reach from the axis line on each side, if not set, 2 px are used (1 px in compact mode)
+    pub fn set_tick_size(mut self, size: usize) -> DrawableSinglePlot<'a, C> {
+        self.tick_size = Some(size);
+        self
+    }
+    /// set which side of the axis lines ticks are drawn on, e.g. [TickDirection::Inward]
+    /// when the plot touches the display edge. If not set, ticks straddle the axis lines.
+    pub fn set_tick_direction(mut self, direction: TickDirection) -> DrawableSinglePlot<'a, C> {
+        self.tick_direction = Some(direction);
+        self
+    }
+    /// set thickness of axis ticks, if not set, axis thickness is used
+    pub fn set_tick_thickness(mut self, thickness: usize) -> DrawableSinglePlot<'a, C> {
+        self.tick_thickness = Some(thickness);
+        self
+    }
+    /// set how curve points are turned into pixels
+    pub fn set_render_mode(mut self, mode: RenderMode) -> DrawableSinglePlot<'a, C> {
+        self.render_mode = Some(mode);
+        self
+    }
+    /// draw curves as smooth splines, see [DrawableCurve::set_smoothing](crate::curve::DrawableCurve::set_smoothing)
+    pub fn set_smoothing(mut self, subdivisions: u32) -> DrawableSinglePlot<'a, C> {
+        self.smoothing = Some(subdivisions);
+        self
+    }
+    /// mark every n-th point of the curves, see [DrawableCurve::set_marker_every](crate::curve::DrawableCurve::set_marker_every).
+    /// With [set_segment_color](DrawableSinglePlot::set_segment_color) points are counted within each color run.
+    pub fn set_marker_every(mut self, n: usize) -> DrawableSinglePlot<'a, C> {
+        self.marker_every = Some(n);
+        self
+    }
+    /// draw parts of the curves above given Y value in alarm color,
+    /// segments crossing the threshold are split at the crossing point
+    pub fn set_threshold(mut self, value: i32, alarm_color: C) -> DrawableSinglePlot<'a, C> {
+        self.threshold = Some((value, alarm_color));
+        self
+    }
+    /// choose color of each segment of the curves, e.g. by quality flags of the samples. Function gets
+    /// index of the first point of the segment within its curve and both its points. Consecutive segments
+    /// of the same color are drawn together, points not connected to any segment are not drawn.
+    /// Each run of the same color is drawn as a separate curve, so smoothing, line patterns and markers
+    /// start over at every color change.
+    pub fn set_segment_color(
+        mut self,
+        color: fn(usize, &PlotPoint, &PlotPoint) -> C,
+    ) -> DrawableSinglePlot<'a, C> {
+        self.segment_color = Some(color);
+        self
+    }
+    /// rotate whole plot clockwise on display with given bounding box, e.g. to draw landscape plot
+    /// on portrait-mounted display. Plot corners are given in rotated coordinates, where width and height
+    /// of the display are swapped for 90° and 270°, [bounding_box](Dimensions::bounding_box) is mapped
+    /// back onto the display. Applies to [Drawable::draw] only, other drawing methods
+    /// can be rotated by drawing on [Rotated] display.
+    pub fn set_rotation(
+        mut self,
+        rotation: Rotation,
+        display: Rectangle,
+    ) -> DrawableSinglePlot<'a, C> {
+        self.rotation = Some((rotation, display));
+        self
+    }
+    fn rotation(&self) -> Rotation {
+        self.rotation
+            .map(|(rotation, _)| rotation)
+            .unwrap_or_default()
+    }
+    /// set how curve points are rounded to whole pixels, rounding to the nearest pixel
+    /// avoids jitter of slowly moving streaming plots. Truncated if not set.
+    pub fn set_rounding(mut self, rounding: Rounding) -> DrawableSinglePlot<'a, C> {
+        self.rounding = Some(rounding);
+        self
+    }
+    /// set bands shaded behind the curves, e.g. acceptable range of values
+    pub fn set_bands(mut self, bands: &'a [(Band, C)]) -> DrawableSinglePlot<'a, C> {
+        self.bands = bands;
+        self
+    }
 
-impl<'a, C> Drawable for DrawableSinglePlot<'a, C>
-where
-    C: PixelColor + Default,
-{
-    type Color = C;
-    type Output = ();
-    /// most important function - draw the plot on the display
-    fn draw<D>(&self, display: &mut D) -> Result<Self::Output, D::Error>
+    /// shade min/max envelope of the data behind the curves, e.g. extremes from
+    /// [min_max](crate::envelope::min_max) behind the decimated curve
+    pub fn set_envelope(
+        mut self,
+        envelope: &'a [EnvelopePoint],
+        color: C,
+    ) -> DrawableSinglePlot<'a, C> {
+        self.envelope = Some((envelope, color));
+        self
+    }
+
+    /// set font of axis tick labels, if not set, 5x8 font is used
+    pub fn set_tick_font(mut self, font: &'a MonoFont<'a>) -> DrawableSinglePlot<'a, C> {
+        self.tick_font = Some(font);
+        self
+    }
+
+    /// set font of axis titles, if not set, tick font is used
+    pub fn set_title_font(mut self, font: &'a MonoFont<'a>) -> DrawableSinglePlot<'a, C> {
+        self.title_font = Some(font);
+        self
+    }
+
+    /// set how Y axis title is laid out, vertical title saves horizontal space on narrow displays
+    pub fn set_y_title_orientation(
+        mut self,
+        orientation: TitleOrientation,
+    ) -> DrawableSinglePlot<'a, C> {
+        self.y_title_orientation = Some(orientation);
+        self
+    }
+
+    /// set on which side of Y axis tick labels are drawn, labels on the right side are drawn inside
+    /// the plot area and leave more room for the curves on narrow displays
+    pub fn set_y_tick_label_side(mut self, side: TickLabelSide) -> DrawableSinglePlot<'a, C> {
+        self.y_tick_label_side = Some(side);
+        self
+    }
+    /// set where X tick labels are drawn relative to the ticks, e.g. centered on them
+    pub fn set_x_tick_label_placement(
+        mut self,
+        placement: TickLabelPlacement,
+    ) -> DrawableSinglePlot<'a, C> {
+        self.x_tick_label_placement = Some(placement);
+        self
+    }
+
+    /// clear whole plot region with given color before drawing, for flicker-free updates without external clear
+    pub fn set_background_color(mut self, color: C) -> DrawableSinglePlot<'a, C> {
+        self.background_color = Some(color);
+        self
+    }
+
+    /// fill the area inside the axes with given color before drawing curves
+    pub fn set_plot_area_color(mut self, color: C) -> DrawableSinglePlot<'a, C> {
+        self.plot_area_color = Some(color);
+        self
+    }
+
+    /// draw full rectangle around the curve area with given color, not just bottom and left axes
+    pub fn set_frame_color(mut self, color: C) -> DrawableSinglePlot<'a, C> {
+        self.frame_color = Some(color);
+        self
+    }
+
+    /// set thickness of the frame, if not set, axis thickness is used
+    pub fn set_frame_thickness(mut self, thickness: usize) -> DrawableSinglePlot<'a, C> {
+        self.frame_thickness = Some(thickness);
+        self
+    }
+
+    /// set title of X axis, e.g. "time \[s\]", if not set, "X" is used
+    pub fn set_x_label(mut self, label: &'a str) -> DrawableSinglePlot<'a, C> {
+        self.x_label = Some(label);
+        self
+    }
+
+    /// set title of Y axis, e.g. "temp \[°C\]", if not set, "Y" is used
+    pub fn set_y_label(mut self, label: &'a str) -> DrawableSinglePlot<'a, C> {
+        self.y_label = Some(label);
+        self
+    }
+
+    /// draw X axis without title, leaving more room for the curves
+    pub fn set_x_label_hidden(mut self) -> DrawableSinglePlot<'a, C> {
+        self.x_label = None;
+        self
+    }
+
+    /// draw Y axis without title, leaving more room for the curves
+    pub fn set_y_label_hidden(mut self) -> DrawableSinglePlot<'a, C> {
+        self.y_label = None;
+        self
+    }
+
+    /// set how X axis scale marks are turned into tick labels
+    pub fn set_x_label_formatter(mut self, formatter: LabelFormatter) -> DrawableSinglePlot<'a, C> {
+        self.x_label_formatter = Some(formatter);
+        self
+    }
+
+    /// set how Y axis scale marks are turned into tick labels
+    pub fn set_y_label_formatter(mut self, formatter: LabelFormatter) -> DrawableSinglePlot<'a, C> {
+        self.y_label_formatter = Some(formatter);
+        self
+    }
+
+    /// label X axis ticks with category names, category N being at X value N, e.g. for per-channel plots
+    pub fn set_x_categories(mut self, categories: &'a [&'a str]) -> DrawableSinglePlot<'a, C> {
+        self.x_categories = Some(categories);
+        self
+    }
+
+    /// draw arrowheads of given length at the right end of X axis and the top of Y axis
+    pub fn set_axis_arrowheads(mut self, size: u32) -> DrawableSinglePlot<'a, C> {
+        self.axis_arrowhead = Some(size);
+        self
+    }
+
+    /// cross the axes at the data origin (x=0, y=0) instead of drawing them along the plot edges,
+    /// like in the math quadrant plots. Axis stays at the edge if zero is outside of the visible range.
+    /// On [rolling window](SinglePlot::set_rolling_window) X zero is the labeled one, "now".
+    pub fn set_axes_at_origin(mut self) -> DrawableSinglePlot<'a, C> {
+        self.axes_at_origin = true;
+        self
+    }
+
+    /// use the same number of pixels per unit on both axes, shrinking the plot area around its center,
+    /// so XY plots like IQ constellations or Lissajous figures are not distorted
+    pub fn set_equal_aspect(mut self) -> DrawableSinglePlot<'a, C> {
+        self.equal_aspect = true;
+        self
+    }
+
+    /// skip X axis completely - its line, ticks, labels and title, e.g. for minimal plots with just a frame
+    pub fn set_x_axis_hidden(mut self) -> DrawableSinglePlot<'a, C> {
+        self.x_axis_hidden = true;
+        self
+    }
+
+    /// skip Y axis completely - its line, ticks, labels and title
+    pub fn set_y_axis_hidden(mut self) -> DrawableSinglePlot<'a, C> {
+        self.y_axis_hidden = true;
+        self
+    }
+
+    /// draw axes without ticks
+    pub fn set_ticks_hidden(mut self) -> DrawableSinglePlot<'a, C> {
+        self.ticks_hidden = true;
+        self
+    }
+
+    /// always mark range start and end of both axes, so the plot shows its bounds
+    pub fn set_end_marks(mut self) -> DrawableSinglePlot<'a, C> {
+        self.end_marks = true;
+        self
+    }
+
+    /// draw axes without tick labels, leaving more room for the curves
+    pub fn set_tick_labels_hidden(mut self) -> DrawableSinglePlot<'a, C> {
+        self.tick_labels_hidden = true;
+        self
+    }
+
+    /// draw grid lines across the plot area at every tick of both axes, behind the curves
+    pub fn set_grid(mut self) -> DrawableSinglePlot<'a, C> {
+        self.grid = true;
+        self
+    }
+
+    /// set color of grid lines independently of the axes, e.g. faint gray behind bright curves.
+    /// If not set, axis color is used.
+    pub fn set_grid_color(mut self, color: C) -> DrawableSinglePlot<'a, C> {
+        self.grid_color = Some(color);
+        self
+    }
+
+    /// set thickness of grid lines, 1 px if not set
+    pub fn set_grid_thickness(mut self, thickness: usize) -> DrawableSinglePlot<'a, C> {
+        self.grid_thickness = Some(thickness);
+        self
+    }
+
+    /// set dash pattern of grid lines, e.g. [LinePattern::DOTTED] on monochromatic displays
+    pub fn set_grid_pattern(mut self, pattern: LinePattern) -> DrawableSinglePlot<'a, C> {
+        self.grid_pattern = Some(pattern);
+        self
+    }
+
+    /// draw legend with the labels of the curves, in the order of the curves. Each label is preceded
+    /// by a sample of the curve line, drawn with its color, thickness and dash pattern.
+    #[cfg(feature = "text")]
+    pub fn set_legend(mut self, labels: &'a [&'a str]) -> DrawableSinglePlot<'a, C> {
+        self.legend = Some(labels);
+        self
+    }
+
+    /// set corner of the plot area where the legend is placed, top right by default
+    #[cfg(feature = "text")]
+    pub fn set_legend_corner(mut self, corner: Corner) -> DrawableSinglePlot<'a, C> {
+        self.legend_corner = Some(corner);
+        self
+    }
+
+    /// leave given number of pixels empty at the top, right, bottom and left edge of the plot bounds,
+    /// e.g. to reserve room for buttons or status text drawn around the plot
+    pub fn set_margins(
+        mut self,
+        top: u32,
+        right: u32,
+        bottom: u32,
+        left: u32,
+    ) -> DrawableSinglePlot<'a, C> {
+        self.margins = Some((top, right, bottom, left));
+        self
+    }
+
+    /// keep curves given number of pixels away from the axes, so points at the edges of the range
+    /// don't merge with axis lines. Axes are moved outwards, ticks stay aligned with the data.
+    pub fn set_padding(mut self, pixels: u32) -> DrawableSinglePlot<'a, C> {
+        self.padding = Some(pixels);
+        self
+    }
+
+    /// set colors of the curves, overriding colors they were added to the plot with.
+    /// N-th curve takes n-th color, palette repeats if there are more curves.
+    pub fn set_palette(mut self, palette: &'a [C]) -> DrawableSinglePlot<'a, C> {
+        self.palette = Some(palette);
+        self
+    }
+
+    /// apply colors of the theme - axes, grid, text, background and curve palette - in one call
+    pub fn set_theme(self, theme: &PlotTheme<'a, C>) -> DrawableSinglePlot<'a, C> {
+        self.set_axis_color(theme.axis_color)
+            .set_grid_color(theme.grid_color)
+            .set_text_color(theme.text_color)
+            .set_background_color(theme.background)
+            .set_palette(theme.palette)
+    }
+
+    /// set drawing priority of the curves, n-th value for the n-th curve - curves with higher priority
+    /// are drawn later, on top of the others, e.g. to keep the most important trace always visible.
+    /// Curves with equal priority, or without one (priority 0), are drawn in the order of the slice.
+    pub fn set_priorities(mut self, priorities: &'a [i32]) -> DrawableSinglePlot<'a, C> {
+        self.priorities = Some(priorities);
+        self
+    }
+
+    /// register custom decoration drawn over the curves with access to the plot's transform,
+    /// e.g. markers or icons placed at data coordinates. Up to [MAX_OVERLAYS] overlays can be added.
+    pub fn add_overlay(
+        mut self,
+        overlay: &'a dyn PlotOverlay<C>,
+    ) -> Result<DrawableSinglePlot<'a, C>, &'static str> {
+        match self.overlays.iter_mut().find(|slot| slot.is_none()) {
+            Some(slot) => {
+                *slot = Some(overlay);
+                Ok(self)
+            }
+            None => Err("Too many overlays"),
+        }
+    }
+
+    /// indices of the curves in the order they are drawn, from the bottom to the top
+    fn draw_order(&self) -> impl Iterator<Item = usize> + '_ {
+        let priorities = self.priorities.unwrap_or(&[]);
+        let key = move |index: usize| (priorities.get(index).copied().unwrap_or(0), index);
+        let count = self.plot.curves.len();
+        let after = move |last: Option<(i32, usize)>| {
+            (0..count).map(key).filter(|key| Some(*key) > last).min()
+        };
+        successors(after(None), move |last| after(Some(*last))).map(|(_, index)| index)
+    }
+
+    /// color of the n-th curve, taken from the palette if set
+    fn curve_color(&self, index: usize) -> C {
+        match self.palette {
+            Some(palette) if !palette.is_empty() => palette[index % palette.len()],
+            _ => self.plot.curves[index].1,
+        }
+    }
+
+    /// plot bounds without the margins
+    fn outer_area(&self) -> (Point, Point) {
+        let (top, right, bottom, left) = self.margins.unwrap_or_default();
+        (
+            self.top_left + Point::new(left as i32, top as i32),
+            self.bottom_right - Point::new(right as i32, bottom as i32),
+        )
+    }
+
+    /// plot area together with the padding around it, bounded by the axes
+    fn padded_area(&self, layout: &PlotLayout<'a, C>) -> Rectangle {
+        let padding = self.padding.unwrap_or(0) as i32;
+        Rectangle::with_corners(
+            layout.top_left - Point::new(padding, padding),
+            layout.bottom_right + Point::new(padding, padding),
+        )
+    }
+
+    /// switch to compact decoration when the plot is narrower or lower than given number of pixels:
+    /// tick labels are dropped, ticks are shrunk to 1 px and axis titles are moved inside the plot area.
+    /// The same code then renders sensibly on both 128x32 OLEDs and large TFTs, e.g. with budget of 64.
+    pub fn set_compact_below(mut self, pixels: u32) -> DrawableSinglePlot<'a, C> {
+        self.compact_below = Some(pixels);
+        self
+    }
+
+    /// whether the plot is small enough to be drawn with compact decoration
+    fn is_compact(&self) -> bool {
+        let (top_left, bottom_right) = self.outer_area();
+        let size = Rectangle::with_corners(top_left, bottom_right).size;
+        match self.compact_below {
+            Some(pixels) => size.width.min(size.height) < pixels,
+            None => false,
+        }
+    }
+
+    /// how far ticks reach from the axis line
+    fn tick_size(&self) -> usize {
+        match (self.tick_size, self.is_compact()) {
+            (Some(size), _) => size,
+            (None, true) => 1,
+            (None, false) => 2,
+        }
+    }
+
+    /// transform mapping data coordinates of the plot onto the display, for placing custom decorations.
+    /// Data is mapped onto the plot area without the pixels covered by axis lines, ticks and frame.
+    pub fn transform(&self) -> Result<PlotTransform, &'static str> {
+        let viewport = self.plot.viewport();
+        let (top_left, bottom_right) = self.data_area(self.plot_area());
+        PlotTransform::new(
+            viewport.x_range(),
+            viewport.y_range(),
+            top_left,
+            bottom_right,
+        )
+        .map(|transform| transform.set_rounding(self.rounding.unwrap_or_default()))
+    }
+
+    /// screen position of the data point, e.g. for placing custom markers over the curves
+    pub fn data_to_screen(&self, point: PlotPoint) -> Result<Point, &'static str> {
+        Ok(self.transform()?.to_screen(&point))
+    }
+
+    /// data point at screen position, e.g. for handling touch input or hit-testing
+    pub fn screen_to_data(&self, point: Point) -> Result<PlotPoint, &'static str> {
+        Ok(self.transform()?.to_data(point))
+    }
+
+    /// area where curves are drawn - the plot bounds shrunk by the margins, the space
+    /// needed for tick labels and titles, so they never land outside of the plot, and the padding
+    pub fn plot_area(&self) -> (Point, Point) {
+        let (top_left, bottom_right) = self.outer_area();
+        let (x_axis, y_axis) = self.axes(top_left, bottom_right);
+        let x_extent = if self.x_axis_hidden {
+            0
+        } else {
+            x_axis.label_extent()
+        };
+        let y_extent = if self.y_axis_hidden {
+            0
+        } else {
+            y_axis.label_extent()
+        };
+        let padding = self.padding.unwrap_or(0) as i32;
+        let area = (
+            Point {
+                x: top_left.x + y_extent as i32 + padding,
+                y: top_left.y + padding,
+            },
+            Point {
+                x: bottom_right.x - padding,
+                y: bottom_right.y - x_extent as i32 - padding,
+            },
+        );
+        if self.equal_aspect {
+            self.equal_aspect_area(area)
+        } else {
+            area
+        }
+    }
+
+    /// largest centered part of the area with the same scale on both axes
+    fn equal_aspect_area(&self, (top_left, bottom_right): (Point, Point)) -> (Point, Point) {
+        let viewport = self.plot.viewport();
+        let span = |range: Range<i32>| (range.end as i64 - range.start as i64).abs();
+        let (x_span, y_span) = (span(viewport.x_range()), span(viewport.y_range()));
+        // the data is mapped onto the area inside the decoration margin
+        let margin = 2 * self.data_margin() as i64;
+        let width = (bottom_right.x - top_left.x) as i64 - margin;
+        let height = (bottom_right.y - top_left.y) as i64 - margin;
+        if x_span == 0 || y_span == 0 || width <= 0 || height <= 0 {
+            return (top_left, bottom_right);
+        }
+        // compare pixels per unit, width / x_span against height / y_span
+        let (shrink_x, shrink_y) = if width * y_span > height * x_span {
+            (width - height * x_span / y_span, 0)
+        } else {
+            (0, height - width * y_span / x_span)
+        };
+        let offset = Point::new((shrink_x / 2) as i32, (shrink_y / 2) as i32);
+        let rest = Point::new(
+            (shrink_x - shrink_x / 2) as i32,
+            (shrink_y - shrink_y / 2) as i32,
+        );
+        (top_left + offset, bottom_right - rest)
+    }
+
+    /// draw the plot like [draw](Drawable::draw), but report plot that can't show its curves,
+    /// e.g. because of single-point curve with empty range, instead of skipping them
+    pub fn try_draw<D>(&self, display: &mut D) -> Result<Rectangle, PlotError<D::Error>>
     where
         D: DrawTarget<Color = C>,
     {
+        let has_data = self.plot.curves.iter().any(|(curve, _)| !curve.is_empty());
+        if has_data {
+            self.transform().map_err(PlotError::Invalid)?;
+        }
+        self.draw(display).map_err(PlotError::Display)
+    }
+
+    /// axes decorated according to the plot settings, placed along the edges of the curve area
+    fn axes(
+        &self,
+        top_left: Point,
+        bottom_right: Point,
+    ) -> (DrawableAxis<'a, C>, DrawableAxis<'a, C>) {
         let color = self.color.unwrap_or_default();
-        let text_color = self.text_color.unwrap_or(color);
         let axis_color = self.axis_color.unwrap_or(color);
         let thickness = self.thickness.unwrap_or(2);
         let axis_thickness = self.axis_thickness.unwrap_or(thickness);
-        let text_style = MonoTextStyleBuilder::new().text_color(text_color).build();
-
-        let x_range = self.plot.curves[0].0.x_range.clone();
-        let y_range = self.plot.curves[0].0.y_range.clone();
+        let compact = self.is_compact();
+        let tick_size = self.tick_size();
+        let tick_thickness = self.tick_thickness.unwrap_or(axis_thickness);
+        let viewport = self.plot.viewport();
+        let padding = self.padding.unwrap_or(0) as i32;
+        let origin = if self.axes_at_origin {
+            PlotTransform::new(
+                viewport.x_range(),
+                viewport.y_range(),
+                top_left,
+                bottom_right,
+            )
+            .ok()
+        } else {
+            None
+        };
+        let x_axis_y = origin
+            .as_ref()
+            .filter(|transform| transform.contains_y(0))
+            .map_or(bottom_right.y + padding, |transform| {
+                transform.y_to_screen(0)
+            });
+        // X axis labeled relative to rolling window's "now" has its zero there
+        let x_zero = self.plot.x_label_origin.unwrap_or(0);
+        let y_axis_x = origin
+            .as_ref()
+            .filter(|transform| transform.contains_x(x_zero))
+            .map_or(top_left.x - padding, |transform| {
+                transform.x_to_screen(x_zero)
+            });
 
-        Axis::new(x_range)
-            .set_title("X")
-            .set_scale(self.plot.x_scale)
+        let x_range = match self.plot.x_label_origin {
+            Some(origin) => {
+                let Range { start, end } = viewport.x_range();
+                start.saturating_sub(origin)..end.saturating_sub(origin)
+            }
+            None => viewport.x_range(),
+        };
+        let mut x_axis = Axis::new(x_range).set_scale(self.plot.x_scale);
+        if let Some(label) = self.x_label {
+            x_axis = x_axis.set_title(label);
+        }
+        if let Some(formatter) = self.x_label_formatter {
+            x_axis = x_axis.set_label_formatter(formatter);
+        }
+        if let Some(categories) = self.x_categories {
+            x_axis = x_axis.set_categories(categories);
+        }
+        if self.end_marks {
+            x_axis = x_axis.set_end_marks();
+        }
+        let mut x_axis = x_axis
             .into_drawable_axis(Placement::X {
-                x1: self.top_left.x,
-                x2: self.bottom_right.x,
-                y: self.bottom_right.y,
+                x1: top_left.x,
+                x2: bottom_right.x,
+                y: x_axis_y,
             })
             .set_color(axis_color)
-            .set_text_style(text_style)
-            .set_tick_size(2)
-            .set_thickness(axis_thickness)
-            .draw(display)?;
-        Axis::new(y_range)
-            .set_title("Y")
-            .set_scale(self.plot.y_scale)
+            .set_tick_label_placement(self.x_tick_label_placement.unwrap_or_default())
+            .set_tick_size(tick_size)
+            .set_tick_direction(self.tick_direction.unwrap_or_default())
+            .set_tick_thickness(tick_thickness)
+            .set_thickness(axis_thickness);
+        let mut y_axis = Axis::new(viewport.y_range()).set_scale(self.plot.y_scale);
+        if let Some(label) = self.y_label {
+            y_axis = y_axis.set_title(label);
+        }
+        if let Some(formatter) = self.y_label_formatter {
+            y_axis = y_axis.set_label_formatter(formatter);
+        }
+        if self.end_marks {
+            y_axis = y_axis.set_end_marks();
+        }
+        let mut y_axis = y_axis
             .into_drawable_axis(Placement::Y {
-                y1: self.top_left.y,
-                y2: self.bottom_right.y,
-                x: self.top_left.x,
+                y1: top_left.y,
+                y2: bottom_right.y,
+                x: y_axis_x,
             })
             .set_color(axis_color)
-            .set_text_style(text_style)
-            .set_tick_size(2)
-            .set_thickness(axis_thickness)
-            .draw(display)?;
+            .set_title_orientation(self.y_title_orientation.unwrap_or_default())
+            .set_tick_label_side(self.y_tick_label_side.unwrap_or_default())
+            .set_tick_size(tick_size)
+            .set_tick_direction(self.tick_direction.unwrap_or_default())
+            .set_tick_thickness(tick_thickness)
+            .set_thickness(axis_thickness);
+        if let Some(size) = self.axis_arrowhead {
+            x_axis = x_axis.set_arrowhead(size);
+            y_axis = y_axis.set_arrowhead(size);
+        }
+        #[cfg(feature = "text")]
+        {
+            let tick_font = self.tick_font.unwrap_or(&FONT_5X8);
+            let title_font = self.title_font.unwrap_or(tick_font);
+            let text_style = MonoTextStyleBuilder::new()
+                .font(tick_font)
+                .text_color(self.text_color.unwrap_or(color))
+                .build();
+            x_axis = x_axis.set_text_style(text_style).set_title_font(title_font);
+            y_axis = y_axis.set_text_style(text_style).set_title_font(title_font);
+        }
+        if compact {
+            x_axis = x_axis.set_title_inside();
+            y_axis = y_axis.set_title_inside();
+        }
+        if compact || self.tick_labels_hidden {
+            x_axis = x_axis.set_tick_labels_hidden();
+            y_axis = y_axis.set_tick_labels_hidden();
+        }
+        if self.ticks_hidden {
+            x_axis = x_axis.set_ticks_hidden();
+            y_axis = y_axis.set_ticks_hidden();
+        }
+        if self.grid {
+            x_axis = x_axis.set_grid(top_left.y, bottom_right.y);
+            y_axis = y_axis.set_grid(top_left.x, bottom_right.x);
+            if let Some(color) = self.grid_color {
+                x_axis = x_axis.set_grid_color(color);
+                y_axis = y_axis.set_grid_color(color);
+            }
+            if let Some(thickness) = self.grid_thickness {
+                x_axis = x_axis.set_grid_thickness(thickness);
+                y_axis = y_axis.set_grid_thickness(thickness);
+            }
+            if let Some(pattern) = self.grid_pattern {
+                x_axis = x_axis.set_grid_pattern(pattern);
+                y_axis = y_axis.set_grid_pattern(pattern);
+            }
+        }
+        (x_axis, y_axis)
+    }
+
+    /// apply plot decoration to the curve and draw it
+    fn draw_curve<D, I>(
+        &self,
+        display: &mut D,
+        transform: &PlotTransform,
+        points: I,
+        index: usize,
+        color: C,
+        thickness: usize,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        I: Iterator<Item = PlotPoint> + Clone,
+    {
+        let mut curve = transform
+            .drawable_curve(points)
+            .set_color(color)
+            .set_thickness(thickness)
+            .set_render_mode(self.render_mode.unwrap_or_default());
+        if let Some(subdivisions) = self.smoothing {
+            curve = curve.set_smoothing(subdivisions);
+        }
+        if let Some(n) = self.marker_every {
+            curve = curve.set_marker_every(n);
+        }
+        if let Some((value, alarm_color)) = self.threshold {
+            let y = transform.y_to_screen(value);
+            let y_range = transform.y_range();
+            curve = if y_range.start <= y_range.end {
+                curve.set_threshold(y, alarm_color)
+            } else {
+                // on inverted Y axis, values above the threshold are drawn below it
+                curve.set_color(alarm_color).set_threshold(y, color)
+            };
+        }
+        match self.curve_pattern {
+            Some(pattern) => curve.set_pattern(pattern(index)).draw(display)?,
+            None => curve.draw(display)?,
+        };
+        Ok(())
+    }
+
+    /// compute geometry of the plot once - plot area, axes with their ticks and labels and the transform.
+    /// Layout stays valid until viewport or decoration of the plot changes and lets
+    /// [draw_decoration](DrawableSinglePlot::draw_decoration) and [draw_data](DrawableSinglePlot::draw_data)
+    /// skip measuring the labels on every frame.
+    pub fn layout(&self) -> PlotLayout<'a, C> {
+        let (top_left, bottom_right) = self.plot_area();
+        let (x_axis, y_axis) = self.axes(top_left, bottom_right);
+        let (data_top_left, data_bottom_right) = self.data_area((top_left, bottom_right));
+        PlotLayout {
+            top_left,
+            bottom_right,
+            data_area: Rectangle::with_corners(data_top_left, data_bottom_right),
+            transform: self.transform().ok(),
+            x_axis,
+            y_axis,
+        }
+    }
+
+    /// plot area without the pixels covered by axis lines, ticks and frame, the curves are mapped onto it
+    /// so [draw_data](DrawableSinglePlot::draw_data) redraws them whole without touching the decoration
+    fn data_area(&self, (top_left, bottom_right): (Point, Point)) -> (Point, Point) {
+        let margin = self.data_margin();
+        (
+            top_left + Point::new(margin, margin),
+            bottom_right - Point::new(margin, margin),
+        )
+    }
+
+    /// how far axis lines, ticks and frame reach into the plot area
+    fn data_margin(&self) -> i32 {
+        let thickness = self.thickness.unwrap_or(2);
+        let axis_thickness = self.axis_thickness.unwrap_or(thickness);
+        let frame_thickness = self.frame_thickness.unwrap_or(axis_thickness);
+        let tick_thickness = self.tick_thickness.unwrap_or(axis_thickness);
+        let (_, inward) = self
+            .tick_direction
+            .unwrap_or_default()
+            .reach(self.tick_size());
+        let padding = self.padding.unwrap_or(0) as i32;
+        // padding keeps the decoration away from the data
+        ((inward + axis_thickness.max(frame_thickness).max(tick_thickness)) as i32 - padding).max(0)
+    }
+
+    /// draw everything but the curves: background, plot area, bands, grid, axes and frame,
+    /// returns bounding box of touched pixels
+    pub fn draw_decoration<D>(
+        &self,
+        layout: &PlotLayout<'a, C>,
+        display: &mut D,
+    ) -> Result<Rectangle, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let display = &mut DirtyTracker::new(display);
+        let thickness = self.thickness.unwrap_or(2);
+
+        if let Some(background_color) = self.background_color {
+            let (top_left, bottom_right) = self.outer_area();
+            display.fill_solid(
+                &Rectangle::with_corners(top_left, bottom_right),
+                background_color,
+            )?;
+        }
+        if let Some(plot_area_color) = self.plot_area_color {
+            display.fill_solid(&self.padded_area(layout), plot_area_color)?;
+        }
+
+        self.draw_bands(layout, display)?;
+
+        self.draw_grid(layout, display)?;
+
+        self.draw_axes(layout, display)?;
+
+        if let Some(frame_color) = self.frame_color {
+            let frame_thickness = self
+                .frame_thickness
+                .or(self.axis_thickness)
+                .unwrap_or(thickness);
+            self.padded_area(layout)
+                .into_styled(PrimitiveStyle::with_stroke(
+                    frame_color,
+                    frame_thickness as u32,
+                ))
+                .draw(display)?;
+        }
+        Ok(display.dirty())
+    }
 
-        for curve in self.plot.curves {
-            //TODO: how to handle errors here? Seems that we can only pass through DrawTarget error, not add our own.
-            // Use anyhow with no_std?
-            if let Ok(c) = curve
-                .0
-                .into_drawable_curve(&self.top_left, &self.bottom_right)
-            {
-                c.set_color(curve.1)
-                    .set_thickness(thickness)
-                    .draw(display)?
+    /// cheap redraw of the data for fast updates, when decoration was already drawn with the same layout.
+    /// Only the inside of the plot area, away from axis lines, ticks and frame is cleared and redrawn -
+    /// curves are clipped to it. Returns bounding box of touched pixels.
+    pub fn draw_data<D>(
+        &self,
+        layout: &PlotLayout<'a, C>,
+        display: &mut D,
+    ) -> Result<Rectangle, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let mut display = DirtyTracker::new(display);
+        if let Some(color) = self.plot_area_color.or(self.background_color) {
+            display.fill_solid(&layout.data_area, color)?;
+        }
+        let mut clipped = display.clipped(&layout.data_area);
+        self.draw_bands(layout, &mut clipped)?;
+        self.draw_grid(layout, &mut clipped)?;
+        if self.axes_at_origin {
+            // axes crossing the data area were just cleared
+            self.draw_axes(layout, &mut clipped)?;
+        }
+        self.draw_curves(layout, &mut clipped)?;
+        self.draw_overlays(layout, &mut clipped)?;
+        self.draw_legend(layout, &mut clipped)?;
+        Ok(display.dirty())
+    }
+
+    fn draw_axes<D>(&self, layout: &PlotLayout<'a, C>, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        if !self.x_axis_hidden {
+            layout.x_axis.draw(display)?;
+        }
+        if !self.y_axis_hidden {
+            layout.y_axis.draw(display)?;
+        }
+        Ok(())
+    }
+
+    /// grid of both axes, drawn before the axes themselves so it doesn't cover their lines
+    fn draw_grid<D>(&self, layout: &PlotLayout<'a, C>, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        layout.x_axis.draw_grid(display)?;
+        layout.y_axis.draw_grid(display)?;
+        Ok(())
+    }
+
+    fn draw_bands<D>(&self, layout: &PlotLayout<'a, C>, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        if let Some(transform) = &layout.transform {
+            for (band, band_color) in self.bands {
+                band.into_drawable(transform)
+                    .set_color(*band_color)
+                    .draw(display)?;
             }
         }
+        Ok(())
+    }
 
+    fn draw_curves<D>(&self, layout: &PlotLayout<'a, C>, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        self.draw_envelope(layout, display)?;
+        for index in self.draw_order() {
+            let points = self.plot.curves[index].0.iter();
+            self.draw_curve_points(layout, display, index, 0, points)?;
+        }
         Ok(())
     }
+
+    /// shaded envelope behind the curves, clipped to the plot area
+    fn draw_envelope<D>(&self, layout: &PlotLayout<'a, C>, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        if let (Some((points, color)), Some(transform)) = (self.envelope, &layout.transform) {
+            Envelope::new(points)
+                .into_drawable(transform)
+                .set_color(color)
+                .draw(&mut display.clipped(&transform.area()))?;
+        }
+        Ok(())
+    }
+
+    /// draw given points of the n-th curve starting with its `first` point, placed the same way
+    /// as when the whole curve is drawn, split into runs of segments of the same color if it's chosen per segment
+    fn draw_curve_points<D, I>(
+        &self,
+        layout: &PlotLayout<'a, C>,
+        display: &mut D,
+        index: usize,
+        first: usize,
+        points: I,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        I: Iterator<Item = PlotPoint> + Clone,
+    {
+        let segment_color = match self.segment_color {
+            Some(segment_color) => segment_color,
+            None => {
+                let color = self.curve_color(index);
+                return self.draw_curve_run(layout, display, index, points, color);
+            }
+        };
+        let draw_run = |display: &mut D, (start, color): (usize, C), end: usize| {
+            let run_points = points.clone().skip(start).take(end - start + 1);
+            self.draw_curve_run(layout, display, index, run_points, color)
+        };
+        // start of the current run and its color, segments touching a gap end the run
+        let mut run: Option<(usize, C)> = None;
+        let mut segments = 0;
+        for (offset, (start, end)) in points.clone().tuple_windows().enumerate() {
+            segments = offset + 1;
+            let color = if start.is_gap() || end.is_gap() {
+                None
+            } else {
+                Some(segment_color(first + offset, &start, &end))
+            };
+            if run.map(|(_, color)| color) != color {
+                if let Some(run) = run {
+                    draw_run(display, run, offset)?;
+                }
+                run = color.map(|color| (offset, color));
+            }
+        }
+        if let Some(run) = run {
+            draw_run(display, run, segments)?;
+        }
+        Ok(())
+    }
+
+    /// draw given points of the n-th curve in given color
+    fn draw_curve_run<D, I>(
+        &self,
+        layout: &PlotLayout<'a, C>,
+        display: &mut D,
+        index: usize,
+        points: I,
+        color: C,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+        I: Iterator<Item = PlotPoint> + Clone,
+    {
+        let thickness = self.thickness.unwrap_or(2);
+        // without transform curves are skipped, try_draw reports it
+        // all curves share ranges of the plot, so they are drawn against the same axes
+        if let Some(transform) = &layout.transform {
+            let policy = self.plot.curves[index].0.out_of_range();
+            let points = points.map(move |p| transform.limit(p, policy));
+            if self.plot.clips_curves() {
+                // with viewport or explicit ranges, curves may reach outside of the plot area
+                let mut clipped = display.clipped(&transform.area());
+                self.draw_curve(&mut clipped, transform, points, index, color, thickness)?;
+            } else {
+                self.draw_curve(display, transform, points, index, color, thickness)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// draw part of the plot and remember where to continue: decoration in the first call, then up to
+    /// `max_segments` line segments of the curves per call and the overlays with the legend at the end. Returns [Poll::Ready]
+    /// once the whole plot is drawn, so long render can be spread over main loop iterations without
+    /// holding the display bus for long. Start with [DrawState::new] and keep the layout until the plot is done.
+    /// Smoothing, dash patterns and markers would restart at the chunk boundaries, so curves using them
+    /// can't be split and [PlotError::Invalid] is returned before anything is drawn.
+    pub fn draw_some<D>(
+        &self,
+        layout: &PlotLayout<'a, C>,
+        state: &mut DrawState,
+        display: &mut D,
+        max_segments: usize,
+    ) -> Result<Poll<()>, PlotError<D::Error>>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        if state.stage == 0 && self.splits_decorated_curves(max_segments) {
+            return Err(PlotError::Invalid(
+                "Smoothing, patterns and markers can't be drawn in chunks",
+            ));
+        }
+        match self.rotation() {
+            Rotation::Deg0 => self.draw_step(layout, state, display, max_segments),
+            rotation => {
+                let mut rotated = Rotated::new(display, rotation);
+                self.draw_step(layout, state, &mut rotated, max_segments)
+            }
+        }
+        .map_err(PlotError::Display)
+    }
+
+    /// some curve is longer than single chunk and decorated in a way that can't continue in the next one
+    fn splits_decorated_curves(&self, max_segments: usize) -> bool {
+        let curves = self.plot.curves;
+        let patterned = self.curve_pattern.map_or(false, |pattern| {
+            (0..curves.len()).any(|index| pattern(index) != LinePattern::SOLID)
+        });
+        let decorated = self.smoothing.is_some() || self.marker_every.is_some() || patterned;
+        decorated
+            && curves
+                .iter()
+                .any(|(curve, _)| curve.len() > max_segments.max(1) + 1)
+    }
+
+    fn draw_step<D>(
+        &self,
+        layout: &PlotLayout<'a, C>,
+        state: &mut DrawState,
+        display: &mut D,
+        max_segments: usize,
+    ) -> Result<Poll<()>, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let curves = self.plot.curves;
+        match state.stage {
+            0 => {
+                self.draw_decoration(layout, display)?;
+                self.draw_envelope(layout, display)?;
+            }
+            stage if stage <= curves.len() => {
+                let index = self.draw_order().nth(stage - 1).unwrap_or_default();
+                let curve = &curves[index].0;
+                let last = curve.len().saturating_sub(1);
+                let end = (state.point + max_segments.max(1)).min(last);
+                if !curve.is_empty() {
+                    // chunks share their boundary point, so no segment is lost between them
+                    let points = curve.iter().skip(state.point).take(end - state.point + 1);
+                    self.draw_curve_points(layout, display, index, state.point, points)?;
+                }
+                if end < last {
+                    state.point = end;
+                    return Ok(Poll::Pending);
+                }
+                state.point = 0;
+            }
+            stage if stage == curves.len() + 1 => {
+                self.draw_overlays(layout, display)?;
+                self.draw_legend(layout, display)?;
+            }
+            _ => return Ok(Poll::Ready(())),
+        }
+        state.stage += 1;
+        if state.stage > curves.len() + 1 {
+            Ok(Poll::Ready(()))
+        } else {
+            Ok(Poll::Pending)
+        }
+    }
+
+    /// custom overlays drawn over the curves, clipped to the plot area
+    fn draw_overlays<D>(&self, layout: &PlotLayout<'a, C>, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        if let Some(transform) = &layout.transform {
+            let mut clipped = display.clipped(&transform.area());
+            for overlay in self.overlays.iter().flatten() {
+                draw_overlay(*overlay, transform, &mut clipped)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// legend drawn over the curves, in the corner of the data area
+    #[cfg(feature = "text")]
+    fn draw_legend<D>(&self, layout: &PlotLayout<'a, C>, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let labels = match self.legend {
+            Some(labels) => labels,
+            None => return Ok(()),
+        };
+        let thickness = self.thickness.unwrap_or(2);
+        let entries = self
+            .plot
+            .curves
+            .iter()
+            .zip(labels)
+            .enumerate()
+            .map(|(index, (_, label))| {
+                let entry =
+                    LegendEntry::new(label, self.curve_color(index)).set_thickness(thickness);
+                match self.curve_pattern {
+                    Some(pattern) => entry.set_pattern(pattern(index)),
+                    None => entry,
+                }
+            });
+        let mut legend = Legend::new(entries)
+            .set_corner(self.legend_corner.unwrap_or_default())
+            .into_drawable(layout.data_area)
+            .set_color(self.text_color.or(self.color).unwrap_or_default());
+        if let Some(color) = self.plot_area_color.or(self.background_color) {
+            legend = legend.set_box_color(color);
+        }
+        legend.draw(display)?;
+        Ok(())
+    }
+
+    /// without text there is no legend
+    #[cfg(not(feature = "text"))]
+    fn draw_legend<D>(&self, _: &PlotLayout<'a, C>, _: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        Ok(())
+    }
+}
+
+/// Progress of the plot drawn in chunks by [DrawableSinglePlot::draw_some]
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DrawState {
+    /// decoration first, then the curves one by one and the overlays with the legend at the end
+    stage: usize,
+    /// index of the curve point the next chunk starts at
+    point: usize,
+}
+
+impl DrawState {
+    /// state of the plot that is not drawn yet, also used to start over
+    pub fn new() -> DrawState {
+        DrawState::default()
+    }
+}
+
+/// Geometry of the plot computed by [DrawableSinglePlot::layout], reused between frames
+pub struct PlotLayout<'a, C>
+where
+    C: PixelColor + Default,
+{
+    top_left: Point,
+    bottom_right: Point,
+    data_area: Rectangle,
+    transform: Option<PlotTransform>,
+    x_axis: DrawableAxis<'a, C>,
+    y_axis: DrawableAxis<'a, C>,
+}
+
+impl<'a, C> PlotLayout<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// area inside the axes, see [DrawableSinglePlot::plot_area]
+    pub fn plot_area(&self) -> (Point, Point) {
+        (self.top_left, self.bottom_right)
+    }
+
+    /// part of the plot area redrawn by [DrawableSinglePlot::draw_data]
+    pub fn data_area(&self) -> Rectangle {
+        self.data_area
+    }
+
+    /// transform of the visible data onto the plot area, None if the plot area or viewport is empty
+    pub fn transform(&self) -> Option<&PlotTransform> {
+        self.transform.as_ref()
+    }
+}
+
+impl<'a, C> DrawableSinglePlot<'a, C>
+where
+    C: PixelColor + Default + DefaultStyle,
+{
+    /// decorate plot with style suitable for the color type,
+    /// on monochromatic displays curves are distinguished by dash patterns
+    pub fn with_default_style(mut self) -> DrawableSinglePlot<'a, C> {
+        self.curve_pattern = Some(C::curve_pattern);
+        self.set_color(C::foreground())
+            .set_thickness(C::curve_thickness())
+            .set_axis_thickness(C::axis_thickness())
+    }
+}
+
+impl<'a, C> Transform for DrawableSinglePlot<'a, C>
+where
+    C: PixelColor + Default,
+{
+    fn translate(&self, by: Point) -> Self {
+        DrawableSinglePlot {
+            top_left: self.top_left + by,
+            bottom_right: self.bottom_right + by,
+            ..*self
+        }
+    }
+
+    fn translate_mut(&mut self, by: Point) -> &mut Self {
+        self.top_left += by;
+        self.bottom_right += by;
+        self
+    }
+}
+
+impl<'a, C> DrawIn for DrawableSinglePlot<'a, C>
+where
+    C: PixelColor + Default,
+{
+    fn moved_to(&self, top_left: Point, bottom_right: Point) -> Self {
+        DrawableSinglePlot {
+            top_left,
+            bottom_right,
+            ..*self
+        }
+    }
+}
+
+impl<'a, C> Dimensions for DrawableSinglePlot<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// whole area of the plot, including labels, on the display the plot is rotated on
+    fn bounding_box(&self) -> Rectangle {
+        let area = Rectangle::with_corners(self.top_left, self.bottom_right);
+        match self.rotation {
+            Some((rotation, display)) => rotate_area(rotation, &display, &area),
+            None => area,
+        }
+    }
+}
+
+impl<'a, C> Drawable for DrawableSinglePlot<'a, C>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    /// bounding box of pixels touched by the plot, for flushing only changed region to the display
+    type Output = Rectangle;
+    /// most important function - draw the plot on the display
+    fn draw<D>(&self, display: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let layout = self.layout();
+        let display = &mut DirtyTracker::new(display);
+        match self.rotation() {
+            Rotation::Deg0 => {
+                self.draw_decoration(&layout, display)?;
+                self.draw_curves(&layout, display)?;
+                self.draw_overlays(&layout, display)?;
+                self.draw_legend(&layout, display)?;
+            }
+            rotation => {
+                let mut rotated = Rotated::new(display, rotation);
+                self.draw_decoration(&layout, &mut rotated)?;
+                self.draw_curves(&layout, &mut rotated)?;
+                self.draw_overlays(&layout, &mut rotated)?;
+                self.draw_legend(&layout, &mut rotated)?;
+            }
+        }
+        Ok(display.dirty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::axis::Scale;
+    use crate::curve::{Curve, PlotPoint};
+    use crate::error::PlotError;
+    use crate::placement::IntoDrawableArea;
+    use crate::rotation::{Rotated, Rotation};
+    use crate::single_plot::{DrawState, SinglePlot};
+    use crate::theme::PlotTheme;
+    use core::ops::Range;
+    use embedded_graphics::{
+        geometry::AnchorPoint,
+        mock_display::MockDisplay,
+        pixelcolor::{BinaryColor, Rgb565, WebColors},
+        prelude::*,
+        primitives::Rectangle,
+    };
+    use test_case::test_case;
+
+    #[test]
+    fn equal_aspect() {
+        let points = [PlotPoint { x: 0, y: 0 }, PlotPoint { x: 20, y: 10 }];
+        let curves = [(Curve::from_data(&points), BinaryColor::On)];
+        let plot = SinglePlot::new(&curves, Scale::Auto, Scale::Auto)
+            .unwrap()
+            .into_drawable(Point::zero(), Point::new(99, 99))
+            .set_tick_labels_hidden()
+            .set_equal_aspect();
+        let size = plot.layout().data_area().size;
+        let (width, height) = (size.width as i32 - 1, size.height as i32 - 1);
+        assert!(width > height && (width - 2 * height).abs() <= 1);
+        let transform = plot.transform().unwrap();
+        let origin = transform.to_screen(&PlotPoint { x: 0, y: 0 });
+        let corner = transform.to_screen(&PlotPoint { x: 10, y: 10 });
+        assert_eq!(corner.x - origin.x, origin.y - corner.y);
+    }
+
+    #[test]
+    fn try_draw_reports_invalid_range() {
+        let points = [PlotPoint { x: 3, y: 3 }];
+        let curves = [(Curve::new(&points, 3..3, 0..10), BinaryColor::On)];
+        let plot = SinglePlot::new(&curves, Scale::Auto, Scale::Auto)
+            .unwrap()
+            .into_drawable(Point::zero(), Point::new(63, 63));
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        assert_eq!(
+            plot.try_draw(&mut display),
+            Err(PlotError::Invalid("Invalid range"))
+        );
+    }
+
+    #[test]
+    fn chunks_draw_whole_plot() {
+        let mut points = [PlotPoint { x: 0, y: 0 }; 10];
+        for (x, point) in points.iter_mut().enumerate() {
+            point.x = x as i32;
+            point.y = (x * x) as i32;
+        }
+        let curves = [(Curve::from_data(&points), BinaryColor::On)];
+        let plot = SinglePlot::new(&curves, Scale::Auto, Scale::Auto)
+            .unwrap()
+            .into_drawable(Point::zero(), Point::new(63, 63))
+            .set_color(BinaryColor::On);
+
+        let mut expected = MockDisplay::new();
+        expected.set_allow_overdraw(true);
+        plot.draw(&mut expected).unwrap();
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        let layout = plot.layout();
+        let mut state = DrawState::new();
+        let mut calls = 0;
+        while plot
+            .draw_some(&layout, &mut state, &mut display, 4)
+            .unwrap()
+            .is_pending()
+        {
+            calls += 1;
+        }
+        // decoration and three chunks of the curve, the legend finishes the plot
+        assert_eq!(calls, 4);
+        display.assert_eq(&expected);
+    }
+
+    #[test_case(4 => true; "split")]
+    #[test_case(9 => false; "single chunk")]
+    fn markers_not_split(max_segments: usize) -> bool {
+        let points = [PlotPoint { x: 0, y: 0 }; 10];
+        let curves = [(Curve::from_data(&points), BinaryColor::On)];
+        let plot = SinglePlot::new(&curves, Scale::Auto, Scale::Auto)
+            .unwrap()
+            .into_drawable(Point::zero(), Point::new(63, 63))
+            .set_marker_every(3);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        let result = plot.draw_some(
+            &plot.layout(),
+            &mut DrawState::new(),
+            &mut display,
+            max_segments,
+        );
+        // nothing is drawn when the plot is refused
+        assert_eq!(result.is_err(), display == MockDisplay::new());
+        result.is_err()
+    }
+
+    #[test]
+    fn data_redraw_keeps_range_extremes() {
+        let points = [PlotPoint { x: 0, y: 0 }, PlotPoint { x: 10, y: 10 }];
+        let curves = [(Curve::from_data(&points), BinaryColor::On)];
+        let plot = SinglePlot::new(&curves, Scale::Auto, Scale::Auto)
+            .unwrap()
+            .into_drawable(Point::zero(), Point::new(63, 63))
+            .set_color(BinaryColor::On)
+            .set_thickness(1);
+        let layout = plot.layout();
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        plot.draw_data(&layout, &mut display).unwrap();
+        for point in points {
+            let screen = plot.data_to_screen(point).unwrap();
+            assert!(layout.data_area().contains(screen));
+            assert_eq!(display.get_pixel(screen), Some(BinaryColor::On));
+        }
+    }
+
+    #[test]
+    fn ranges() {
+        let low = [PlotPoint { x: 0, y: 10 }, PlotPoint { x: 10, y: 20 }];
+        let high = [PlotPoint { x: 5, y: 50 }, PlotPoint { x: 20, y: 60 }];
+        let curves = [
+            (Curve::from_data(&low), BinaryColor::On),
+            (Curve::from_data(&high), BinaryColor::On),
+        ];
+        let plot = SinglePlot::new(&curves, Scale::Auto, Scale::Auto).unwrap();
+        assert_eq!(plot.viewport().x_range(), 0..20);
+        assert_eq!(plot.viewport().y_range(), 10..60);
+        let plot = plot.set_y_range(0..100);
+        assert_eq!(plot.viewport().x_range(), 0..20);
+        assert_eq!(plot.viewport().y_range(), 0..100);
+    }
+
+    #[test]
+    fn rolling_window_labels_stay_in_place() {
+        let draw = |now: i32| {
+            let points = [
+                PlotPoint { x: now - 50, y: 0 },
+                PlotPoint { x: now - 20, y: 10 },
+                PlotPoint { x: now, y: 5 },
+            ];
+            let curves = [(Curve::from_data(&points), BinaryColor::On)];
+            let plot = SinglePlot::new(&curves, Scale::Fixed(20), Scale::Auto)
+                .unwrap()
+                .set_rolling_window(now, 60);
+            assert_eq!(plot.viewport().x_range(), now - 60..now);
+            let mut display = MockDisplay::new();
+            display.set_allow_overdraw(true);
+            plot.into_drawable(Point::new(10, 0), Point::new(63, 50))
+                .set_y_label_hidden()
+                .draw(&mut display)
+                .unwrap();
+            display
+        };
+        // absolute timestamps would be labeled differently
+        draw(60).assert_eq(&draw(1000));
+    }
+
+    #[test_case(false, None => 10..60; "data only")]
+    #[test_case(true, None => 0..60; "zero included")]
+    #[test_case(false, Some(10) => 5..65; "padded")]
+    #[test_case(true, Some(10) => 0..65; "padded and zero included")]
+    fn automatic_y_range(includes_zero: bool, padding: Option<u32>) -> Range<i32> {
+        let points = [PlotPoint { x: 0, y: 10 }, PlotPoint { x: 10, y: 60 }];
+        let curves = [(Curve::from_data(&points), BinaryColor::On)];
+        let mut plot = SinglePlot::new(&curves, Scale::Auto, Scale::Auto).unwrap();
+        if includes_zero {
+            plot = plot.set_y_includes_zero();
+        }
+        if let Some(percent) = padding {
+            plot = plot.set_y_range_padding(percent);
+        }
+        plot.viewport().y_range()
+    }
+
+    #[test]
+    fn segment_colors() {
+        fn flagged(index: usize, _: &PlotPoint, _: &PlotPoint) -> Rgb565 {
+            if index < 2 {
+                Rgb565::RED
+            } else {
+                Rgb565::GREEN
+            }
+        }
+        let points = [0, 10, 20, 30].map(|x| PlotPoint { x, y: 5 });
+        let curves = [(Curve::from_data(&points), Rgb565::WHITE)];
+        let plot = SinglePlot::new(&curves, Scale::Auto, Scale::Auto)
+            .unwrap()
+            .set_y_range(0..10)
+            .into_drawable(Point::zero(), Point::new(63, 63))
+            .set_segment_color(flagged);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        plot.draw(&mut display).unwrap();
+        let pixel = |x: i32| display.get_pixel(plot.data_to_screen(PlotPoint { x, y: 5 }).unwrap());
+        assert_eq!(pixel(5), Some(Rgb565::RED));
+        assert_eq!(pixel(15), Some(Rgb565::RED));
+        assert_eq!(pixel(25), Some(Rgb565::GREEN));
+    }
+
+    #[test]
+    fn priority_curve_on_top() {
+        let points = [PlotPoint { x: 0, y: 0 }, PlotPoint { x: 10, y: 10 }];
+        let curves = [
+            (Curve::from_data(&points), Rgb565::RED),
+            (Curve::from_data(&points), Rgb565::GREEN),
+        ];
+        let draw = |priorities| {
+            let plot = SinglePlot::new(&curves, Scale::Auto, Scale::Auto)
+                .unwrap()
+                .into_drawable(Point::zero(), Point::new(63, 63))
+                .set_priorities(priorities);
+            let mut display = MockDisplay::new();
+            display.set_allow_overdraw(true);
+            plot.draw(&mut display).unwrap();
+            let center = plot.data_to_screen(PlotPoint { x: 5, y: 5 }).unwrap();
+            display.get_pixel(center)
+        };
+        assert_eq!(draw(&[]), Some(Rgb565::GREEN));
+        assert_eq!(draw(&[1]), Some(Rgb565::RED));
+    }
+
+    #[test]
+    fn anchored_placement() {
+        let points = [PlotPoint { x: 0, y: 0 }, PlotPoint { x: 10, y: 10 }];
+        let curves = [(Curve::from_data(&points), BinaryColor::On)];
+        let plot = SinglePlot::new(&curves, Scale::Auto, Scale::Auto)
+            .unwrap()
+            .into_drawable_at(Point::new(50, 40), AnchorPoint::Center, Size::new(40, 20));
+        assert_eq!(
+            plot.bounding_box(),
+            Rectangle::with_center(Point::new(50, 40), Size::new(40, 20))
+        );
+    }
+
+    #[test]
+    fn theme_palette_overrides_curve_color() {
+        let points = [PlotPoint { x: 0, y: 0 }, PlotPoint { x: 10, y: 10 }];
+        let curves = [(Curve::from_data(&points), Rgb565::RED)];
+        let plot = SinglePlot::new(&curves, Scale::Auto, Scale::Auto)
+            .unwrap()
+            .into_drawable(Point::zero(), Point::new(63, 63))
+            .set_theme(&PlotTheme::dark());
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        plot.draw(&mut display).unwrap();
+        let pixels = (0..64).flat_map(|x| (0..64).map(move |y| Point::new(x, y)));
+        let colors = pixels.filter_map(|p| display.get_pixel(p));
+        assert!(colors.clone().any(|color| color == Rgb565::CSS_GOLD));
+        assert!(!colors.clone().any(|color| color == Rgb565::RED));
+    }
+
+    #[test]
+    fn margins_shrink_plot_bounds() {
+        let points = [PlotPoint { x: 0, y: 0 }, PlotPoint { x: 10, y: 10 }];
+        let curves = [(Curve::from_data(&points), BinaryColor::On)];
+        let plot = SinglePlot::new(&curves, Scale::Auto, Scale::Auto).unwrap();
+        let draw = |plot: crate::single_plot::DrawableSinglePlot<BinaryColor>| {
+            let mut display = MockDisplay::new();
+            display.set_allow_overdraw(true);
+            plot.draw(&mut display).unwrap();
+            display
+        };
+        let with_margins = draw(
+            plot.into_drawable(Point::zero(), Point::new(63, 63))
+                .set_margins(5, 6, 7, 8),
+        );
+        let bounds = Rectangle::with_corners(Point::new(8, 5), Point::new(57, 56));
+        let affected = with_margins.affected_area();
+        assert_eq!(bounds.intersection(&affected), affected);
+        assert_eq!(
+            with_margins,
+            draw(plot.into_drawable(Point::new(8, 5), Point::new(57, 56)))
+        );
+    }
+
+    #[test]
+    fn padding_keeps_curve_off_axes() {
+        let points = [PlotPoint { x: 0, y: 0 }, PlotPoint { x: 10, y: 10 }];
+        let curves = [(Curve::from_data(&points), Rgb565::RED)];
+        let plot = SinglePlot::new(&curves, Scale::Auto, Scale::Auto).unwrap();
+        let drawable = |padding| {
+            plot.into_drawable(Point::zero(), Point::new(63, 63))
+                .set_axis_color(Rgb565::WHITE)
+                .set_padding(padding)
+        };
+        let (top_left, bottom_right) = drawable(0).plot_area();
+        let padded = drawable(4);
+        assert_eq!(
+            padded.plot_area(),
+            (top_left + Point::new(4, 4), bottom_right - Point::new(4, 4))
+        );
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        padded.draw(&mut display).unwrap();
+        let (top_left, bottom_right) = padded.plot_area();
+        let area = Rectangle::with_corners(top_left, bottom_right);
+        let pixels = || display.bounding_box().points();
+        assert!(pixels()
+            .filter(|&p| display.get_pixel(p) == Some(Rgb565::RED))
+            .all(|p| area.contains(p)));
+        assert!(pixels()
+            .filter(|&p| display.get_pixel(p) == Some(Rgb565::WHITE))
+            .all(|p| !area.contains(p)));
+    }
+
+    #[test]
+    fn rotated_bounding_box() {
+        let points = [PlotPoint { x: 0, y: 0 }, PlotPoint { x: 10, y: 10 }];
+        let curves = [(Curve::from_data(&points), BinaryColor::On)];
+        let mut display = MockDisplay::<BinaryColor>::new();
+        let plot = SinglePlot::new(&curves, Scale::Auto, Scale::Auto)
+            .unwrap()
+            .into_drawable(Point::new(2, 2), Point::new(41, 21))
+            .set_rotation(Rotation::Deg90, display.bounding_box());
+        let bounds = plot.bounding_box();
+        assert_eq!(
+            bounds,
+            Rectangle::with_corners(Point::new(42, 2), Point::new(61, 41))
+        );
+        let rotated = Rotated::new(&mut display, Rotation::Deg90);
+        let area = Rectangle::with_corners(Point::new(2, 2), Point::new(41, 21));
+        assert_eq!(rotated.area_to_display(&area), bounds);
+    }
+
+    #[test]
+    fn rolling_window_y_axis_at_now() {
+        let points = [PlotPoint { x: 50, y: -5 }, PlotPoint { x: 90, y: 5 }];
+        let curves = [(Curve::from_data(&points), Rgb565::RED)];
+        let plot = SinglePlot::new(&curves, Scale::Auto, Scale::Auto)
+            .unwrap()
+            .set_rolling_window(100, 60)
+            .into_drawable(Point::zero(), Point::new(55, 63))
+            .set_axis_color(Rgb565::WHITE)
+            .set_axes_at_origin();
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        plot.draw(&mut display).unwrap();
+        // "now" is the right end of the window
+        let (_, bottom_right) = plot.plot_area();
+        let now = |y| {
+            let point = plot.data_to_screen(PlotPoint { x: 100, y }).unwrap();
+            display.get_pixel(Point::new(bottom_right.x, point.y))
+        };
+        assert_eq!(now(-4), Some(Rgb565::WHITE));
+        assert_eq!(now(4), Some(Rgb565::WHITE));
+    }
 }
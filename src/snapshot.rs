@@ -0,0 +1,142 @@
+//! Plots pre-rendered into in-memory pixel buffer, enabled with `snapshot` feature
+//!
+//! Static parts of the screen, e.g. axes, grid and decoration of a plot with live data, don't change between
+//! frames. [render] draws them once into caller-provided buffer, then the [Snapshot] is blitted onto
+//! the display each frame with single contiguous fill, which is much cheaper than drawing them again.
+use core::convert::Infallible;
+
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// Pixels of rectangular area of the display, row by row, kept in borrowed buffer
+pub struct Snapshot<'b, C>
+where
+    C: PixelColor,
+{
+    pixels: &'b mut [C],
+    area: Rectangle,
+}
+
+impl<'b, C> Snapshot<'b, C>
+where
+    C: PixelColor,
+{
+    /// create snapshot of given area of the display filled with background color,
+    /// buffer needs at least one pixel for each pixel of the area
+    pub fn new(
+        buffer: &'b mut [C],
+        area: Rectangle,
+        background: C,
+    ) -> Result<Snapshot<'b, C>, &'static str> {
+        let len = area.size.width as usize * area.size.height as usize;
+        if buffer.len() < len {
+            return Err("Buffer too small for the area");
+        }
+        let pixels = &mut buffer[..len];
+        pixels.iter_mut().for_each(|pixel| *pixel = background);
+        Ok(Snapshot { pixels, area })
+    }
+
+    /// rendered pixels, row by row
+    pub fn pixels(&self) -> &[C] {
+        self.pixels
+    }
+}
+
+/// render the drawable, e.g. plot or its decoration, into the buffer. Snapshot covers bounding box
+/// of the drawable, pixels not touched by it are left in background color.
+pub fn render<'b, C, T>(
+    drawable: &T,
+    buffer: &'b mut [C],
+    background: C,
+) -> Result<Snapshot<'b, C>, &'static str>
+where
+    C: PixelColor,
+    T: Drawable<Color = C> + Dimensions,
+{
+    let mut snapshot = Snapshot::new(buffer, drawable.bounding_box(), background)?;
+    // drawing into memory can't fail
+    drawable.draw(&mut snapshot).ok();
+    Ok(snapshot)
+}
+
+impl<'b, C> Dimensions for Snapshot<'b, C>
+where
+    C: PixelColor,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.area
+    }
+}
+
+impl<'b, C> DrawTarget for Snapshot<'b, C>
+where
+    C: PixelColor,
+{
+    type Color = C;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Infallible>
+    where
+        I: IntoIterator<Item = Pixel<C>>,
+    {
+        let width = self.area.size.width as i32;
+        for Pixel(point, color) in pixels {
+            if self.area.contains(point) {
+                let offset = point - self.area.top_left;
+                self.pixels[(offset.y * width + offset.x) as usize] = color;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'b, C> Drawable for Snapshot<'b, C>
+where
+    C: PixelColor,
+{
+    type Color = C;
+    /// area of the display covered by the snapshot
+    type Output = Rectangle;
+
+    /// blit the snapshot onto the display, at the position it was rendered at
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<Rectangle, D::Error> {
+        display.fill_contiguous(&self.area, self.pixels.iter().copied())?;
+        Ok(self.area)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gauge::Gauge;
+    use crate::snapshot::{render, Snapshot};
+    use embedded_graphics::{
+        mock_display::MockDisplay, pixelcolor::Rgb565, prelude::*, primitives::Rectangle,
+    };
+
+    #[test]
+    fn blit_matches_direct_drawing() {
+        let gauge = Gauge::new(40, 0..100)
+            .unwrap()
+            .into_drawable(Point::new(2, 2), Point::new(41, 21))
+            .set_color(Rgb565::GREEN)
+            .set_axis_color(Rgb565::WHITE);
+        let mut direct = MockDisplay::new();
+        direct.set_allow_overdraw(true);
+        gauge.draw(&mut direct).unwrap();
+        let mut buffer = [Rgb565::BLACK; 40 * 20];
+        let snapshot = render(&gauge, &mut buffer, Rgb565::BLACK).unwrap();
+        let mut blitted = MockDisplay::new();
+        assert_eq!(snapshot.draw(&mut blitted).unwrap(), gauge.bounding_box());
+        for point in gauge.bounding_box().points() {
+            let expected = direct.get_pixel(point).unwrap_or(Rgb565::BLACK);
+            assert_eq!(blitted.get_pixel(point), Some(expected));
+        }
+    }
+
+    #[test]
+    fn buffer_too_small() {
+        let mut buffer = [Rgb565::BLACK; 10];
+        let area = Rectangle::new(Point::zero(), Size::new(4, 4));
+        assert!(Snapshot::new(&mut buffer, area, Rgb565::BLACK).is_err());
+    }
+}
@@ -0,0 +1,460 @@
+//! Spectrum plot of FFT magnitude bins, in decibels over frequency
+//!
+//! Takes linear magnitudes as produced by FFT routine, converts them to dB relative to the strongest bin
+//! (or given reference) in integer math and labels the axes in Hz and dB - the glue needed for
+//! audio or vibration analysis. [PeakHold] keeps the loudest level of each bin across frames,
+//! drawn as fainter trace above the live one.
+use embedded_graphics::{
+    draw_target::DrawTargetExt, mono_font::MonoTextStyle, prelude::*, primitives::Rectangle,
+};
+
+use crate::axis::{Axis, Scale};
+use crate::curve::PlotPoint;
+use crate::dirty::DirtyTracker;
+use crate::edge_axes::EdgeAxes;
+use crate::placement::{area_drawable, IntoDrawableArea};
+use crate::transform::PlotTransform;
+
+/// dB level of the bottom of the plot if floor is not set
+const DEFAULT_FLOOR: i32 = -60;
+
+/// How magnitude bins are drawn
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SpectrumMode {
+    /// bins are connected with a line
+    Line,
+    /// each bin is drawn as a bar growing from the floor
+    Bars,
+}
+
+impl Default for SpectrumMode {
+    fn default() -> Self {
+        SpectrumMode::Line
+    }
+}
+
+/// Display-agnostic spectrum of FFT magnitude bins
+#[derive(Clone, Copy)]
+pub struct SpectrumPlot<'a> {
+    /// linear magnitudes of bins from 0 Hz up, usually first half of FFT output
+    magnitudes: &'a [u32],
+    sample_rate: u32,
+    fft_size: u32,
+    /// lowest level shown in dB, quieter bins are drawn at the floor
+    floor: Option<i32>,
+    /// magnitude corresponding to 0 dB, if not set, the strongest bin is used
+    reference: Option<u32>,
+}
+
+impl<'a> SpectrumPlot<'a> {
+    /// create spectrum of FFT of given size computed from samples taken with given rate (in Hz)
+    pub fn new(
+        magnitudes: &'a [u32],
+        sample_rate: u32,
+        fft_size: u32,
+    ) -> Result<SpectrumPlot<'a>, &'static str> {
+        if magnitudes.is_empty() {
+            Err("No bins provided")
+        } else if fft_size == 0 {
+            Err("FFT size must not be zero")
+        } else {
+            Ok(SpectrumPlot {
+                magnitudes,
+                sample_rate,
+                fft_size,
+                floor: None,
+                reference: None,
+            })
+        }
+    }
+
+    /// set lowest level shown in dB (negative), -60 dB if not set
+    pub fn set_floor(mut self, floor: i32) -> SpectrumPlot<'a> {
+        self.floor = Some(floor.min(-1));
+        self
+    }
+
+    /// set magnitude corresponding to 0 dB, e.g. full scale of the ADC, so levels are comparable between frames
+    pub fn set_reference(mut self, magnitude: u32) -> SpectrumPlot<'a> {
+        self.reference = Some(magnitude);
+        self
+    }
+
+    /// frequency of the bin in Hz
+    pub fn bin_frequency(&self, index: usize) -> i32 {
+        (index as u64 * self.sample_rate as u64 / self.fft_size as u64).min(i32::MAX as u64) as i32
+    }
+
+    /// level of the bin in dB relative to the reference, clamped to the floor
+    pub fn decibels(&self, index: usize) -> i32 {
+        let magnitude = self.magnitudes.get(index).copied().unwrap_or(0);
+        self.level(magnitude, self.reference())
+    }
+
+    /// bins as points - frequency in Hz and level in dB
+    pub fn points(&self) -> impl Iterator<Item = PlotPoint> + Clone + '_ {
+        let reference = self.reference();
+        self.magnitudes
+            .iter()
+            .enumerate()
+            .map(move |(index, magnitude)| PlotPoint {
+                x: self.bin_frequency(index),
+                y: self.level(*magnitude, reference),
+            })
+    }
+
+    /// magnitude corresponding to 0 dB
+    fn reference(&self) -> u32 {
+        self.reference
+            .unwrap_or_else(|| self.magnitudes.iter().copied().max().unwrap_or(0))
+    }
+
+    /// level of the magnitude in dB relative to the reference, clamped to the floor
+    fn level(&self, magnitude: u32, reference: u32) -> i32 {
+        let floor = self.floor.unwrap_or(DEFAULT_FLOOR);
+        if magnitude == 0 || reference == 0 {
+            return floor;
+        }
+        let difference = log2_fixed(magnitude) - log2_fixed(reference);
+        // 20 * log10(2) = 6.0206 dB per octave of magnitude
+        let divisor = 10000 << 16;
+        let decibels = (difference * 60206 + divisor / 2).div_euclid(divisor);
+        (decibels as i32).max(floor)
+    }
+
+    /// convert to drawable form for specific display
+    pub fn into_drawable<C>(
+        self,
+        top_left: Point,
+        bottom_right: Point,
+    ) -> DrawableSpectrumPlot<'a, C>
+    where
+        C: PixelColor + Default,
+    {
+        DrawableSpectrumPlot {
+            spectrum: self,
+            mode: None,
+            color: None,
+            axis_color: None,
+            text_style: None,
+            peaks: None,
+            top_left,
+            bottom_right,
+        }
+    }
+}
+
+impl<'a, C> IntoDrawableArea<DrawableSpectrumPlot<'a, C>> for SpectrumPlot<'a>
+where
+    C: PixelColor + Default,
+{
+    fn into_drawable_between(
+        self,
+        top_left: Point,
+        bottom_right: Point,
+    ) -> DrawableSpectrumPlot<'a, C> {
+        self.into_drawable(top_left, bottom_right)
+    }
+}
+
+/// Running maximum level of each bin across frames (max-hold), kept in user-provided buffer
+///
+/// Levels are only comparable between frames with fixed [reference](SpectrumPlot::set_reference).
+pub struct PeakHold<'a> {
+    peaks: &'a mut [i32],
+    /// number of bins updated since the last reset
+    len: usize,
+}
+
+impl<'a> PeakHold<'a> {
+    /// hold peaks of up to `buffer.len()` bins, higher bins are ignored
+    pub fn new(buffer: &'a mut [i32]) -> PeakHold<'a> {
+        PeakHold {
+            peaks: buffer,
+            len: 0,
+        }
+    }
+
+    /// raise held levels to levels of the new frame
+    pub fn update(&mut self, spectrum: &SpectrumPlot) {
+        let bins = spectrum.magnitudes.len().min(self.peaks.len());
+        for (index, peak) in self.peaks[..bins].iter_mut().enumerate() {
+            let level = spectrum.decibels(index);
+            *peak = if index < self.len {
+                (*peak).max(level)
+            } else {
+                level
+            };
+        }
+        self.len = self.len.max(bins);
+    }
+
+    /// forget held levels, e.g. after changing the reference or input
+    pub fn reset(&mut self) {
+        self.len = 0;
+    }
+
+    /// held levels in dB, from the lowest bin up
+    pub fn peaks(&self) -> &[i32] {
+        &self.peaks[..self.len]
+    }
+}
+
+/// base 2 logarithm of non-zero value in 16.16 fixed point
+fn log2_fixed(value: u32) -> i64 {
+    let integer = 31 - value.leading_zeros();
+    // value normalized to [1, 2) in 2.30 fixed point, each squaring yields next bit of the fraction
+    let mut mantissa = ((value as u64) << 30) >> integer;
+    let mut result = (integer as i64) << 16;
+    for bit in (0..16).rev() {
+        mantissa = (mantissa * mantissa) >> 30;
+        if mantissa >= 2 << 30 {
+            mantissa >>= 1;
+            result += 1 << bit;
+        }
+    }
+    result
+}
+
+/// Drawable spectrum plot, constructed for specific display
+pub struct DrawableSpectrumPlot<'a, C>
+where
+    C: PixelColor + Default,
+{
+    spectrum: SpectrumPlot<'a>,
+    mode: Option<SpectrumMode>,
+    color: Option<C>,
+    axis_color: Option<C>,
+    text_style: Option<MonoTextStyle<'a, C>>,
+    peaks: Option<(&'a [i32], C)>,
+    top_left: Point,
+    bottom_right: Point,
+}
+
+/// builder methods to modify plot decoration
+impl<'a, C> DrawableSpectrumPlot<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// set how bins are drawn
+    pub fn set_mode(mut self, mode: SpectrumMode) -> DrawableSpectrumPlot<'a, C> {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// set color of the spectrum line or bars
+    pub fn set_color(mut self, color: C) -> DrawableSpectrumPlot<'a, C> {
+        self.color = Some(color);
+        self
+    }
+
+    /// if not set, main color will be used
+    pub fn set_axis_color(mut self, color: C) -> DrawableSpectrumPlot<'a, C> {
+        self.axis_color = Some(color);
+        self
+    }
+
+    /// set style of tick labels and titles, if not set, axis color and 5x8 font are used
+    pub fn set_text_style(mut self, style: MonoTextStyle<'a, C>) -> DrawableSpectrumPlot<'a, C> {
+        self.text_style = Some(style);
+        self
+    }
+
+    /// draw held levels from [PeakHold::peaks], usually in dimmed main color (see [dim](crate::persistence::dim)) -
+    /// as a line behind the live spectrum, or as a mark drawn over each bar
+    pub fn set_peak_hold(mut self, peaks: &'a [i32], color: C) -> DrawableSpectrumPlot<'a, C> {
+        self.peaks = Some((peaks, color));
+        self
+    }
+
+    /// frequency axis along the bottom edge and level axis along the left edge
+    fn axes(&self) -> EdgeAxes<'a, C> {
+        let last_bin = self.spectrum.magnitudes.len() - 1;
+        EdgeAxes {
+            x_axis: Axis::new(0..self.spectrum.bin_frequency(last_bin).max(1))
+                .set_title("Hz")
+                .set_scale(Scale::Auto),
+            y_axis: Axis::new(self.spectrum.floor.unwrap_or(DEFAULT_FLOOR)..0)
+                .set_title("dB")
+                .set_scale(Scale::Auto),
+            color: self.axis_color.or(self.color).unwrap_or_default(),
+            text_style: self.text_style,
+        }
+    }
+
+    /// area where the spectrum is drawn - the plot bounds without space for labels
+    pub fn plot_area(&self) -> (Point, Point) {
+        self.axes().plot_area(self.top_left, self.bottom_right)
+    }
+}
+
+area_drawable!(DrawableSpectrumPlot);
+
+impl<'a, C> Drawable for DrawableSpectrumPlot<'a, C>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    /// bounding box of pixels touched by the plot, for flushing only changed region to the display
+    type Output = Rectangle;
+
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<Rectangle, D::Error> {
+        let display = &mut DirtyTracker::new(display);
+        let axes = self.axes();
+        let (top_left, bottom_right) = axes.plot_area(self.top_left, self.bottom_right);
+        let (x_axis, y_axis) = axes.place(top_left, bottom_right);
+        let floor = self.spectrum.floor.unwrap_or(DEFAULT_FLOOR);
+        let last_bin = self.spectrum.magnitudes.len() - 1;
+        let transform = match PlotTransform::new(
+            0..self.spectrum.bin_frequency(last_bin).max(1),
+            floor..0,
+            top_left,
+            bottom_right,
+        ) {
+            Ok(transform) => transform,
+            Err(_) => return Ok(display.dirty()),
+        };
+        let color = self.color.unwrap_or_default();
+        let bins = self.spectrum.magnitudes.len() as i32;
+        let width = bottom_right.x - top_left.x + 1;
+        // horizontal extent of n-th bar
+        let bar = |index: usize| {
+            let index = index as i32;
+            let x_start = top_left.x + width * index / bins;
+            let x_end = top_left.x + width * (index + 1) / bins - 2;
+            (x_start, x_end.max(x_start))
+        };
+        {
+            let mut clipped = display.clipped(&transform.area());
+            let peaks = self
+                .peaks
+                .map(|(peaks, peak_color)| (&peaks[..peaks.len().min(bins as usize)], peak_color));
+            match self.mode.unwrap_or_default() {
+                SpectrumMode::Line => {
+                    if let Some((peaks, peak_color)) = peaks {
+                        let points = peaks.iter().enumerate().map(|(index, level)| PlotPoint {
+                            x: self.spectrum.bin_frequency(index),
+                            y: *level,
+                        });
+                        transform
+                            .drawable_curve(points)
+                            .set_color(peak_color)
+                            .set_thickness(1)
+                            .draw(&mut clipped)?;
+                    }
+                    transform
+                        .drawable_curve(self.spectrum.points())
+                        .set_color(color)
+                        .set_thickness(1)
+                        .draw(&mut clipped)?;
+                }
+                SpectrumMode::Bars => {
+                    for (index, point) in self.spectrum.points().enumerate() {
+                        let (x_start, x_end) = bar(index);
+                        clipped.fill_solid(
+                            &Rectangle::with_corners(
+                                Point::new(x_start, transform.y_to_screen(point.y)),
+                                Point::new(x_end, bottom_right.y),
+                            ),
+                            color,
+                        )?;
+                    }
+                    // marks go over the bars, so peaks held at the live level stay visible
+                    if let Some((peaks, peak_color)) = peaks {
+                        for (index, level) in peaks.iter().enumerate() {
+                            let (x_start, x_end) = bar(index);
+                            let y = transform.y_to_screen(*level);
+                            clipped.fill_solid(
+                                &Rectangle::with_corners(
+                                    Point::new(x_start, y),
+                                    Point::new(x_end, y),
+                                ),
+                                peak_color,
+                            )?;
+                        }
+                    }
+                }
+            }
+        }
+        x_axis.draw(display)?;
+        y_axis.draw(display)?;
+        Ok(display.dirty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spectrum::{log2_fixed, PeakHold, SpectrumMode, SpectrumPlot};
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb565, prelude::*};
+    use test_case::test_case;
+
+    #[test_case(1 => 0; "one")]
+    #[test_case(2 => 1 << 16; "two")]
+    #[test_case(3 => 103872; "fraction")]
+    #[test_case(u32::MAX => (32 << 16) - 1; "largest")]
+    fn log2(value: u32) -> i64 {
+        log2_fixed(value)
+    }
+
+    const BINS: [u32; 4] = [1000, 100, 0, 1];
+
+    #[test_case(0 => 0; "reference")]
+    #[test_case(1 => -20; "tenth")]
+    #[test_case(2 => -60; "silence at floor")]
+    #[test_case(3 => -60; "clamped to floor")]
+    fn decibels(index: usize) -> i32 {
+        SpectrumPlot::new(&BINS, 8000, 8).unwrap().decibels(index)
+    }
+
+    #[test]
+    fn frequencies() {
+        let spectrum = SpectrumPlot::new(&BINS, 8000, 8).unwrap();
+        assert_eq!(spectrum.bin_frequency(3), 3000);
+        assert!(SpectrumPlot::new(&BINS, 8000, 0).is_err());
+    }
+
+    #[test]
+    fn peaks_held_across_frames() {
+        let mut buffer = [0; 8];
+        let mut peak_hold = PeakHold::new(&mut buffer);
+        for frame in [[1000, 100, 10], [10, 1000, 100]] {
+            let spectrum = SpectrumPlot::new(&frame, 8000, 8).unwrap();
+            peak_hold.update(&spectrum.set_reference(1000));
+        }
+        assert_eq!(peak_hold.peaks(), &[0, 0, -20]);
+        peak_hold.reset();
+        assert!(peak_hold.peaks().is_empty());
+    }
+
+    #[test]
+    fn peak_marks_above_bars() {
+        let live = [100, 1000];
+        let peaks = [0, 0];
+        let spectrum = SpectrumPlot::new(&live, 8000, 4)
+            .unwrap()
+            .set_reference(1000)
+            .into_drawable(Point::zero(), Point::new(40, 40))
+            .set_mode(SpectrumMode::Bars)
+            .set_color(Rgb565::GREEN)
+            .set_axis_color(Rgb565::WHITE)
+            .set_peak_hold(&peaks, Rgb565::RED);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        spectrum.draw(&mut display).unwrap();
+        let (top_left, _) = spectrum.plot_area();
+        // first bar is 20 dB below its held peak at the top of the plot
+        let mark = Point::new(top_left.x + 1, top_left.y);
+        assert_eq!(display.get_pixel(mark), Some(Rgb565::RED));
+        assert_eq!(display.get_pixel(mark + Point::new(0, 2)), None);
+        // second bar reaches its held peak, the mark stays on top of it
+        let (_, bottom_right) = spectrum.plot_area();
+        let mark = Point::new(bottom_right.x - 1, top_left.y);
+        assert_eq!(display.get_pixel(mark), Some(Rgb565::RED));
+        assert_eq!(
+            display.get_pixel(mark + Point::new(0, 2)),
+            Some(Rgb565::GREEN)
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use embedded_graphics::prelude::Point;
+
+/// largest number of pieces single segment of the curve is split into when smoothing
+pub(crate) const MAX_SUBDIVISIONS: u32 = 32;
+
+/// point `step`/`steps` of the way between `p1` and `p2` on the Catmull-Rom spline
+/// going through `p0`, `p1`, `p2` and `p3`, computed in integer math
+pub(crate) fn catmull_rom(
+    p0: Point,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    step: u32,
+    steps: u32,
+) -> Point {
+    let t = step as i64;
+    let n = steps.max(1) as i64;
+    let interpolate = |c0: i32, c1: i32, c2: i32, c3: i32| {
+        let (c0, c1, c2, c3) = (c0 as i64, c1 as i64, c2 as i64, c3 as i64);
+        // 2 * n^3 * P(t/n)
+        let value = 2 * c1 * n * n * n
+            + (c2 - c0) * t * n * n
+            + (2 * c0 - 5 * c1 + 4 * c2 - c3) * t * t * n
+            + (3 * c1 - c0 - 3 * c2 + c3) * t * t * t;
+        let divisor = 2 * n * n * n;
+        // round to the nearest pixel
+        (value + divisor / 2).div_euclid(divisor) as i32
+    };
+    Point::new(
+        interpolate(p0.x, p1.x, p2.x, p3.x),
+        interpolate(p0.y, p1.y, p2.y, p3.y),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::spline::catmull_rom;
+    use embedded_graphics::prelude::Point;
+    use test_case::test_case;
+
+    #[test_case(0 => Point::new(10, 10); "start")]
+    #[test_case(4 => Point::new(20, 20); "end")]
+    #[test_case(2 => Point::new(15, 15); "straight line stays straight")]
+    fn straight(step: u32) -> Point {
+        catmull_rom(
+            Point::new(0, 0),
+            Point::new(10, 10),
+            Point::new(20, 20),
+            Point::new(30, 30),
+            step,
+            4,
+        )
+    }
+
+    #[test]
+    fn overshoots_peak() {
+        // curve going up and back down is rounded above the sample
+        let point = catmull_rom(
+            Point::new(0, 0),
+            Point::new(10, 10),
+            Point::new(20, 10),
+            Point::new(30, 0),
+            2,
+            4,
+        );
+        assert_eq!(point, Point::new(15, 11));
+    }
+}
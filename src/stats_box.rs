@@ -0,0 +1,172 @@
+//! Scope-like statistics readout - minimum, maximum and mean of the visible part of the curve
+use core::fmt::Write;
+use heapless::String;
+
+use embedded_graphics::{
+    mono_font::{ascii::FONT_5X8, MonoTextStyle},
+    prelude::*,
+    primitives::{PrimitiveStyleBuilder, Rectangle},
+    text::{Baseline, Text},
+};
+
+use crate::curve::Curve;
+use crate::history::Summary;
+use crate::transform::PlotTransform;
+
+/// Corner of the plot area where the panel is placed
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Default for Corner {
+    fn default() -> Self {
+        Corner::TopRight
+    }
+}
+
+/// Display-agnostic statistics panel of the curve
+#[derive(Clone, Copy)]
+pub struct StatsBox<'a> {
+    curve: &'a Curve<'a>,
+    corner: Option<Corner>,
+}
+
+impl<'a> StatsBox<'a> {
+    pub fn new(curve: &'a Curve<'a>) -> StatsBox<'a> {
+        StatsBox {
+            curve,
+            corner: None,
+        }
+    }
+
+    /// set corner of the plot area where the panel is drawn, top right by default
+    pub fn set_corner(mut self, corner: Corner) -> StatsBox<'a> {
+        self.corner = Some(corner);
+        self
+    }
+
+    /// statistics of Y values of the points within X range of the transform, gaps are skipped
+    pub fn summary(&self, transform: &PlotTransform) -> Option<Summary> {
+        Summary::of(
+            self.curve
+                .iter()
+                .filter(|point| !point.is_gap() && transform.contains_x(point.x))
+                .map(|point| point.y),
+        )
+    }
+
+    /// compute statistics of the plotted window and turn them into drawable panel
+    /// placed in the area of provided transform
+    pub fn into_drawable<C>(self, transform: &PlotTransform) -> DrawableStatsBox<'a, C>
+    where
+        C: PixelColor + Default,
+    {
+        DrawableStatsBox {
+            summary: self.summary(transform),
+            corner: self.corner.unwrap_or_default(),
+            area: transform.area(),
+            color: None,
+            box_color: None,
+            text_style: None,
+        }
+    }
+}
+
+/// Drawable statistics panel, constructed for specific display
+pub struct DrawableStatsBox<'a, C>
+where
+    C: PixelColor + Default,
+{
+    summary: Option<Summary>,
+    corner: Corner,
+    area: Rectangle,
+    color: Option<C>,
+    box_color: Option<C>,
+    text_style: Option<MonoTextStyle<'a, C>>,
+}
+
+/// builder methods to modify panel decoration
+impl<'a, C> DrawableStatsBox<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// set color of the panel border, also used for the text if text style is not set
+    pub fn set_color(mut self, color: C) -> DrawableStatsBox<'a, C> {
+        self.color = Some(color);
+        self
+    }
+
+    /// set fill color of the panel, if not set, panel is transparent
+    pub fn set_box_color(mut self, color: C) -> DrawableStatsBox<'a, C> {
+        self.box_color = Some(color);
+        self
+    }
+
+    pub fn set_text_style(mut self, style: MonoTextStyle<'a, C>) -> DrawableStatsBox<'a, C> {
+        self.text_style = Some(style);
+        self
+    }
+}
+
+impl<'a, C> Drawable for DrawableStatsBox<'a, C>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    type Output = ();
+
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<(), D::Error> {
+        let summary = match self.summary {
+            Some(summary) => summary,
+            None => return Ok(()),
+        };
+        let color = self.color.unwrap_or_default();
+        let character_style = self
+            .text_style
+            .unwrap_or_else(|| MonoTextStyle::new(&FONT_5X8, color));
+
+        let mut buf: String<48> = String::new();
+        write!(
+            buf,
+            "min {}\nmax {}\navg {}",
+            summary.min, summary.max, summary.avg
+        )
+        .ok();
+        let mut text = Text::with_baseline(&buf, Point::zero(), character_style, Baseline::Top);
+        let size = text.bounding_box().size;
+        let area = self.area;
+        let bottom_right = area.top_left + area.size - Point::new(1, 1);
+        // text is kept 4 pixels away from the plot edge, so there is room for the panel border
+        let x = match self.corner {
+            Corner::TopLeft | Corner::BottomLeft => area.top_left.x + 4,
+            Corner::TopRight | Corner::BottomRight => bottom_right.x - 4 - size.width as i32,
+        };
+        let y = match self.corner {
+            Corner::TopLeft | Corner::TopRight => area.top_left.y + 4,
+            Corner::BottomLeft | Corner::BottomRight => bottom_right.y - 4 - size.height as i32,
+        };
+        text.position = Point { x, y };
+
+        let mut box_style = PrimitiveStyleBuilder::new()
+            .stroke_color(color)
+            .stroke_width(1);
+        if let Some(box_color) = self.box_color {
+            box_style = box_style.fill_color(box_color);
+        }
+        let text_area = text.bounding_box();
+        Rectangle::new(
+            text_area.top_left - Point::new(2, 2),
+            text_area.size + Size::new(4, 4),
+        )
+        .into_styled(box_style.build())
+        .draw(display)?;
+        text.draw(display)?;
+        Ok(())
+    }
+}
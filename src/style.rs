@@ -0,0 +1,290 @@
+//! Default decoration picked based on the color type of the display
+//!
+//! Monochromatic displays can't distinguish curves by color, so curves are told apart by dash patterns
+//! and drawn with 1 px lines, while color displays get a palette and thicker lines.
+//! It lets examples and quick prototypes look right on any display without manual tuning.
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Point, Size},
+    pixelcolor::{BinaryColor, PixelColor, Rgb565, Rgb888, RgbColor},
+    primitives::{Line, PointsIter, Primitive, PrimitiveStyle, Rectangle},
+    Drawable,
+};
+
+/// Dash pattern of the line, each bit corresponds to a single pixel, starting from the least significant one.
+/// Pattern repeats every 8 pixels.
+#[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinePattern(pub u8);
+
+impl LinePattern {
+    pub const SOLID: LinePattern = LinePattern(0xFF);
+    pub const DASHED: LinePattern = LinePattern(0x0F);
+    pub const DOTTED: LinePattern = LinePattern(0x55);
+    pub const DASH_DOT: LinePattern = LinePattern(0x27);
+
+    fn is_drawn(&self, pixel: u32) -> bool {
+        self.0 & (1 << (pixel % 8)) != 0
+    }
+}
+
+impl Default for LinePattern {
+    fn default() -> Self {
+        LinePattern::SOLID
+    }
+}
+
+/// draw line using dash pattern, `phase` is the position in the pattern and is advanced by drawn length,
+/// so the pattern continues smoothly on consecutive segments of the curve
+pub(crate) fn draw_patterned_line<D>(
+    display: &mut D,
+    line: Line,
+    pattern: LinePattern,
+    color: D::Color,
+    thickness: usize,
+    phase: &mut u32,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+{
+    let thickness = thickness.max(1) as u32;
+    let offset = (thickness / 2) as i32;
+    for point in line.points() {
+        if pattern.is_drawn(*phase) {
+            display.fill_solid(
+                &Rectangle::new(
+                    point - Point::new(offset, offset),
+                    Size::new(thickness, thickness),
+                ),
+                color,
+            )?;
+        }
+        *phase = phase.wrapping_add(1);
+    }
+    Ok(())
+}
+
+/// draw line with the same pixels as styled [Line] primitive, but with far fewer draw calls on displays
+/// that optimize rectangle fills (e.g. over SPI). Thin lines are drawn as runs of pixels, thick lines
+/// parallel to an axis as single fill, thick diagonal lines fall back to the styled primitive.
+pub(crate) fn draw_line_runs<D>(
+    display: &mut D,
+    line: Line,
+    color: D::Color,
+    thickness: usize,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+{
+    match thickness {
+        0 => Ok(()),
+        1 => fill_line_runs(display, line, color, 1),
+        _ if line.start.x == line.end.x || line.start.y == line.end.y => {
+            fill_line_runs(display, line, color, thickness)
+        }
+        _ => line
+            .into_styled(PrimitiveStyle::with_stroke(color, thickness as u32))
+            .draw(display),
+    }
+}
+
+/// draw line as runs of pixels along its main direction, each run being single `fill_solid`
+/// `thickness` pixels wide across the line. Thick diagonal lines get rougher edges than styled [Line].
+pub(crate) fn fill_line_runs<D>(
+    display: &mut D,
+    line: Line,
+    color: D::Color,
+    thickness: usize,
+) -> Result<(), D::Error>
+where
+    D: DrawTarget,
+{
+    let thickness = thickness as u32;
+    let delta = line.end - line.start;
+    let horizontal = delta.x.abs() >= delta.y.abs();
+    // same side as `Line` puts the extra pixel of even thickness on
+    let flipped = if horizontal { delta.x < 0 } else { delta.y > 0 };
+    let extra = (thickness % 2 == 0 && flipped) as i32;
+    let offset = (thickness / 2) as i32 - extra;
+    let draw_run = |display: &mut D, start: Point, end: Point| {
+        let top_left = start.component_min(end);
+        let length = (end - start).abs();
+        let area = if horizontal {
+            Rectangle::new(
+                top_left - Point::new(0, offset),
+                Size::new(length.x as u32 + 1, thickness),
+            )
+        } else {
+            Rectangle::new(
+                top_left - Point::new(offset, 0),
+                Size::new(thickness, length.y as u32 + 1),
+            )
+        };
+        display.fill_solid(&area, color)
+    };
+    // run being collected: its first and last point
+    let mut run: Option<(Point, Point)> = None;
+    for point in line.points() {
+        run = match run {
+            Some((start, _)) if horizontal && start.y == point.y => Some((start, point)),
+            Some((start, _)) if !horizontal && start.x == point.x => Some((start, point)),
+            Some((start, end)) => {
+                draw_run(display, start, end)?;
+                Some((point, point))
+            }
+            None => Some((point, point)),
+        };
+    }
+    if let Some((start, end)) = run {
+        draw_run(display, start, end)?;
+    }
+    Ok(())
+}
+
+/// Sensible decoration for specific color type
+pub trait DefaultStyle: PixelColor {
+    /// color used for axes, text and frame
+    fn foreground() -> Self;
+
+    /// color used to clear the display
+    fn background() -> Self;
+
+    /// color of the n-th curve on the plot
+    fn curve_color(index: usize) -> Self;
+
+    /// dash pattern of the n-th curve on the plot
+    fn curve_pattern(_index: usize) -> LinePattern {
+        LinePattern::SOLID
+    }
+
+    /// thickness of curve lines
+    fn curve_thickness() -> usize;
+
+    /// thickness of axis lines
+    fn axis_thickness() -> usize {
+        1
+    }
+}
+
+impl DefaultStyle for BinaryColor {
+    fn foreground() -> Self {
+        BinaryColor::On
+    }
+
+    fn background() -> Self {
+        BinaryColor::Off
+    }
+
+    fn curve_color(_index: usize) -> Self {
+        BinaryColor::On
+    }
+
+    fn curve_pattern(index: usize) -> LinePattern {
+        const PATTERNS: [LinePattern; 4] = [
+            LinePattern::SOLID,
+            LinePattern::DASHED,
+            LinePattern::DOTTED,
+            LinePattern::DASH_DOT,
+        ];
+        PATTERNS[index % PATTERNS.len()]
+    }
+
+    fn curve_thickness() -> usize {
+        1
+    }
+}
+
+macro_rules! rgb_default_style {
+    ($color:ty) => {
+        impl DefaultStyle for $color {
+            fn foreground() -> Self {
+                <$color>::WHITE
+            }
+
+            fn background() -> Self {
+                <$color>::BLACK
+            }
+
+            fn curve_color(index: usize) -> Self {
+                const PALETTE: [$color; 6] = [
+                    <$color>::YELLOW,
+                    <$color>::CYAN,
+                    <$color>::MAGENTA,
+                    <$color>::GREEN,
+                    <$color>::RED,
+                    <$color>::BLUE,
+                ];
+                PALETTE[index % PALETTE.len()]
+            }
+
+            fn curve_thickness() -> usize {
+                2
+            }
+        }
+    };
+}
+
+rgb_default_style!(Rgb565);
+rgb_default_style!(Rgb888);
+
+#[cfg(test)]
+mod tests {
+    use crate::style::draw_line_runs;
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        pixelcolor::BinaryColor,
+        prelude::*,
+        primitives::{Line, PrimitiveStyle},
+    };
+    use test_case::test_case;
+
+    #[test_case(Point::new(0, 0), Point::new(20, 7); "shallow")]
+    #[test_case(Point::new(3, 20), Point::new(9, 0); "steep upwards")]
+    #[test_case(Point::new(0, 5), Point::new(30, 5); "horizontal")]
+    fn runs_match_thin_line(start: Point, end: Point) {
+        let mut expected = MockDisplay::<BinaryColor>::new();
+        Line::new(start, end)
+            .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+            .draw(&mut expected)
+            .unwrap();
+        let mut display = MockDisplay::<BinaryColor>::new();
+        draw_line_runs(&mut display, Line::new(start, end), BinaryColor::On, 1).unwrap();
+        display.assert_eq(&expected);
+    }
+
+    #[test_case(1; "thin")]
+    #[test_case(2; "two pixels")]
+    #[test_case(3; "three pixels")]
+    #[test_case(4; "four pixels")]
+    fn runs_match_styled_line(thickness: usize) {
+        let lines = [
+            (Point::new(10, 5), Point::new(10, 25)),
+            (Point::new(12, 25), Point::new(12, 5)),
+            (Point::new(5, 10), Point::new(30, 10)),
+            (Point::new(30, 12), Point::new(5, 12)),
+            (Point::new(5, 5), Point::new(30, 20)),
+            (Point::new(30, 5), Point::new(20, 30)),
+            (Point::new(20, 20), Point::new(20, 20)),
+        ];
+        for (start, end) in lines.iter().copied() {
+            let mut expected = MockDisplay::<BinaryColor>::new();
+            Line::new(start, end)
+                .into_styled(PrimitiveStyle::with_stroke(
+                    BinaryColor::On,
+                    thickness as u32,
+                ))
+                .draw(&mut expected)
+                .unwrap();
+            let mut display = MockDisplay::<BinaryColor>::new();
+            draw_line_runs(
+                &mut display,
+                Line::new(start, end),
+                BinaryColor::On,
+                thickness,
+            )
+            .unwrap();
+            display.assert_eq(&expected);
+        }
+    }
+}
@@ -0,0 +1,311 @@
+//! Oscilloscope sweep mode, new samples overwrite the trace from left to right like on classic scopes and ECGs
+//!
+//! Instead of scrolling the whole trace on each sample, [Sweep] writes samples into fixed slots across the plot
+//! and wraps around at the right edge. Erase bar moving ahead of the newest sample separates the current sweep
+//! from the previous one, so [DrawableSweep::draw_update] only has to clear the bar and draw the new samples.
+use core::ops::Range;
+
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+use crate::curve::PlotPoint;
+use crate::dirty::DirtyTracker;
+use crate::transform::PlotTransform;
+
+/// N samples across the plot, n-th slot is drawn at X = n
+#[derive(Clone)]
+pub struct Sweep<const N: usize> {
+    samples: [i32; N],
+    /// slots filled so far, all of them after the first sweep
+    len: usize,
+    /// slot the next sample is written to
+    cursor: usize,
+    /// samples added since the last draw
+    pending: usize,
+}
+
+impl<const N: usize> Default for Sweep<N> {
+    fn default() -> Self {
+        Sweep::new()
+    }
+}
+
+impl<const N: usize> Sweep<N> {
+    pub fn new() -> Sweep<N> {
+        Sweep {
+            samples: [0; N],
+            len: 0,
+            cursor: 0,
+            pending: 0,
+        }
+    }
+
+    /// write the sample at the cursor and move the cursor right, back to the left edge after the last slot.
+    /// [PlotPoint::GAP] leaves the slot empty.
+    pub fn push(&mut self, value: i32) {
+        if N == 0 {
+            return;
+        }
+        self.samples[self.cursor] = value;
+        self.cursor = (self.cursor + 1) % N;
+        self.len = (self.len + 1).min(N);
+        self.pending = (self.pending + 1).min(N);
+    }
+
+    /// forget all samples and start the sweep from the left edge
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.cursor = 0;
+        self.pending = 0;
+    }
+
+    /// slot the next sample is written to
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// number of samples added since the last [Sweep::mark_drawn]
+    pub fn pending(&self) -> usize {
+        self.pending
+    }
+
+    /// note that pending samples were drawn, call after [DrawableSweep::draw_update]
+    pub fn mark_drawn(&mut self) {
+        self.pending = 0;
+    }
+
+    /// X range covering all the slots, for building the transform of the plot
+    pub fn x_range(&self) -> Range<i32> {
+        0..N.saturating_sub(1) as i32
+    }
+
+    /// turn samples into drawable object mapped by provided transform
+    pub fn into_drawable<'a, C>(&'a self, transform: &'a PlotTransform) -> DrawableSweep<'a, C, N>
+    where
+        C: PixelColor + Default,
+    {
+        DrawableSweep {
+            sweep: self,
+            transform,
+            color: None,
+            background_color: None,
+            thickness: None,
+            erase_width: None,
+        }
+    }
+
+    /// points of the slots, slots within the erase bar and not filled yet are gaps
+    fn points(
+        &self,
+        slots: Range<usize>,
+        erased: Range<usize>,
+    ) -> impl Iterator<Item = PlotPoint> + Clone + '_ {
+        slots.map(move |slot| {
+            if slot >= self.len || erased.contains(&slot) {
+                PlotPoint::gap(slot as i32)
+            } else {
+                PlotPoint {
+                    x: slot as i32,
+                    y: self.samples[slot],
+                }
+            }
+        })
+    }
+}
+
+/// Drawable sweep, constructed for specific display
+pub struct DrawableSweep<'a, C, const N: usize>
+where
+    C: PixelColor + Default,
+{
+    sweep: &'a Sweep<N>,
+    transform: &'a PlotTransform,
+    color: Option<C>,
+    background_color: Option<C>,
+    thickness: Option<usize>,
+    erase_width: Option<usize>,
+}
+
+/// builder methods to modify sweep decoration
+impl<'a, C, const N: usize> DrawableSweep<'a, C, N>
+where
+    C: PixelColor + Default,
+{
+    pub fn set_color(mut self, color: C) -> DrawableSweep<'a, C, N> {
+        self.color = Some(color);
+        self
+    }
+
+    /// set color the erase bar is filled with, e.g. color of the plot area - default color if not set
+    pub fn set_background_color(mut self, color: C) -> DrawableSweep<'a, C, N> {
+        self.background_color = Some(color);
+        self
+    }
+
+    pub fn set_thickness(mut self, thickness: usize) -> DrawableSweep<'a, C, N> {
+        self.thickness = Some(thickness);
+        self
+    }
+
+    /// set how many slots ahead of the cursor are erased, 1/16 of the slots if not set
+    pub fn set_erase_width(mut self, slots: usize) -> DrawableSweep<'a, C, N> {
+        self.erase_width = Some(slots);
+        self
+    }
+
+    /// slots covered by the erase bar, it doesn't wrap around so it never erases the current sweep
+    fn erased(&self) -> Range<usize> {
+        let width = self.erase_width.unwrap_or(N / 16).max(1);
+        self.sweep.cursor..(self.sweep.cursor + width).min(N)
+    }
+
+    fn draw_slots<D>(&self, display: &mut D, slots: Range<usize>) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        self.transform
+            .drawable_curve(self.sweep.points(slots, self.erased()))
+            .set_color(self.color.unwrap_or_default())
+            .set_thickness(self.thickness.unwrap_or(1))
+            .draw(display)?;
+        Ok(())
+    }
+
+    /// clear the erase bar and draw only samples added since the last [Sweep::mark_drawn], connected
+    /// to the ones drawn before. Whole plot area is redrawn when the erase bar would overtake
+    /// the samples drawn last time. Returns bounding box of touched pixels.
+    pub fn draw_update<D>(&self, display: &mut D) -> Result<Rectangle, D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let display = &mut DirtyTracker::new(display);
+        let background = self.background_color.unwrap_or_default();
+        let (pending, cursor) = (self.sweep.pending, self.sweep.cursor);
+        let erased = self.erased();
+        if pending + erased.len() >= N {
+            display.fill_solid(&self.transform.area(), background)?;
+            self.draw(display)?;
+            return Ok(display.dirty());
+        }
+        if !erased.is_empty() {
+            let area = self.transform.area();
+            let left = self.transform.x_to_screen(erased.start as i32);
+            // up to the first slot past the bar, erasing segments leading from the last erased slot
+            let right = if erased.end < N {
+                (self.transform.x_to_screen(erased.end as i32) - 1).max(left)
+            } else {
+                area.top_left.x + area.size.width as i32 - 1
+            };
+            let bar = Rectangle::with_corners(
+                Point::new(left, area.top_left.y),
+                Point::new(right, area.top_left.y + area.size.height as i32 - 1),
+            );
+            display.fill_solid(&bar, background)?;
+        }
+        // start one slot earlier to connect to the previous sample
+        if pending > cursor {
+            self.draw_slots(display, N - (pending - cursor) - 1..N)?;
+            self.draw_slots(display, 0..cursor)?;
+        } else if pending > 0 {
+            self.draw_slots(display, (cursor - pending).saturating_sub(1)..cursor)?;
+        }
+        Ok(display.dirty())
+    }
+}
+
+impl<'a, C, const N: usize> Drawable for DrawableSweep<'a, C, N>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    /// bounding box of pixels touched by the trace, for flushing only changed region to the display
+    type Output = Rectangle;
+
+    /// draw whole trace - current sweep left of the cursor and previous one right of the erase bar
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<Rectangle, D::Error> {
+        let display = &mut DirtyTracker::new(display);
+        self.draw_slots(display, 0..N)?;
+        Ok(display.dirty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sweep::Sweep;
+    use crate::transform::PlotTransform;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor, prelude::*};
+
+    #[test]
+    fn cursor_wraps_around() {
+        let mut sweep: Sweep<4> = Sweep::new();
+        for value in 0..6 {
+            sweep.push(value);
+        }
+        assert_eq!(sweep.cursor(), 2);
+        assert_eq!(sweep.pending(), 4);
+        sweep.mark_drawn();
+        assert_eq!(sweep.pending(), 0);
+    }
+
+    #[test]
+    fn new_sweep_overwrites_old_one() {
+        let mut sweep: Sweep<32> = Sweep::new();
+        let transform =
+            PlotTransform::new(sweep.x_range(), 0..10, Point::new(2, 2), Point::new(61, 22))
+                .unwrap();
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        // second sweep at different level, one sample per frame
+        for frame in 0..52 {
+            sweep.push(if frame < 32 { 2 } else { 8 });
+            sweep
+                .into_drawable(&transform)
+                .set_color(BinaryColor::On)
+                .set_background_color(BinaryColor::Off)
+                .set_erase_width(4)
+                .draw_update(&mut display)
+                .unwrap();
+            sweep.mark_drawn();
+        }
+        let (old, new) = (transform.y_to_screen(2), transform.y_to_screen(8));
+        let pixel =
+            |slot: i32, y: i32| display.get_pixel(Point::new(transform.x_to_screen(slot), y));
+        assert_eq!(pixel(5, new), Some(BinaryColor::On));
+        assert_eq!(pixel(5, old), Some(BinaryColor::Off));
+        // erase bar ahead of the cursor at slot 20, previous sweep behind it
+        assert_eq!(pixel(21, old), Some(BinaryColor::Off));
+        assert_eq!(pixel(25, old), Some(BinaryColor::On));
+    }
+
+    #[test]
+    fn default_erase_bar_clears_whole_segment() {
+        let mut sweep: Sweep<16> = Sweep::new();
+        let transform =
+            PlotTransform::new(sweep.x_range(), 0..10, Point::new(2, 2), Point::new(61, 22))
+                .unwrap();
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        for frame in 0..20 {
+            sweep.push(if frame < 16 { 2 } else { 8 });
+            sweep
+                .into_drawable(&transform)
+                .set_color(BinaryColor::On)
+                .set_background_color(BinaryColor::Off)
+                .draw_update(&mut display)
+                .unwrap();
+            sweep.mark_drawn();
+        }
+        // one slot wide bar at the cursor erases the old segment up to the next slot
+        let old = transform.y_to_screen(2);
+        let (start, end) = (transform.x_to_screen(4), transform.x_to_screen(5));
+        for x in start..end {
+            assert_eq!(
+                display.get_pixel(Point::new(x, old)),
+                Some(BinaryColor::Off)
+            );
+        }
+        assert_eq!(
+            display.get_pixel(Point::new(end, old)),
+            Some(BinaryColor::On)
+        );
+    }
+}
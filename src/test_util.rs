@@ -0,0 +1,87 @@
+//! Golden-pattern test helpers for layouts built with this crate, available with `test-util` feature
+//!
+//! Drawables are rendered onto [MockDisplay] and compared against expected pattern strings in the format
+//! of [MockDisplay::from_pattern], e.g. `"..##.."` rows for [BinaryColor](embedded_graphics::pixelcolor::BinaryColor).
+//! Plots overdraw their own pixels, e.g. curves over grid lines, so overdraw is allowed on rendered displays.
+//! Mock display covers 64x64 pixels, bigger layouts are checked window by window.
+use embedded_graphics::{
+    draw_target::DrawTargetExt, mock_display::ColorMapping, mock_display::MockDisplay, prelude::*,
+    primitives::Rectangle,
+};
+
+/// draw the drawable onto new mock display, panics if it draws outside of the display
+pub fn render<C, T>(drawable: &T) -> MockDisplay<C>
+where
+    C: PixelColor,
+    T: Drawable<Color = C>,
+{
+    let mut display = MockDisplay::new();
+    display.set_allow_overdraw(true);
+    // mock display doesn't fail, it panics on invalid drawing
+    drawable.draw(&mut display).ok();
+    display
+}
+
+/// draw 64x64 window of the drawable starting at given point, pixels outside of the window are skipped,
+/// so parts of layouts bigger than the mock display can be checked
+pub fn render_window<C, T>(drawable: &T, top_left: Point) -> MockDisplay<C>
+where
+    C: PixelColor,
+    T: Drawable<Color = C>,
+{
+    let mut display = MockDisplay::new();
+    display.set_allow_overdraw(true);
+    let window = Rectangle::new(top_left, display.size());
+    // mock display doesn't fail, it panics on invalid drawing
+    drawable
+        .draw(&mut display.translated(-top_left).clipped(&window))
+        .ok();
+    display
+}
+
+/// assert the drawable renders exactly the pattern, panics with diff of the displays otherwise
+pub fn assert_renders<C, T>(drawable: &T, pattern: &[&str])
+where
+    C: PixelColor + ColorMapping,
+    T: Drawable<Color = C>,
+{
+    render(drawable).assert_pattern(pattern);
+}
+
+/// assert 64x64 window of the drawable starting at given point renders exactly the pattern
+pub fn assert_window_renders<C, T>(drawable: &T, top_left: Point, pattern: &[&str])
+where
+    C: PixelColor + ColorMapping,
+    T: Drawable<Color = C>,
+{
+    render_window(drawable, top_left).assert_pattern(pattern);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gauge::Gauge;
+    use crate::test_util::{assert_renders, assert_window_renders, render};
+    use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+    #[test]
+    fn half_full_gauge() {
+        let gauge = Gauge::new(50, 0..100)
+            .unwrap()
+            .into_drawable(Point::new(1, 1), Point::new(10, 3))
+            .set_color(BinaryColor::On)
+            .set_track_color(BinaryColor::Off)
+            .set_ticks_hidden();
+        assert_renders(&gauge, &["           ", " #####.....", " #####....."]);
+        assert_window_renders(&gauge, Point::new(4, 1), &["##.....", "##....."]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn render_outside_of_display() {
+        let gauge = Gauge::new(50, 0..100)
+            .unwrap()
+            .into_drawable(Point::new(60, 1), Point::new(70, 3))
+            .set_color(BinaryColor::On);
+        render(&gauge);
+    }
+}
@@ -0,0 +1,111 @@
+//! Themes bundling colors of plot decoration
+//!
+//! [PlotTheme] is applied to a plot with [set_theme](crate::single_plot::DrawableSinglePlot::set_theme),
+//! so dashboards with many plots get consistent styling without repeating the same setters.
+//! Dark and light presets are provided for common color types.
+use embedded_graphics::pixelcolor::{BinaryColor, PixelColor, Rgb565, Rgb888, RgbColor, WebColors};
+
+/// Colors of the plot decoration and palette of the curves
+#[derive(Clone, Copy)]
+pub struct PlotTheme<'a, C>
+where
+    C: PixelColor,
+{
+    pub axis_color: C,
+    pub grid_color: C,
+    pub text_color: C,
+    pub background: C,
+    /// colors of the curves, n-th curve takes n-th color, palette repeats if there are more curves
+    pub palette: &'a [C],
+}
+
+impl<C> PlotTheme<'static, C>
+where
+    C: ThemePresets,
+{
+    /// preset with light decoration on dark background
+    pub fn dark() -> PlotTheme<'static, C> {
+        C::dark()
+    }
+
+    /// preset with dark decoration on light background
+    pub fn light() -> PlotTheme<'static, C> {
+        C::light()
+    }
+}
+
+/// Dark and light themes for the color type, used by [PlotTheme::dark] and [PlotTheme::light]
+pub trait ThemePresets: PixelColor {
+    fn dark() -> PlotTheme<'static, Self>;
+
+    fn light() -> PlotTheme<'static, Self>;
+}
+
+impl ThemePresets for BinaryColor {
+    /// lit pixels on dark background
+    fn dark() -> PlotTheme<'static, BinaryColor> {
+        PlotTheme {
+            axis_color: BinaryColor::On,
+            grid_color: BinaryColor::On,
+            text_color: BinaryColor::On,
+            background: BinaryColor::Off,
+            palette: &[BinaryColor::On],
+        }
+    }
+
+    /// inverted, dark pixels on lit background
+    fn light() -> PlotTheme<'static, BinaryColor> {
+        PlotTheme {
+            axis_color: BinaryColor::Off,
+            grid_color: BinaryColor::Off,
+            text_color: BinaryColor::Off,
+            background: BinaryColor::On,
+            palette: &[BinaryColor::Off],
+        }
+    }
+}
+
+macro_rules! rgb_themes {
+    ($color:ty) => {
+        impl ThemePresets for $color {
+            /// bright curves on black background, with dim grid
+            fn dark() -> PlotTheme<'static, $color> {
+                PlotTheme {
+                    axis_color: <$color>::CSS_LIGHT_GRAY,
+                    grid_color: <$color>::CSS_DIM_GRAY,
+                    text_color: <$color>::WHITE,
+                    background: <$color>::BLACK,
+                    palette: &[
+                        <$color>::CSS_GOLD,
+                        <$color>::CSS_DEEP_SKY_BLUE,
+                        <$color>::CSS_HOT_PINK,
+                        <$color>::CSS_LIME_GREEN,
+                        <$color>::CSS_ORANGE_RED,
+                        <$color>::CSS_MEDIUM_PURPLE,
+                    ],
+                }
+            }
+
+            /// saturated curves on white background, with light grid
+            fn light() -> PlotTheme<'static, $color> {
+                PlotTheme {
+                    axis_color: <$color>::CSS_DIM_GRAY,
+                    grid_color: <$color>::CSS_LIGHT_GRAY,
+                    text_color: <$color>::BLACK,
+                    background: <$color>::WHITE,
+                    palette: &[
+                        <$color>::CSS_ROYAL_BLUE,
+                        <$color>::CSS_CRIMSON,
+                        <$color>::CSS_FOREST_GREEN,
+                        <$color>::CSS_DARK_ORANGE,
+                        <$color>::CSS_DARK_VIOLET,
+                        <$color>::CSS_TEAL,
+                    ],
+                }
+            }
+        }
+    };
+}
+
+rgb_themes!(Rgb565);
+rgb_themes!(Rgb888);
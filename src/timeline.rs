@@ -0,0 +1,256 @@
+//! Gantt-style timeline of intervals, e.g. RTOS task scheduling trace
+//!
+//! Each task gets its own labeled row and every [TimelineEvent] is drawn as a horizontal bar in the row of its task,
+//! against a shared time axis. Events are clipped to the time window, so the window can scroll over a longer trace.
+use core::ops::Range;
+
+use embedded_graphics::{mono_font::MonoTextStyle, prelude::*, primitives::Rectangle};
+
+use crate::axis::{Axis, DrawableAxis, Scale};
+use crate::dirty::DirtyTracker;
+use crate::placement::{corners, IntoDrawableArea};
+use crate::range_conv::Scalable;
+use crate::rows::{row_bounds, LabeledRows};
+
+/// Interval during which the task was running
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimelineEvent {
+    /// index of the task in the task list of the timeline
+    pub task: usize,
+    pub start: i32,
+    pub end: i32,
+}
+
+/// Display-agnostic timeline of events of given tasks
+#[derive(Clone)]
+pub struct Timeline<'a> {
+    tasks: &'a [&'a str],
+    events: &'a [TimelineEvent],
+    time_range: Range<i32>,
+    scale: Option<Scale<'a>>,
+}
+
+impl<'a> Timeline<'a> {
+    /// create timeline showing the events within given time window,
+    /// events of tasks outside of the task list are skipped
+    pub fn new(
+        tasks: &'a [&'a str],
+        events: &'a [TimelineEvent],
+        time_range: Range<i32>,
+    ) -> Result<Timeline<'a>, &'static str> {
+        if tasks.is_empty() {
+            Err("No tasks")
+        } else if time_range.start >= time_range.end {
+            Err("Empty time range")
+        } else {
+            Ok(Timeline {
+                tasks,
+                events,
+                time_range,
+                scale: None,
+            })
+        }
+    }
+
+    /// set density of time axis ticks, [Scale::Auto] if not set
+    pub fn set_scale(mut self, scale: Scale<'a>) -> Timeline<'a> {
+        self.scale = Some(scale);
+        self
+    }
+
+    /// convert to drawable form for specific display
+    pub fn into_drawable<C>(self, top_left: Point, bottom_right: Point) -> DrawableTimeline<'a, C>
+    where
+        C: PixelColor + Default,
+    {
+        DrawableTimeline {
+            timeline: self,
+            color: None,
+            task_colors: &[],
+            axis_color: None,
+            text_style: None,
+            top_left,
+            bottom_right,
+        }
+    }
+}
+
+impl<'a, C> IntoDrawableArea<DrawableTimeline<'a, C>> for Timeline<'a>
+where
+    C: PixelColor + Default,
+{
+    fn into_drawable_between(
+        self,
+        top_left: Point,
+        bottom_right: Point,
+    ) -> DrawableTimeline<'a, C> {
+        self.into_drawable(top_left, bottom_right)
+    }
+}
+
+/// Drawable timeline, constructed for specific display
+pub struct DrawableTimeline<'a, C>
+where
+    C: PixelColor + Default,
+{
+    timeline: Timeline<'a>,
+    color: Option<C>,
+    task_colors: &'a [C],
+    axis_color: Option<C>,
+    text_style: Option<MonoTextStyle<'a, C>>,
+    top_left: Point,
+    bottom_right: Point,
+}
+
+/// builder methods to modify timeline decoration
+impl<'a, C> DrawableTimeline<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// set color of the bars
+    pub fn set_color(mut self, color: C) -> DrawableTimeline<'a, C> {
+        self.color = Some(color);
+        self
+    }
+
+    /// set color of bars of each task, tasks without their color use main color
+    pub fn set_task_colors(mut self, colors: &'a [C]) -> DrawableTimeline<'a, C> {
+        self.task_colors = colors;
+        self
+    }
+
+    /// if not set, main color will be used
+    pub fn set_axis_color(mut self, color: C) -> DrawableTimeline<'a, C> {
+        self.axis_color = Some(color);
+        self
+    }
+
+    /// set style of task and tick labels, if not set, axis color and 5x8 font are used
+    pub fn set_text_style(mut self, style: MonoTextStyle<'a, C>) -> DrawableTimeline<'a, C> {
+        self.text_style = Some(style);
+        self
+    }
+
+    fn labeled_rows(&self) -> LabeledRows<'a, C> {
+        LabeledRows {
+            top_left: self.top_left,
+            bottom_right: self.bottom_right,
+            axis_color: self.axis_color.or(self.color).unwrap_or_default(),
+            text_style: self.text_style,
+        }
+    }
+
+    /// time axis along the bottom edge and area of the rows
+    fn layout(&self) -> (DrawableAxis<'a, C>, Rectangle) {
+        let axis = Axis::new(self.timeline.time_range.clone())
+            .set_scale(self.timeline.scale.unwrap_or_default());
+        self.labeled_rows()
+            .layout(axis, self.timeline.tasks.iter().copied())
+    }
+
+    /// screen Y of the top and bottom edge of bars in n-th row
+    fn row(&self, rows: &Rectangle, task: usize) -> (i32, i32) {
+        row_bounds(rows, self.timeline.tasks.len(), task, 6)
+    }
+
+    /// screen X of the time, clipped to the time window
+    fn time_x(&self, rows: &Rectangle, time: i32) -> i32 {
+        let (left, right) = corners(rows);
+        let Range { start, end } = self.timeline.time_range;
+        time.clamp(start, end)
+            .scale_between_ranges(&self.timeline.time_range, &(left.x..right.x))
+    }
+}
+
+impl<'a, C> Dimensions for DrawableTimeline<'a, C>
+where
+    C: PixelColor + Default,
+{
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::with_corners(self.top_left, self.bottom_right)
+    }
+}
+
+impl<'a, C> Drawable for DrawableTimeline<'a, C>
+where
+    C: PixelColor + Default,
+{
+    type Color = C;
+    /// bounding box of pixels touched by the timeline, for flushing only changed region to the display
+    type Output = Rectangle;
+
+    fn draw<D: DrawTarget<Color = C>>(&self, display: &mut D) -> Result<Rectangle, D::Error> {
+        let display = &mut DirtyTracker::new(display);
+        let (axis, rows) = self.layout();
+        let Range { start, end } = self.timeline.time_range;
+        let color = self.color.unwrap_or_default();
+        let visible = self.timeline.events.iter().filter(|event| {
+            event.task < self.timeline.tasks.len() && event.start < end && event.end > start
+        });
+        for event in visible {
+            let (top, bottom) = self.row(&rows, event.task);
+            display.fill_solid(
+                &Rectangle::with_corners(
+                    Point::new(self.time_x(&rows, event.start), top),
+                    Point::new(self.time_x(&rows, event.end), bottom),
+                ),
+                self.task_colors.get(event.task).copied().unwrap_or(color),
+            )?;
+        }
+        #[cfg(feature = "text")]
+        self.labeled_rows()
+            .draw_labels(display, &rows, self.timeline.tasks.iter().copied())?;
+        axis.draw(display)?;
+        Ok(display.dirty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::timeline::{Timeline, TimelineEvent};
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb565, prelude::*};
+
+    #[test]
+    fn bars_in_task_rows() {
+        let tasks = ["idle", "net"];
+        let events = [
+            TimelineEvent {
+                task: 0,
+                start: 0,
+                end: 40,
+            },
+            TimelineEvent {
+                task: 1,
+                start: 60,
+                end: 150,
+            },
+            TimelineEvent {
+                task: 5,
+                start: 0,
+                end: 100,
+            },
+        ];
+        let colors = [Rgb565::GREEN, Rgb565::RED];
+        let timeline = Timeline::new(&tasks, &events, 0..100)
+            .unwrap()
+            .into_drawable(Point::zero(), Point::new(55, 63))
+            .set_task_colors(&colors)
+            .set_axis_color(Rgb565::WHITE);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        timeline.draw(&mut display).unwrap();
+        let (_, rows) = timeline.layout();
+        let (idle, _) = timeline.row(&rows, 0);
+        let (net, _) = timeline.row(&rows, 1);
+        let x = |time: i32| timeline.time_x(&rows, time);
+        assert_eq!(
+            display.get_pixel(Point::new(x(20), idle)),
+            Some(Rgb565::GREEN)
+        );
+        assert_eq!(display.get_pixel(Point::new(x(50), idle)), None);
+        assert_eq!(display.get_pixel(Point::new(x(99), net)), Some(Rgb565::RED));
+        assert_eq!(display.get_pixel(Point::new(x(50), net)), None);
+    }
+}
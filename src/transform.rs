@@ -0,0 +1,274 @@
+//! Mapping between data coordinates and display coordinates
+//!
+//! [PlotTransform] is used internally by curves and can be used by any external decoration
+//! (annotations, cursors, custom overlays) that has to be placed in data coordinates.
+use core::ops::Range;
+
+use embedded_graphics::{geometry::Point, pixelcolor::PixelColor, primitives::Rectangle};
+
+use crate::curve::{DrawableCurve, PlotPoint};
+use crate::range_conv::{ErrorAccumulator, Scalable};
+
+pub use crate::range_conv::Rounding;
+
+/// What happens to data points outside of the ranges of the transform
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OutOfRange {
+    /// segments are cut at the boundary of the plot area, so lines leave the area at the right angle
+    Clip,
+    /// points are moved onto the nearest edge of the ranges, e.g. saturated readings stick to the border
+    Clamp,
+    /// points are turned into gaps, together with the segments leading to them
+    Drop,
+}
+
+impl Default for OutOfRange {
+    fn default() -> Self {
+        OutOfRange::Clip
+    }
+}
+
+/// Maps data ranges onto rectangular area of the display, X grows to the right and Y grows upwards.
+/// Descending ranges (start > end) are allowed and invert the direction of the axis.
+#[derive(Clone)]
+pub struct PlotTransform {
+    x_range: Range<i32>,
+    y_range: Range<i32>,
+    top_left: Point,
+    bottom_right: Point,
+    rounding: Rounding,
+}
+
+impl PlotTransform {
+    /// create transform that maps `x_range` and `y_range` onto area bounded by provided points
+    pub fn new(
+        x_range: Range<i32>,
+        y_range: Range<i32>,
+        top_left: Point,
+        bottom_right: Point,
+    ) -> Result<PlotTransform, &'static str> {
+        if (top_left.x > bottom_right.x)
+            | (top_left.y > bottom_right.y)
+            | (x_range.start == x_range.end)
+            | (y_range.start == y_range.end)
+        {
+            return Err("Invalid range");
+        }
+        Ok(PlotTransform {
+            x_range,
+            y_range,
+            top_left,
+            bottom_right,
+            rounding: Rounding::default(),
+        })
+    }
+
+    /// set how mapped coordinates are rounded to whole pixels, truncated if not set
+    pub fn set_rounding(mut self, rounding: Rounding) -> PlotTransform {
+        self.rounding = rounding;
+        self
+    }
+
+    pub fn x_range(&self) -> &Range<i32> {
+        &self.x_range
+    }
+
+    pub fn y_range(&self) -> &Range<i32> {
+        &self.y_range
+    }
+
+    pub fn top_left(&self) -> Point {
+        self.top_left
+    }
+
+    pub fn bottom_right(&self) -> Point {
+        self.bottom_right
+    }
+
+    /// check if data X value lies within the range, including its end
+    pub fn contains_x(&self, x: i32) -> bool {
+        contains(&self.x_range, x)
+    }
+
+    /// check if data Y value lies within the range, including its end
+    pub fn contains_y(&self, y: i32) -> bool {
+        contains(&self.y_range, y)
+    }
+
+    /// apply the policy to the data point - clamped or dropped if it lies outside of the ranges.
+    /// Clipping happens when drawing, so the point is kept as is.
+    pub fn limit(&self, point: PlotPoint, policy: OutOfRange) -> PlotPoint {
+        if point.is_gap() || self.contains_x(point.x) && self.contains_y(point.y) {
+            return point;
+        }
+        match policy {
+            OutOfRange::Clip => point,
+            OutOfRange::Clamp => {
+                let clamp = |value: i32, range: &Range<i32>| {
+                    value.clamp(range.start.min(range.end), range.start.max(range.end))
+                };
+                PlotPoint {
+                    x: clamp(point.x, &self.x_range),
+                    y: clamp(point.y, &self.y_range),
+                }
+            }
+            OutOfRange::Drop => PlotPoint::gap(point.x),
+        }
+    }
+
+    /// area of the display that data ranges are mapped onto
+    pub fn area(&self) -> Rectangle {
+        Rectangle::with_corners(self.top_left, self.bottom_right)
+    }
+
+    /// screen column for data X value
+    pub fn x_to_screen(&self, x: i32) -> i32 {
+        x.scale_between_ranges_rounded(&self.x_range, &self.screen_x_range(), self.rounding)
+    }
+
+    /// screen row for data Y value
+    pub fn y_to_screen(&self, y: i32) -> i32 {
+        y.scale_between_ranges_rounded(&self.y_range, &self.screen_y_range(), self.rounding)
+    }
+
+    fn screen_x_range(&self) -> Range<i32> {
+        self.top_left.x..self.bottom_right.x
+    }
+
+    /// Y grows upwards, so the range goes from the bottom of the area
+    fn screen_y_range(&self) -> Range<i32> {
+        self.bottom_right.y..self.top_left.y
+    }
+
+    /// screen position of data point
+    pub fn to_screen(&self, point: &PlotPoint) -> Point {
+        Point {
+            x: self.x_to_screen(point.x),
+            y: self.y_to_screen(point.y),
+        }
+    }
+
+    /// data X value for screen column
+    pub fn x_to_data(&self, x: i32) -> i32 {
+        x.scale_between_ranges_rounded(&self.screen_x_range(), &self.x_range, self.rounding)
+    }
+
+    /// data Y value for screen row
+    pub fn y_to_data(&self, y: i32) -> i32 {
+        y.scale_between_ranges_rounded(&self.screen_y_range(), &self.y_range, self.rounding)
+    }
+
+    /// data point at screen position, e.g. for handling touch input
+    pub fn to_data(&self, point: Point) -> PlotPoint {
+        PlotPoint {
+            x: self.x_to_data(point.x),
+            y: self.y_to_data(point.y),
+        }
+    }
+
+    /// lazily map data points to screen positions, gaps are mapped to `None`.
+    /// With [Rounding::Accumulated] rounding error of each point is carried into the next one.
+    pub fn map_points<'t, I>(
+        &'t self,
+        points: I,
+    ) -> impl Iterator<Item = Option<Point>> + Clone + 't
+    where
+        I: Iterator<Item = PlotPoint> + Clone + 't,
+    {
+        let accumulated = self.rounding == Rounding::Accumulated;
+        let errors = (ErrorAccumulator::default(), ErrorAccumulator::default());
+        points.scan(errors, move |(x_error, y_error), p| {
+            Some(if p.is_gap() {
+                None
+            } else if accumulated {
+                Some(Point {
+                    x: x_error.scale(p.x, &self.x_range, &self.screen_x_range()),
+                    y: y_error.scale(p.y, &self.y_range, &self.screen_y_range()),
+                })
+            } else {
+                Some(self.to_screen(&p))
+            })
+        })
+    }
+
+    /// create drawable curve from data points mapped by this transform
+    pub fn drawable_curve<'t, C, I>(
+        &'t self,
+        points: I,
+    ) -> DrawableCurve<C, impl Iterator<Item = Option<Point>> + Clone + 't>
+    where
+        C: PixelColor,
+        I: Iterator<Item = PlotPoint> + Clone + 't,
+    {
+        DrawableCurve::new(self.map_points(points))
+    }
+}
+
+/// check if value lies between range bounds, regardless of the range direction
+fn contains(range: &Range<i32>, value: i32) -> bool {
+    value >= range.start.min(range.end) && value <= range.start.max(range.end)
+}
+
+#[cfg(test)]
+#[allow(clippy::reversed_empty_ranges)]
+mod tests {
+    use crate::curve::PlotPoint;
+    use crate::transform::{OutOfRange, PlotTransform, Rounding};
+    use embedded_graphics::geometry::Point;
+    use heapless::Vec;
+    use test_case::test_case;
+
+    fn transform() -> PlotTransform {
+        PlotTransform::new(0..100, -50..50, Point::new(10, 20), Point::new(110, 120)).unwrap()
+    }
+
+    #[test_case(0, -50 => Point::new(10, 120); "origin in bottom left")]
+    #[test_case(100, 50 => Point::new(110, 20); "top right")]
+    #[test_case(50, 0 => Point::new(60, 70); "center")]
+    fn to_screen(x: i32, y: i32) -> Point {
+        transform().to_screen(&PlotPoint { x, y })
+    }
+
+    #[test_case(Point::new(10, 120) => (0, -50); "bottom left")]
+    #[test_case(Point::new(60, 70) => (50, 0); "center")]
+    fn to_data(point: Point) -> (i32, i32) {
+        let p = transform().to_data(point);
+        (p.x, p.y)
+    }
+
+    #[test_case(OutOfRange::Clip => (150, 70); "clip")]
+    #[test_case(OutOfRange::Clamp => (100, 50); "clamp")]
+    #[test_case(OutOfRange::Drop => (150, PlotPoint::GAP); "drop")]
+    fn limit(policy: OutOfRange) -> (i32, i32) {
+        let p = transform().limit(PlotPoint { x: 150, y: 70 }, policy);
+        (p.x, p.y)
+    }
+
+    #[test]
+    fn accumulated_rounding_dithers_between_pixels() {
+        // value sits at row 6.6, so it's drawn on row 7 and every other time on row 6
+        let t = PlotTransform::new(0..10, 0..100, Point::new(0, 0), Point::new(10, 10))
+            .unwrap()
+            .set_rounding(Rounding::Accumulated);
+        let points = (0..5).map(|x| PlotPoint { x, y: 34 });
+        let rows: Vec<i32, 5> = t.map_points(points).map(|p| p.unwrap().y).collect();
+        assert_eq!(&rows[..], &[7, 6, 7, 6, 7]);
+        assert_eq!(t.y_to_screen(34), 7);
+    }
+
+    #[test]
+    fn inverted_y() {
+        let t = PlotTransform::new(0..10, 100..0, Point::new(0, 0), Point::new(10, 100)).unwrap();
+        assert_eq!(t.to_screen(&PlotPoint { x: 0, y: 0 }), Point::new(0, 0));
+        assert_eq!(t.to_screen(&PlotPoint { x: 0, y: 100 }), Point::new(0, 100));
+        assert!(t.contains_y(30));
+    }
+
+    #[test]
+    fn invalid_area() {
+        assert!(PlotTransform::new(0..10, 0..10, Point::new(10, 10), Point::new(0, 0)).is_err());
+        assert!(PlotTransform::new(0..0, 0..10, Point::new(0, 0), Point::new(10, 10)).is_err());
+    }
+}
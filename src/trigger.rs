@@ -0,0 +1,165 @@
+//! Oscilloscope-style trigger picking stable window of streaming samples
+//!
+//! Periodic waveform drawn from a ring buffer of samples drifts from frame to frame, because the buffer
+//! starts at a random phase. [Trigger] finds the level crossing in the buffer and selects the window
+//! aligned to it, so the waveform stands still like on a scope.
+use core::ops::Range;
+
+use crate::curve::PlotPoint;
+
+/// Direction of the level crossing that fires the trigger
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Edge {
+    /// signal goes up through the level
+    Rising,
+    /// signal goes down through the level
+    Falling,
+}
+
+impl Default for Edge {
+    fn default() -> Self {
+        Edge::Rising
+    }
+}
+
+/// Trigger firing when the signal crosses given level
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trigger {
+    level: i32,
+    edge: Edge,
+    /// how far the signal has to get to the other side of the level to re-arm the trigger
+    hysteresis: Option<i32>,
+    /// number of samples shown before the trigger point
+    pre_trigger: Option<usize>,
+}
+
+impl Trigger {
+    pub fn new(level: i32, edge: Edge) -> Trigger {
+        Trigger {
+            level,
+            edge,
+            hysteresis: None,
+            pre_trigger: None,
+        }
+    }
+
+    /// require the signal to get this far from the level before it can trigger again,
+    /// so noise around the level doesn't fire the trigger. No hysteresis if not set.
+    pub fn set_hysteresis(mut self, hysteresis: i32) -> Trigger {
+        self.hysteresis = Some(hysteresis.max(0));
+        self
+    }
+
+    /// show given number of samples before the trigger point, the trigger is at the left edge if not set
+    pub fn set_pre_trigger(mut self, samples: usize) -> Trigger {
+        self.pre_trigger = Some(samples);
+        self
+    }
+
+    /// index of the latest sample firing the trigger that leaves room for full window of given width
+    pub fn find<I>(&self, samples: I, width: usize) -> Option<usize>
+    where
+        I: ExactSizeIterator<Item = i32>,
+    {
+        let len = samples.len();
+        let pre_trigger = self.pre_trigger.unwrap_or(0);
+        let hysteresis = self.hysteresis.unwrap_or(0);
+        let mut armed = false;
+        let mut found = None;
+        for (index, sample) in samples.enumerate() {
+            let (below, crossed) = match self.edge {
+                Edge::Rising => (
+                    sample < self.level.saturating_sub(hysteresis),
+                    sample >= self.level,
+                ),
+                Edge::Falling => (
+                    sample > self.level.saturating_add(hysteresis),
+                    sample <= self.level,
+                ),
+            };
+            if below {
+                armed = true;
+            } else if armed && crossed {
+                armed = false;
+                if index >= pre_trigger && index - pre_trigger + width <= len {
+                    found = Some(index);
+                }
+            }
+        }
+        found
+    }
+
+    /// indices of the window of given width aligned to the trigger, if the trigger doesn't fire,
+    /// the latest samples are shown, like auto mode of a scope
+    pub fn window<I>(&self, samples: I, width: usize) -> Range<usize>
+    where
+        I: ExactSizeIterator<Item = i32>,
+    {
+        let len = samples.len();
+        let start = match self.find(samples, width) {
+            Some(index) => index - self.pre_trigger.unwrap_or(0),
+            None => len.saturating_sub(width),
+        };
+        start..(start + width).min(len)
+    }
+
+    /// samples of the window as points, X being the distance from the trigger point -
+    /// with fixed X range of the curve, the waveform stays in place between frames
+    pub fn points<I>(&self, samples: I, width: usize) -> impl Iterator<Item = PlotPoint> + Clone
+    where
+        I: ExactSizeIterator<Item = i32> + Clone,
+    {
+        let window = self.window(samples.clone(), width);
+        let pre_trigger = self.pre_trigger.unwrap_or(0) as i32;
+        samples
+            .skip(window.start)
+            .take(window.len())
+            .enumerate()
+            .map(move |(index, y)| PlotPoint {
+                x: index as i32 - pre_trigger,
+                y,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trigger::{Edge, Trigger};
+    use core::ops::Range;
+    use test_case::test_case;
+
+    const SAMPLES: [i32; 12] = [5, 0, 5, 10, 5, 0, 5, 10, 5, 0, 5, 10];
+
+    #[test_case(Trigger::new(5, Edge::Rising), 4 => 6..10; "latest rising edge")]
+    #[test_case(Trigger::new(5, Edge::Falling), 4 => 8..12; "falling edge")]
+    #[test_case(Trigger::new(5, Edge::Rising).set_pre_trigger(2), 4 => 8..12; "pre-trigger")]
+    #[test_case(Trigger::new(5, Edge::Rising), 8 => 2..10; "wide window")]
+    #[test_case(Trigger::new(20, Edge::Rising), 4 => 8..12; "auto mode")]
+    fn window(trigger: Trigger, width: usize) -> Range<usize> {
+        trigger.window(SAMPLES.iter().copied(), width)
+    }
+
+    #[test]
+    fn hysteresis() {
+        let noisy = [0, 4, 6, 4, 6, 10, 0, 0];
+        let trigger = Trigger::new(5, Edge::Rising);
+        assert_eq!(trigger.find(noisy.iter().copied(), 2), Some(4));
+        assert_eq!(
+            trigger.set_hysteresis(2).find(noisy.iter().copied(), 2),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn hysteresis_at_the_limits() {
+        let samples = [i32::MIN, i32::MAX, i32::MIN, i32::MAX];
+        let rising = Trigger::new(0, Edge::Rising).set_hysteresis(i32::MAX);
+        assert_eq!(rising.find(samples.iter().copied(), 1), Some(3));
+        let falling = Trigger::new(0, Edge::Falling).set_hysteresis(i32::MAX);
+        assert_eq!(falling.find(samples.iter().copied(), 1), None);
+    }
+}
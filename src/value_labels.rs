@@ -0,0 +1,177 @@
+//! Numeric values printed next to data points, for sparse line charts on dashboards
+//!
+//! [ValueLabels] writes Y value of each (or every n-th) point of the curve above it. Label that would overlap
+//! the previous one or reach outside of the plot area is moved below the point, and skipped if it doesn't fit
+//! there either, so dense data doesn't turn into a smudge. Labels are drawn as [PlotOverlay] of the plot.
+use core::fmt::Write;
+
+use embedded_graphics::{
+    mono_font::{ascii::FONT_5X8, MonoTextStyle},
+    prelude::*,
+    primitives::Rectangle,
+    text::{Baseline, Text},
+};
+use heapless::String;
+
+use crate::axis::LabelFormatter;
+use crate::curve::{Curve, CurvePoints};
+use crate::overlay::{OverlayError, OverlayTarget, PlotOverlay};
+use crate::transform::PlotTransform;
+
+/// distance between the point and its label, in pixels
+const LABEL_GAP: i32 = 2;
+
+/// Y values of the curve points, drawn next to the points
+#[derive(Clone)]
+pub struct ValueLabels<'a, C>
+where
+    C: PixelColor + Default,
+{
+    points: CurvePoints<'a>,
+    every: Option<usize>,
+    color: Option<C>,
+    text_style: Option<MonoTextStyle<'a, C>>,
+    label_formatter: Option<LabelFormatter>,
+}
+
+/// builder methods to modify labels
+impl<'a, C> ValueLabels<'a, C>
+where
+    C: PixelColor + Default,
+{
+    /// label every point of the curve
+    pub fn new(curve: &Curve<'a>) -> ValueLabels<'a, C> {
+        ValueLabels {
+            points: curve.iter(),
+            every: None,
+            color: None,
+            text_style: None,
+            label_formatter: None,
+        }
+    }
+
+    /// label only every n-th point, starting with the first one
+    pub fn set_every(mut self, n: usize) -> ValueLabels<'a, C> {
+        self.every = Some(n.max(1));
+        self
+    }
+
+    /// set color of the labels, used if text style is not set
+    pub fn set_color(mut self, color: C) -> ValueLabels<'a, C> {
+        self.color = Some(color);
+        self
+    }
+
+    /// set style of the labels, if not set, label color and 5x8 font are used
+    pub fn set_text_style(mut self, style: MonoTextStyle<'a, C>) -> ValueLabels<'a, C> {
+        self.text_style = Some(style);
+        self
+    }
+
+    /// set how values are turned into labels, e.g. `float_format::format_tenths` with `float` feature
+    pub fn set_label_formatter(mut self, formatter: LabelFormatter) -> ValueLabels<'a, C> {
+        self.label_formatter = Some(formatter);
+        self
+    }
+
+    /// draw the labels with given coordinate mapping, only within its area
+    pub fn draw_with<D>(&self, transform: &PlotTransform, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let style = self
+            .text_style
+            .unwrap_or_else(|| MonoTextStyle::new(&FONT_5X8, self.color.unwrap_or_default()));
+        let area = transform.area();
+        let mut previous = None;
+        let every = self.every.unwrap_or(1);
+        let labeled = self.points.clone().step_by(every).filter(|p| !p.is_gap());
+        for point in labeled {
+            let text = format_value(point.y, self.label_formatter);
+            let text = Text::with_baseline(&text, Point::zero(), style, Baseline::Top);
+            let size = text.bounding_box().size;
+            let anchor = transform.to_screen(&point);
+            if let Some(label) = place_label(anchor, size, false, &area, previous) {
+                text.translate(label.top_left).draw(display)?;
+                previous = Some(label);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a, C> PlotOverlay<C> for ValueLabels<'a, C>
+where
+    C: PixelColor + Default,
+{
+    fn draw(
+        &self,
+        transform: &PlotTransform,
+        target: &mut OverlayTarget<'_, C>,
+    ) -> Result<(), OverlayError> {
+        self.draw_with(transform, target)
+    }
+}
+
+/// value written with the formatter, or as plain integer
+pub(crate) fn format_value(value: i32, formatter: Option<LabelFormatter>) -> String<11> {
+    let mut text = String::new();
+    match formatter {
+        Some(formatter) => formatter(value, &mut text).ok(),
+        None => write!(text, "{}", value).ok(),
+    };
+    text
+}
+
+/// area of label of given size centered above the anchor point, or below it if it's `below_first`.
+/// If it would reach outside of the area or overlap the previous label, the other side is tried,
+/// none if the label doesn't fit on either side.
+pub(crate) fn place_label(
+    anchor: Point,
+    size: Size,
+    below_first: bool,
+    area: &Rectangle,
+    previous: Option<Rectangle>,
+) -> Option<Rectangle> {
+    let x = anchor.x - size.width as i32 / 2;
+    let above = Rectangle::new(
+        Point::new(x, anchor.y - LABEL_GAP - size.height as i32),
+        size,
+    );
+    let below = Rectangle::new(Point::new(x, anchor.y + LABEL_GAP + 1), size);
+    let (first, second) = if below_first {
+        (below, above)
+    } else {
+        (above, below)
+    };
+    let fits = |label: &Rectangle| {
+        let inside = area.intersection(label) == *label;
+        let overlaps = previous
+            .iter()
+            .any(|previous| !previous.intersection(label).is_zero_sized());
+        inside && !overlaps
+    };
+    [first, second].iter().copied().find(fits)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::value_labels::place_label;
+    use embedded_graphics::{prelude::*, primitives::Rectangle};
+    use test_case::test_case;
+
+    const AREA: Rectangle = Rectangle::new(Point::new(0, 0), Size::new(40, 40));
+    const SIZE: Size = Size::new(10, 8);
+
+    #[test_case(Point::new(20, 20), None => Some(Point::new(15, 10)); "above")]
+    #[test_case(Point::new(20, 5), None => Some(Point::new(15, 8)); "below at the top edge")]
+    #[test_case(
+        Point::new(22, 20),
+        Some(Rectangle::new(Point::new(15, 10), SIZE)) => Some(Point::new(17, 23));
+        "below the previous one"
+    )]
+    #[test_case(Point::new(38, 20), None => None; "out of the area")]
+    fn label_placement(anchor: Point, previous: Option<Rectangle>) -> Option<Point> {
+        place_label(anchor, SIZE, false, &AREA, previous).map(|label| label.top_left)
+    }
+}
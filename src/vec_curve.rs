@@ -0,0 +1,132 @@
+//! Curves and plots owning their data on the heap, available with `alloc` feature
+//!
+//! Host-side tools and embedded Linux applications can keep plot data in [VecCurve] and [VecPlot]
+//! without fixed capacities or borrowed slices outliving the plot.
+use alloc::vec::Vec;
+
+use embedded_graphics::pixelcolor::PixelColor;
+
+use crate::axis::Scale;
+use crate::curve::{Curve, CurveBuffer, PlotPoint};
+use crate::single_plot::SinglePlot;
+use crate::viewport::Viewport;
+
+/// curve data stored in growable vector
+pub type VecCurve = CurveBuffer<Vec<PlotPoint>>;
+
+impl VecCurve {
+    /// add point at the end of the curve
+    pub fn push(&mut self, point: PlotPoint) {
+        self.storage_mut().push(point);
+    }
+
+    /// remove all points, manual ranges are kept
+    pub fn clear(&mut self) {
+        self.storage_mut().clear();
+    }
+}
+
+impl From<Vec<PlotPoint>> for VecCurve {
+    fn from(points: Vec<PlotPoint>) -> VecCurve {
+        VecCurve::from_storage(points)
+    }
+}
+
+/// plot owning its curves, borrowed as [SinglePlot] for drawing
+#[derive(Clone)]
+pub struct VecPlot<C>
+where
+    C: PixelColor + Default,
+{
+    curves: Vec<(VecCurve, C)>,
+    x_scale: Scale<'static>,
+    y_scale: Scale<'static>,
+    viewport: Option<Viewport>,
+}
+
+impl<C> VecPlot<C>
+where
+    C: PixelColor + Default,
+{
+    /// create plot without curves
+    pub fn new(x_scale: Scale<'static>, y_scale: Scale<'static>) -> VecPlot<C> {
+        VecPlot {
+            curves: Vec::new(),
+            x_scale,
+            y_scale,
+            viewport: None,
+        }
+    }
+
+    /// add curve drawn with given color
+    pub fn push_curve(&mut self, curve: VecCurve, color: C) {
+        self.curves.push((curve, color));
+    }
+
+    /// curves of the plot with their colors, e.g. to push new samples
+    pub fn curves_mut(&mut self) -> &mut Vec<(VecCurve, C)> {
+        &mut self.curves
+    }
+
+    /// set visible part of the data, see [SinglePlot::set_viewport]
+    pub fn set_viewport(&mut self, viewport: Viewport) {
+        self.viewport = Some(viewport);
+    }
+
+    /// borrow the data as [SinglePlot] for the duration of the closure, e.g. to draw it:
+    /// `plot.with_plot(|plot| plot.into_drawable(top_left, bottom_right).draw(&mut display))`
+    pub fn with_plot<R>(&self, f: impl FnOnce(SinglePlot<'_, C>) -> R) -> Result<R, &'static str> {
+        let curves: Vec<(Curve, C)> = self
+            .curves
+            .iter()
+            .map(|(curve, color)| (curve.as_curve(), *color))
+            .collect();
+        let mut plot = SinglePlot::new(&curves, self.x_scale, self.y_scale)?;
+        if let Some(viewport) = self.viewport {
+            plot = plot.set_viewport(viewport);
+        }
+        Ok(f(plot))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::axis::Scale;
+    use crate::curve::PlotPoint;
+    use crate::vec_curve::{VecCurve, VecPlot};
+    use alloc::vec;
+    use embedded_graphics::pixelcolor::BinaryColor;
+
+    #[test]
+    fn grows_past_any_capacity() {
+        let mut curve = VecCurve::new();
+        for x in 0..100 {
+            curve.push(PlotPoint { x, y: x % 7 });
+        }
+        assert_eq!(curve.len(), 100);
+        let borrowed = curve.as_curve();
+        assert_eq!((borrowed.x_range, borrowed.y_range), (0..99, 0..6));
+        curve.clear();
+        assert!(curve.is_empty());
+    }
+
+    #[test]
+    fn manual_ranges_survive_clear() {
+        let mut curve = VecCurve::with_ranges(0..10, -5..5);
+        curve.push(PlotPoint { x: 20, y: 20 });
+        curve.clear();
+        curve.push(PlotPoint { x: 1, y: 1 });
+        let borrowed = curve.as_curve();
+        assert_eq!((borrowed.x_range, borrowed.y_range), (0..10, -5..5));
+    }
+
+    #[test]
+    fn plot_borrows_owned_curves() {
+        let mut plot = VecPlot::new(Scale::Auto, Scale::Auto);
+        let curve = VecCurve::from(vec![PlotPoint { x: 0, y: 0 }, PlotPoint { x: 4, y: 2 }]);
+        plot.push_curve(curve, BinaryColor::On);
+        plot.curves_mut()[0].0.push(PlotPoint { x: 8, y: 1 });
+        let x_range = plot.with_plot(|plot| plot.viewport().x_range());
+        assert_eq!(x_range, Ok(0..8));
+    }
+}
@@ -0,0 +1,106 @@
+//! Visible part of the data, independent of the full data ranges of the curves
+//!
+//! UIs can navigate long captures by zooming and panning the viewport without rebuilding curves.
+use core::ops::Range;
+
+use crate::curve::PlotPoint;
+
+/// X and Y sub-ranges of the data that are visible on the plot,
+/// descending range (start > end) inverts the direction of the axis
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Viewport {
+    x_start: i32,
+    x_end: i32,
+    y_start: i32,
+    y_end: i32,
+}
+
+impl Viewport {
+    pub fn new(x_range: Range<i32>, y_range: Range<i32>) -> Viewport {
+        Viewport {
+            x_start: x_range.start,
+            x_end: x_range.end,
+            y_start: y_range.start,
+            y_end: y_range.end,
+        }
+    }
+
+    pub fn x_range(&self) -> Range<i32> {
+        self.x_start..self.x_end
+    }
+
+    pub fn y_range(&self) -> Range<i32> {
+        self.y_start..self.y_end
+    }
+
+    /// zoom around `center` point, `factor_percent` above 100 zooms in (200 shows half of the data),
+    /// below 100 zooms out. Visible ranges never shrink below single unit and keep their direction.
+    pub fn zoom(&mut self, factor_percent: u32, center: PlotPoint) {
+        let factor = factor_percent.max(1) as i64;
+        let scale = |start: i32, end: i32, center: i32| {
+            let center = center as i64;
+            let new_start = center - (center - start as i64) * 100 / factor;
+            let new_end = center + (end as i64 - center) * 100 / factor;
+            let new_end = if new_end != new_start {
+                new_end
+            } else if end < start {
+                new_start - 1
+            } else {
+                new_start + 1
+            };
+            (clamp_i32(new_start), clamp_i32(new_end))
+        };
+        let (x_start, x_end) = scale(self.x_start, self.x_end, center.x);
+        let (y_start, y_end) = scale(self.y_start, self.y_end, center.y);
+        *self = Viewport {
+            x_start,
+            x_end,
+            y_start,
+            y_end,
+        };
+    }
+
+    /// move visible window by given distance in data units
+    pub fn pan(&mut self, dx: i32, dy: i32) {
+        self.x_start = self.x_start.saturating_add(dx);
+        self.x_end = self.x_end.saturating_add(dx);
+        self.y_start = self.y_start.saturating_add(dy);
+        self.y_end = self.y_end.saturating_add(dy);
+    }
+}
+
+fn clamp_i32(value: i64) -> i32 {
+    value.max(i32::MIN as i64).min(i32::MAX as i64) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::curve::PlotPoint;
+    use crate::viewport::Viewport;
+
+    #[test]
+    fn zoom_in_and_out() {
+        let mut viewport = Viewport::new(0..100, -50..50);
+        viewport.zoom(200, PlotPoint { x: 50, y: 0 });
+        assert_eq!(viewport, Viewport::new(25..75, -25..25));
+        viewport.zoom(50, PlotPoint { x: 25, y: 0 });
+        assert_eq!(viewport, Viewport::new(25..125, -50..50));
+    }
+
+    #[test]
+    fn zoom_never_empty() {
+        let mut viewport = Viewport::new(0..10, 0..10);
+        viewport.zoom(10000, PlotPoint { x: 5, y: 5 });
+        assert!(!viewport.x_range().is_empty());
+        assert!(!viewport.y_range().is_empty());
+    }
+
+    #[test]
+    fn pan() {
+        let mut viewport = Viewport::new(0..100, 0..10);
+        viewport.pan(10, -5);
+        assert_eq!(viewport, Viewport::new(10..110, -5..5));
+    }
+}